@@ -0,0 +1,72 @@
+/*
+ * bibo - Fast, local neural text-to-speech (C API)
+ *
+ * Link against libbibo (built with `cargo build --release --lib`).
+ * Failing calls return NULL or -1; bibo_last_error() then describes the
+ * failure on the calling thread. Strings returned by bibo are freed with
+ * bibo_string_free, engines with bibo_engine_free, audio with
+ * bibo_audio_free. Internal panics are caught and reported the same way;
+ * they never unwind into the caller.
+ */
+
+#ifndef BIBO_H
+#define BIBO_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct BiboEngine BiboEngine;
+typedef struct BiboAudio BiboAudio;
+
+/* Last error on this thread, or NULL. Valid until the next failing call. */
+const char *bibo_last_error(void);
+
+/* Library version, e.g. "0.3.0". Static; do not free. */
+const char *bibo_version(void);
+
+/* Free a string returned by bibo. */
+void bibo_string_free(char *s);
+
+/* Voice catalog as a JSON array (id, name, lang, languages, gender,
+ * quality, size_mb, num_speakers, installed), or NULL. */
+char *bibo_voices_json(void);
+
+/* Download a voice unless installed. Blocks. 0 on success, -1 on error. */
+int bibo_download_voice(const char *voice_id);
+
+/* Clean text for speaking; format is "md", "html", or "txt". */
+char *bibo_clean_text(const char *text, const char *format);
+
+/* Length scale for a speed preset ("slow", "normal", "fast") or rate
+ * multiplier ("1.5", 0.25-4.0). Returns -1 if invalid. */
+float bibo_length_scale(const char *spec);
+
+/* Create an engine for an installed voice, or NULL. */
+BiboEngine *bibo_engine_new(const char *voice_id);
+void bibo_engine_free(BiboEngine *engine);
+
+/* Select the speaker of a multi-speaker voice. */
+void bibo_engine_set_speaker(BiboEngine *engine, uint32_t speaker);
+
+/* Synthesize text; length_scale 1.0 is normal speed, larger is slower. */
+BiboAudio *bibo_synthesize(const BiboEngine *engine, const char *text, float length_scale);
+
+/* Synthesize straight to a WAV file. 0 on success, -1 on error. */
+int bibo_synthesize_to_file(const BiboEngine *engine, const char *text,
+                            float length_scale, const char *path);
+
+/* 16-bit mono samples, owned by the buffer. */
+const int16_t *bibo_audio_samples(const BiboAudio *audio);
+size_t bibo_audio_len(const BiboAudio *audio);
+uint32_t bibo_audio_sample_rate(const BiboAudio *audio);
+void bibo_audio_free(BiboAudio *audio);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* BIBO_H */
@@ -0,0 +1,205 @@
+"""Python bindings for bibo - fast, local neural text-to-speech.
+
+Same voices, catalog, and text cleaning as the ``bibo`` CLI, through the
+library's C ABI (``include/bibo.h``)::
+
+    import bibo
+
+    bibo.download_voice("ryan")
+    engine = bibo.Engine("ryan")
+    audio = engine.synthesize("Hello from Python", speed=1.2)
+    audio.save("hello.wav")
+
+In a notebook, ``IPython.display.Audio(audio.to_wav_bytes())`` plays it.
+"""
+
+import array
+import ctypes
+import io
+import json
+import os
+import sys
+import wave
+from pathlib import Path
+
+__all__ = [
+    "Audio",
+    "BiboError",
+    "Engine",
+    "clean_text",
+    "download_voice",
+    "version",
+    "voices",
+]
+
+
+class BiboError(RuntimeError):
+    """Raised when a bibo call fails."""
+
+
+def _find_library():
+    # BIBO_LIBRARY points at a locally built libbibo (cargo build --lib)
+    override = os.environ.get("BIBO_LIBRARY")
+    if override:
+        return override
+
+    here = Path(__file__).parent
+    for candidate in sorted(here.glob("_bibo*")):
+        if candidate.suffix in (".so", ".dylib", ".pyd", ".dll"):
+            return str(candidate)
+
+    suffix = {"darwin": "dylib", "win32": "dll"}.get(sys.platform, "so")
+    raise ImportError(
+        f"bibo native library not found in {here}; "
+        f"build the wheel or set BIBO_LIBRARY=/path/to/libbibo.{suffix}"
+    )
+
+
+_lib = ctypes.CDLL(_find_library())
+
+_c_char_p = ctypes.c_char_p
+_void_p = ctypes.c_void_p
+
+_SIGNATURES = {
+    "bibo_last_error": ([], _c_char_p),
+    "bibo_version": ([], _c_char_p),
+    "bibo_string_free": ([_void_p], None),
+    "bibo_voices_json": ([], _void_p),
+    "bibo_download_voice": ([_c_char_p], ctypes.c_int),
+    "bibo_clean_text": ([_c_char_p, _c_char_p], _void_p),
+    "bibo_length_scale": ([_c_char_p], ctypes.c_float),
+    "bibo_engine_new": ([_c_char_p], _void_p),
+    "bibo_engine_free": ([_void_p], None),
+    "bibo_engine_set_speaker": ([_void_p, ctypes.c_uint32], None),
+    "bibo_synthesize": ([_void_p, _c_char_p, ctypes.c_float], _void_p),
+    "bibo_synthesize_to_file": ([_void_p, _c_char_p, ctypes.c_float, _c_char_p], ctypes.c_int),
+    "bibo_audio_samples": ([_void_p], ctypes.POINTER(ctypes.c_int16)),
+    "bibo_audio_len": ([_void_p], ctypes.c_size_t),
+    "bibo_audio_sample_rate": ([_void_p], ctypes.c_uint32),
+    "bibo_audio_free": ([_void_p], None),
+}
+
+for _name, (_args, _restype) in _SIGNATURES.items():
+    _func = getattr(_lib, _name)
+    _func.argtypes = _args
+    _func.restype = _restype
+
+
+def _error():
+    message = _lib.bibo_last_error()
+    return BiboError(message.decode() if message else "unknown error")
+
+
+def _take_string(ptr):
+    """Copy a bibo-owned string into Python and free it."""
+    if not ptr:
+        raise _error()
+    try:
+        return ctypes.string_at(ptr).decode()
+    finally:
+        _lib.bibo_string_free(ptr)
+
+
+def _length_scale(speed):
+    """Preset ("fast") or rate multiplier (1.2) to a length scale."""
+    scale = _lib.bibo_length_scale(str(speed).encode())
+    if scale <= 0:
+        raise _error()
+    return scale
+
+
+def version():
+    """Version of the native bibo library."""
+    return _lib.bibo_version().decode()
+
+
+def voices():
+    """Voice catalog with an ``installed`` flag per voice."""
+    return json.loads(_take_string(_lib.bibo_voices_json()))
+
+
+def download_voice(voice_id):
+    """Download a voice unless it is already installed."""
+    if _lib.bibo_download_voice(voice_id.encode()) != 0:
+        raise _error()
+
+
+def clean_text(text, format="md"):
+    """Clean text the way the CLI does before speaking ("md", "html", or "txt")."""
+    return _take_string(_lib.bibo_clean_text(text.encode(), format.encode()))
+
+
+class Audio:
+    """Synthesized 16-bit mono audio."""
+
+    def __init__(self, samples, sample_rate):
+        self.samples = samples
+        self.sample_rate = sample_rate
+
+    @property
+    def duration(self):
+        """Length in seconds."""
+        return len(self.samples) / self.sample_rate if self.sample_rate else 0.0
+
+    def to_wav_bytes(self):
+        """Encode as a WAV file in memory."""
+        buf = io.BytesIO()
+        with wave.open(buf, "wb") as wav:
+            wav.setnchannels(1)
+            wav.setsampwidth(2)
+            wav.setframerate(self.sample_rate)
+            wav.writeframes(self.samples.tobytes())
+        return buf.getvalue()
+
+    def save(self, path):
+        """Write a WAV file."""
+        Path(path).write_bytes(self.to_wav_bytes())
+
+    def __repr__(self):
+        return f"Audio({self.duration:.2f}s, {self.sample_rate} Hz)"
+
+
+class Engine:
+    """Synthesis engine for one installed voice."""
+
+    def __init__(self, voice="melo", speaker=0):
+        self._ptr = _lib.bibo_engine_new(voice.encode())
+        if not self._ptr:
+            raise _error()
+        self.voice = voice
+        if speaker:
+            _lib.bibo_engine_set_speaker(self._ptr, speaker)
+
+    def synthesize(self, text, speed=1.0):
+        """Synthesize text to an :class:`Audio` buffer."""
+        ptr = _lib.bibo_synthesize(self._ptr, text.encode(), _length_scale(speed))
+        if not ptr:
+            raise _error()
+        try:
+            length = _lib.bibo_audio_len(ptr)
+            samples = array.array("h", ctypes.string_at(_lib.bibo_audio_samples(ptr), length * 2))
+            return Audio(samples, _lib.bibo_audio_sample_rate(ptr))
+        finally:
+            _lib.bibo_audio_free(ptr)
+
+    def synthesize_to_file(self, text, path, speed=1.0):
+        """Synthesize text straight to a WAV file."""
+        status = _lib.bibo_synthesize_to_file(
+            self._ptr, text.encode(), _length_scale(speed), os.fsencode(path)
+        )
+        if status != 0:
+            raise _error()
+
+    def close(self):
+        if self._ptr:
+            _lib.bibo_engine_free(self._ptr)
+            self._ptr = None
+
+    def __enter__(self):
+        return self
+
+    def __exit__(self, *exc):
+        self.close()
+
+    def __del__(self):
+        self.close()
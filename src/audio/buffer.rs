@@ -0,0 +1,293 @@
+//! In-memory audio buffers
+
+use crate::error::{BiboError, Result};
+use std::fs::File;
+use std::io::{BufWriter, Cursor, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Low-pass cutoff when lowering the rate, in cycles per output sample
+/// (0.5 is the new Nyquist frequency); the margin is the transition band
+const LOW_PASS_CUTOFF: f64 = 0.45;
+
+/// Zero crossings of the low-pass kernel on each side of its center
+const LOW_PASS_ZEROS: f64 = 8.0;
+
+/// Mono 16-bit PCM audio with its sample rate
+#[derive(Debug, Clone, Default)]
+pub struct AudioBuffer {
+    pub samples: Vec<i16>,
+    pub sample_rate: u32,
+}
+
+impl AudioBuffer {
+    pub fn new(samples: Vec<i16>, sample_rate: u32) -> Self {
+        Self {
+            samples,
+            sample_rate,
+        }
+    }
+
+    /// Silent buffer of the given length
+    pub fn silence(secs: f32, sample_rate: u32) -> Self {
+        let len = (secs.max(0.0) * sample_rate as f32) as usize;
+        Self::new(vec![0; len], sample_rate)
+    }
+
+    /// Append another buffer (must share the sample rate)
+    pub fn append(&mut self, other: &AudioBuffer) {
+        debug_assert_eq!(self.sample_rate, other.sample_rate);
+        self.samples.extend_from_slice(&other.samples);
+    }
+
+    /// Convert to another sample rate
+    ///
+    /// Linear interpolation; when lowering the rate, a windowed-sinc
+    /// low-pass first removes what the new rate cannot hold, so it does
+    /// not alias into audible noise.
+    pub fn resample(&self, sample_rate: u32) -> AudioBuffer {
+        if sample_rate == self.sample_rate || self.sample_rate == 0 || self.samples.is_empty() {
+            return Self::new(self.samples.clone(), sample_rate);
+        }
+        let ratio = self.sample_rate as f64 / sample_rate as f64;
+        let source: Vec<f64> = if ratio > 1.0 {
+            low_pass(&self.samples, LOW_PASS_CUTOFF / ratio)
+        } else {
+            self.samples.iter().map(|&s| s as f64).collect()
+        };
+        let len = (source.len() as f64 / ratio).round() as usize;
+        let last = source.len() - 1;
+        let samples = (0..len)
+            .map(|i| {
+                let pos = i as f64 * ratio;
+                let index = (pos as usize).min(last);
+                let next = (index + 1).min(last);
+                let frac = pos - index as f64;
+                let (a, b) = (source[index], source[next]);
+                (a + (b - a) * frac)
+                    .round()
+                    .clamp(i16::MIN as f64, i16::MAX as f64) as i16
+            })
+            .collect();
+        Self::new(samples, sample_rate)
+    }
+
+    /// Read a mono 16-bit WAV file
+    pub fn load(path: &Path) -> Result<Self> {
+        let reader = hound::WavReader::open(path)
+            .map_err(|e| BiboError::Other(format!("Failed to read WAV: {}", e)))?;
+        let sample_rate = reader.spec().sample_rate;
+
+        let samples: Vec<i16> = reader
+            .into_samples::<i16>()
+            .filter_map(|s| s.ok())
+            .collect();
+
+        Ok(Self::new(samples, sample_rate))
+    }
+
+    /// Write as a mono 16-bit WAV file
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let file = File::create(path)
+            .map_err(|e| BiboError::Other(format!("Failed to create WAV: {}", e)))?;
+        self.write_wav(BufWriter::new(file))
+    }
+
+    /// Encode as an in-memory WAV file
+    pub fn to_wav_bytes(&self) -> Result<Vec<u8>> {
+        let mut bytes = Cursor::new(Vec::new());
+        self.write_wav(&mut bytes)?;
+        Ok(bytes.into_inner())
+    }
+
+    /// Raw little-endian 16-bit PCM
+    pub fn to_pcm_bytes(&self) -> Vec<u8> {
+        self.samples.iter().flat_map(|s| s.to_le_bytes()).collect()
+    }
+
+    fn write_wav<W: Write + Seek>(&self, writer: W) -> Result<()> {
+        let mut writer = hound::WavWriter::new(writer, wav_spec(self.sample_rate))
+            .map_err(|e| BiboError::Other(format!("Failed to create WAV: {}", e)))?;
+        for &sample in &self.samples {
+            writer
+                .write_sample(sample)
+                .map_err(|e| BiboError::Other(format!("Failed to write WAV: {}", e)))?;
+        }
+        writer
+            .finalize()
+            .map_err(|e| BiboError::Other(format!("Failed to write WAV: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Duration in seconds
+    pub fn duration_secs(&self) -> f32 {
+        if self.sample_rate == 0 {
+            return 0.0;
+        }
+        self.samples.len() as f32 / self.sample_rate as f32
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+}
+
+/// Hann-windowed sinc low-pass at `cutoff` cycles per sample, with unit
+/// gain at DC; samples past either end repeat the edge sample
+fn low_pass(samples: &[i16], cutoff: f64) -> Vec<f64> {
+    let half = (LOW_PASS_ZEROS / (2.0 * cutoff)).ceil() as isize;
+    let mut kernel: Vec<f64> = (-half..=half)
+        .map(|k| {
+            let x = 2.0 * cutoff * k as f64;
+            let sinc = if k == 0 {
+                1.0
+            } else {
+                (std::f64::consts::PI * x).sin() / (std::f64::consts::PI * x)
+            };
+            let window = 0.5 + 0.5 * (std::f64::consts::PI * k as f64 / (half + 1) as f64).cos();
+            sinc * window
+        })
+        .collect();
+    let sum: f64 = kernel.iter().sum();
+    kernel.iter_mut().for_each(|tap| *tap /= sum);
+
+    let last = samples.len() as isize - 1;
+    (0..samples.len() as isize)
+        .map(|i| {
+            kernel
+                .iter()
+                .zip(i - half..)
+                .map(|(tap, j)| tap * samples[j.clamp(0, last) as usize] as f64)
+                .sum()
+        })
+        .collect()
+}
+
+/// Mono 16-bit PCM, the format of every buffer
+fn wav_spec(sample_rate: u32) -> hound::WavSpec {
+    hound::WavSpec {
+        channels: 1,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    }
+}
+
+/// Size of a 16-bit PCM WAV header
+const WAV_HEADER_BYTES: u64 = 44;
+
+fn too_long() -> BiboError {
+    BiboError::Other("Audio too long for a single WAV file (4 GiB limit)".to_string())
+}
+
+/// WAV file written clip by clip, so long audio never sits in memory whole
+///
+/// Clips are resampled to the file's rate. Going past the 4 GiB a WAV
+/// header can describe is an error rather than a corrupt file.
+pub struct WavFileWriter {
+    writer: hound::WavWriter<BufWriter<File>>,
+    path: PathBuf,
+    sample_rate: u32,
+    samples: u64,
+}
+
+impl WavFileWriter {
+    pub fn create(path: &Path, sample_rate: u32) -> Result<Self> {
+        let writer = hound::WavWriter::create(path, wav_spec(sample_rate))
+            .map_err(|e| BiboError::Other(format!("Failed to create WAV: {}", e)))?;
+        Ok(Self {
+            writer,
+            path: path.to_path_buf(),
+            sample_rate,
+            samples: 0,
+        })
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Samples written so far
+    pub fn samples_written(&self) -> u64 {
+        self.samples
+    }
+
+    /// Duration written so far in seconds
+    pub fn duration_secs(&self) -> f32 {
+        self.samples as f32 / self.sample_rate.max(1) as f32
+    }
+
+    /// Append a clip
+    pub fn append(&mut self, audio: &AudioBuffer) -> Result<()> {
+        let audio = audio.resample(self.sample_rate);
+        let samples = self.samples + audio.samples.len() as u64;
+        if WAV_HEADER_BYTES + samples * 2 > u32::MAX as u64 {
+            return Err(too_long());
+        }
+        for &sample in &audio.samples {
+            self.writer
+                .write_sample(sample)
+                .map_err(|e| BiboError::Other(format!("Failed to write WAV: {}", e)))?;
+        }
+        self.samples = samples;
+        Ok(())
+    }
+
+    /// Finish the file with named markers (cue points) at sample offsets,
+    /// such as chapter starts
+    pub fn finish(self, markers: &[(u64, &str)]) -> Result<()> {
+        self.writer
+            .finalize()
+            .map_err(|e| BiboError::Other(format!("Failed to write WAV: {}", e)))?;
+        if markers.is_empty() {
+            return Ok(());
+        }
+        append_markers(&self.path, markers)
+    }
+}
+
+/// Add `cue ` and `LIST`/`adtl` label chunks to a WAV file and fix up the
+/// RIFF size; players and editors show these as markers
+fn append_markers(path: &Path, markers: &[(u64, &str)]) -> Result<()> {
+    let mut cue = (markers.len() as u32).to_le_bytes().to_vec();
+    let mut labels = b"adtl".to_vec();
+    for (id, (sample, label)) in (1u32..).zip(markers) {
+        // Below 2^31: the data chunk is limited to 4 GiB of 16-bit samples
+        let sample = *sample as u32;
+        cue.extend(id.to_le_bytes());
+        cue.extend(sample.to_le_bytes());
+        cue.extend(b"data");
+        cue.extend(0u32.to_le_bytes());
+        cue.extend(0u32.to_le_bytes());
+        cue.extend(sample.to_le_bytes());
+
+        let mut text = label.as_bytes().to_vec();
+        text.push(0);
+        labels.extend(b"labl");
+        labels.extend((4 + text.len() as u32).to_le_bytes());
+        labels.extend(id.to_le_bytes());
+        labels.extend(&text);
+        // Chunks are word aligned
+        if text.len() % 2 == 1 {
+            labels.push(0);
+        }
+    }
+    let mut chunks = vec![];
+    for (id, chunk) in [(b"cue ", cue), (b"LIST", labels)] {
+        chunks.extend(id);
+        chunks.extend((chunk.len() as u32).to_le_bytes());
+        chunks.extend(chunk);
+    }
+
+    let write_error = |e: std::io::Error| BiboError::Other(format!("Failed to write WAV: {}", e));
+    let mut file = File::options()
+        .write(true)
+        .open(path)
+        .map_err(write_error)?;
+    let len = file.seek(SeekFrom::End(0)).map_err(write_error)? + chunks.len() as u64;
+    let riff_size = u32::try_from(len - 8).map_err(|_| too_long())?;
+    file.write_all(&chunks).map_err(write_error)?;
+    file.seek(SeekFrom::Start(4)).map_err(write_error)?;
+    file.write_all(&riff_size.to_le_bytes())
+        .map_err(write_error)
+}
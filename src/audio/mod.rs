@@ -1,5 +1,7 @@
 //! Audio playback module
 
+pub mod buffer;
 pub mod player;
 
-pub use player::AudioPlayer;
+pub use buffer::{AudioBuffer, WavFileWriter};
+pub use player::{AudioOutput, AudioPlayer, PlaybackLock};
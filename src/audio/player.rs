@@ -1,10 +1,11 @@
 //! Cross-platform audio playback
 
 use crate::error::{BiboError, Result};
+use rodio::buffer::SamplesBuffer;
 use rodio::{Decoder, OutputStream, Sink};
 use std::fs::File;
 use std::io::BufReader;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Audio player for WAV files
 pub struct AudioPlayer;
@@ -36,22 +37,100 @@ impl AudioPlayer {
 
     /// Play raw audio samples
     pub fn play_samples(samples: Vec<i16>, sample_rate: u32) -> Result<()> {
-        use rodio::buffer::SamplesBuffer;
+        let output = AudioOutput::new()?;
+        output.play(&samples, sample_rate);
+        Ok(())
+    }
+}
 
-        let (_stream, stream_handle) = OutputStream::try_default()
+/// Long-lived audio output for continuous playback
+///
+/// Keeps one output stream open so consecutive clips play back to back
+/// without reopening the audio device.
+pub struct AudioOutput {
+    _stream: OutputStream,
+    sink: Sink,
+}
+
+impl AudioOutput {
+    /// Open the default audio output device
+    pub fn new() -> Result<Self> {
+        let (stream, stream_handle) = OutputStream::try_default()
             .map_err(|e| BiboError::PlaybackFailed(format!("Failed to get audio output: {}", e)))?;
 
         let sink = Sink::try_new(&stream_handle).map_err(|e| {
             BiboError::PlaybackFailed(format!("Failed to create audio sink: {}", e))
         })?;
 
+        Ok(Self {
+            _stream: stream,
+            sink,
+        })
+    }
+
+    /// Queue samples for playback without waiting
+    pub fn append(&self, samples: &[i16], sample_rate: u32) {
         // Convert i16 to f32 for rodio
         let samples_f32: Vec<f32> = samples.iter().map(|&s| s as f32 / 32768.0).collect();
-        let source = SamplesBuffer::new(1, sample_rate, samples_f32);
+        self.sink
+            .append(SamplesBuffer::new(1, sample_rate, samples_f32));
+    }
 
-        sink.append(source);
-        sink.sleep_until_end();
+    /// Play samples and wait until playback finishes
+    pub fn play(&self, samples: &[i16], sample_rate: u32) {
+        self.append(samples, sample_rate);
+        self.wait();
+    }
 
-        Ok(())
+    /// Block until all queued audio has played
+    pub fn wait(&self) {
+        self.sink.sleep_until_end();
+    }
+
+    /// Drop all queued audio immediately
+    pub fn stop(&self) {
+        self.sink.clear();
+        self.sink.play();
+    }
+
+    /// Whether any audio is still queued or playing
+    pub fn is_playing(&self) -> bool {
+        !self.sink.empty()
+    }
+}
+
+/// Cross-process playback lock
+///
+/// Every bibo process that plays audio holds it while playing: the CLI,
+/// the REPL, stream and book modes, the daemon, MCP, and speechd. Their
+/// audio then takes turns on the speaker instead of overlapping. Released
+/// on drop.
+pub struct PlaybackLock {
+    _file: File,
+}
+
+impl PlaybackLock {
+    /// Lock file location, next to the daemon socket
+    pub fn path() -> PathBuf {
+        dirs::runtime_dir()
+            .or_else(dirs::cache_dir)
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("bibo")
+            .join("playback.lock")
+    }
+
+    /// Wait until no other bibo process is playing, then hold the speaker
+    pub fn acquire() -> Result<Self> {
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                BiboError::PlaybackFailed(format!("Failed to create lock dir: {}", e))
+            })?;
+        }
+        let file = File::create(&path)
+            .map_err(|e| BiboError::PlaybackFailed(format!("Failed to open lock file: {}", e)))?;
+        file.lock()
+            .map_err(|e| BiboError::PlaybackFailed(format!("Failed to lock playback: {}", e)))?;
+        Ok(Self { _file: file })
     }
 }
@@ -0,0 +1,26 @@
+//! sd_bibo - speech-dispatcher output module
+//!
+//! speech-dispatcher launches modules by file name from its modules
+//! directory. This shim runs `bibo speechd` from next to itself (or PATH).
+
+use std::os::unix::process::CommandExt;
+use std::path::PathBuf;
+use std::process::{exit, Command};
+
+fn main() {
+    let sibling = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.canonicalize().ok())
+        .map(|exe| exe.with_file_name("bibo"))
+        .filter(|bibo| bibo.exists());
+    let bibo = sibling.unwrap_or_else(|| PathBuf::from("bibo"));
+
+    // speech-dispatcher passes the module config file as the only argument
+    let error = Command::new(&bibo)
+        .arg("speechd")
+        .args(std::env::args_os().skip(1))
+        .exec();
+
+    eprintln!("sd_bibo: failed to run {}: {}", bibo.display(), error);
+    exit(1);
+}
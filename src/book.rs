@@ -0,0 +1,245 @@
+//! Book mode - EPUB chapters to audio, one chapter at a time
+//!
+//! `-o DIR/` writes a numbered WAV per chapter ("01 Title.wav"); `-o FILE.wav`
+//! writes one file with a cue marker at each chapter start. Finished
+//! chapters are recorded in a progress file, so an interrupted run resumes
+//! at the first chapter still missing. A chapter is rendered again when its
+//! text, voice, speaker, or speed changed since it was written.
+
+use bibo::audio::{AudioBuffer, AudioPlayer, PlaybackLock, WavFileWriter};
+use bibo::error::{BiboError, Result};
+use bibo::text::Segment;
+use bibo::tts::TtsEngine;
+use colored::Colorize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Silence between chapters in a single output file
+const CHAPTER_GAP_SECS: f32 = 1.5;
+
+/// Progress file in the chapter directory
+const PROGRESS_FILE: &str = ".bibo-progress.json";
+
+/// A chapter's title and what to say
+pub struct Chapter {
+    pub title: String,
+    pub segments: Vec<Segment>,
+}
+
+/// Play the chapters, or write them to `output`
+pub fn run(
+    engine: &TtsEngine,
+    chapters: &[Chapter],
+    output: Option<&str>,
+    length_scale: f32,
+    quiet: bool,
+) -> Result<()> {
+    let Some(output) = output else {
+        return play(engine, chapters, length_scale, quiet);
+    };
+    let directory = output.ends_with(std::path::is_separator);
+    let output = Path::new(output);
+    let single = output
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("wav"));
+    if !single && !directory && !output.is_dir() {
+        return Err(BiboError::Other(format!(
+            "Output must be a .wav file or a directory: {} (use {}/ for a new directory)",
+            output.display(),
+            output.display()
+        )));
+    }
+    if !single {
+        write_chapters(engine, chapters, output, length_scale, quiet)?;
+        if !quiet {
+            println!(
+                "{} Saved {} chapters to {}",
+                "✅".green(),
+                chapters.len(),
+                output.display()
+            );
+        }
+        return Ok(());
+    }
+
+    // Chapters are written beside the output first, so a single file resumes too
+    let name = output.file_name().unwrap_or_default().to_string_lossy();
+    let parts = output.with_file_name(format!(".{}.parts", name));
+    let files = write_chapters(engine, chapters, &parts, length_scale, quiet)?;
+
+    let temp = output.with_file_name(format!(".{}.tmp", name));
+    let duration_secs = match join_parts(engine, chapters, &files, &temp) {
+        Ok(duration_secs) => duration_secs,
+        Err(e) => {
+            let _ = fs::remove_file(&temp);
+            return Err(e);
+        }
+    };
+    fs::rename(&temp, output)
+        .map_err(|e| BiboError::Other(format!("Failed to save {}: {}", output.display(), e)))?;
+    let _ = fs::remove_dir_all(&parts);
+
+    if !quiet {
+        println!(
+            "{} Saved: {} ({} chapters, {:.1}s audio)",
+            "✅".green(),
+            output.display(),
+            chapters.len(),
+            duration_secs
+        );
+    }
+    Ok(())
+}
+
+/// Stream the chapter files into one WAV with a marker at each chapter,
+/// holding a single chapter in memory; returns the duration in seconds
+fn join_parts(
+    engine: &TtsEngine,
+    chapters: &[Chapter],
+    files: &[PathBuf],
+    output: &Path,
+) -> Result<f32> {
+    let mut writer: Option<WavFileWriter> = None;
+    let mut markers = vec![];
+    for (chapter, file) in chapters.iter().zip(files) {
+        let part = AudioBuffer::load(file)?;
+        // The first chapter sets the output rate
+        let writer = match &mut writer {
+            Some(writer) => writer,
+            None => writer.insert(WavFileWriter::create(output, part.sample_rate)?),
+        };
+        if writer.samples_written() > 0 {
+            writer.append(&AudioBuffer::silence(
+                CHAPTER_GAP_SECS,
+                writer.sample_rate(),
+            ))?;
+        }
+        markers.push((writer.samples_written(), chapter.title.as_str()));
+        writer.append(&part)?;
+    }
+    let writer = match writer {
+        Some(writer) => writer,
+        None => WavFileWriter::create(output, engine.sample_rate())?,
+    };
+    let duration_secs = writer.duration_secs();
+    writer.finish(&markers)?;
+    Ok(duration_secs)
+}
+
+/// Speak each chapter in turn
+fn play(engine: &TtsEngine, chapters: &[Chapter], length_scale: f32, quiet: bool) -> Result<()> {
+    let _lock = PlaybackLock::acquire()?;
+    for (i, chapter) in chapters.iter().enumerate() {
+        if !quiet {
+            println!(
+                "{} [{}/{}] {}",
+                "📖".cyan(),
+                i + 1,
+                chapters.len(),
+                chapter.title
+            );
+        }
+        let audio = engine.synthesize_segments(&chapter.segments, length_scale)?;
+        AudioPlayer::play_samples(audio.samples, audio.sample_rate)?;
+    }
+    Ok(())
+}
+
+/// Write one WAV per chapter into `dir`, skipping chapters already written
+/// with the same settings; returns the files in chapter order
+fn write_chapters(
+    engine: &TtsEngine,
+    chapters: &[Chapter],
+    dir: &Path,
+    length_scale: f32,
+    quiet: bool,
+) -> Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir)
+        .map_err(|e| BiboError::Other(format!("Failed to create {}: {}", dir.display(), e)))?;
+    let progress_path = dir.join(PROGRESS_FILE);
+    let mut progress: HashMap<String, String> = fs::read_to_string(&progress_path)
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default();
+
+    let width = chapters.len().to_string().len().max(2);
+    let mut files = vec![];
+    let mut resumed = false;
+    for (i, chapter) in chapters.iter().enumerate() {
+        let name = format!("{:0width$} {}.wav", i + 1, file_title(&chapter.title));
+        let path = dir.join(&name);
+        let digest = digest(engine, chapter, length_scale);
+        let label = format!("[{}/{}]", i + 1, chapters.len());
+
+        if progress.get(&name) == Some(&digest) && path.exists() {
+            resumed = true;
+            files.push(path);
+            continue;
+        }
+        if resumed && !quiet {
+            println!("{} Resuming at chapter {}", "⏩".cyan(), i + 1);
+        }
+        resumed = false;
+        if !quiet {
+            println!("{} {} {}", "🎧".cyan(), label, chapter.title);
+        }
+
+        let audio = engine.synthesize_segments(&chapter.segments, length_scale)?;
+        // A chapter file only appears once it is complete
+        let temp = dir.join(format!(".{}.tmp", name));
+        audio.save(&temp)?;
+        fs::rename(&temp, &path)
+            .map_err(|e| BiboError::Other(format!("Failed to save {}: {}", path.display(), e)))?;
+
+        progress.insert(name, digest);
+        let json = serde_json::to_string_pretty(&progress)
+            .map_err(|e| BiboError::Other(format!("Failed to save progress: {}", e)))?;
+        fs::write(&progress_path, json)
+            .map_err(|e| BiboError::Other(format!("Failed to save progress: {}", e)))?;
+        files.push(path);
+    }
+    Ok(files)
+}
+
+/// Digest of everything that changes a chapter's audio
+fn digest(engine: &TtsEngine, chapter: &Chapter, length_scale: f32) -> String {
+    let key = format!(
+        "{}|{}|{}|{:?}",
+        engine.voice_id(),
+        engine.speaker(),
+        length_scale.to_bits(),
+        chapter.segments
+    );
+    Sha256::digest(key.as_bytes())
+        .iter()
+        .take(8)
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Chapter title usable in a file name
+fn file_title(title: &str) -> String {
+    let cleaned: String = title
+        .chars()
+        .filter(|c| !c.is_control() && !r#"/\:*?"<>|"#.contains(*c))
+        .collect();
+    let words: Vec<&str> = cleaned.split_whitespace().collect();
+    let mut name = String::new();
+    for word in words {
+        if name.chars().count() + word.chars().count() > 60 {
+            break;
+        }
+        if !name.is_empty() {
+            name.push(' ');
+        }
+        name.push_str(word);
+    }
+    let name = name.trim_end_matches('.').to_string();
+    if name.is_empty() {
+        "Chapter".to_string()
+    } else {
+        name
+    }
+}
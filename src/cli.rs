@@ -2,31 +2,93 @@
 //!
 //! YC Standard: Single-letter shortcuts, intuitive defaults
 
-use clap::{Parser, ValueEnum};
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, ValueEnum)]
-pub enum Speed {
-    Slow,
+pub use bibo::input::lines::Overflow;
+pub use bibo::input::InputFormat;
+pub use bibo::text::code::CodeMode;
+pub use bibo::tts::{parse_length_scale, Speed};
+
+/// Speech priority when several messages compete for the speaker
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, ValueEnum, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    /// Background reading, played after everything else
+    Low,
+    #[default]
     Normal,
-    Fast,
+    /// Played before queued normal messages
+    High,
+    /// Interrupts lower-priority speech, which resumes afterwards
+    Urgent,
 }
 
-impl Speed {
-    /// Convert to length scale for Piper
-    /// Lower = faster speech
-    pub fn to_length_scale(&self) -> f32 {
-        match self {
-            Speed::Slow => 1.2,
-            Speed::Normal => 1.0,
-            Speed::Fast => 0.8,
-        }
-    }
+/// Subcommands
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Manage the synthesis cache
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+    /// Run an HTTP server with an OpenAI-compatible /v1/audio/speech endpoint
+    Serve {
+        /// Address to bind
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+        /// Port to listen on (0 picks a free port)
+        #[arg(short, long, default_value_t = 8000)]
+        port: u16,
+    },
+    /// Run a Wyoming protocol TTS server (Home Assistant)
+    Wyoming {
+        /// Address to bind
+        #[arg(long, default_value = "0.0.0.0")]
+        host: String,
+        /// Port to listen on (0 picks a free port)
+        #[arg(short, long, default_value_t = 10200)]
+        port: u16,
+    },
+    /// Run an MCP server on stdio so LLM agents can speak
+    Mcp,
+    /// Run as a speech-dispatcher output module (launched via sd_bibo)
+    Speechd {
+        /// Module config file passed by speech-dispatcher (unused)
+        #[arg(value_name = "CONFIG")]
+        config: Option<String>,
+    },
+    /// Run the background daemon, or control a running one
+    Daemon {
+        #[command(subcommand)]
+        action: Option<DaemonAction>,
+    },
 }
 
-impl Default for Speed {
-    fn default() -> Self {
-        Speed::Normal
-    }
+/// Daemon control actions
+#[derive(Subcommand, Debug, Clone, Copy)]
+pub enum DaemonAction {
+    /// Show whether the daemon is running
+    Status,
+    /// Show what is playing and what is queued
+    Queue,
+    /// Skip the current utterance
+    Skip,
+    /// Stop playback and clear the queue
+    Stop,
+    /// Stop the daemon
+    Shutdown,
+}
+
+/// Cache actions
+#[derive(Subcommand, Debug, Clone, Copy)]
+pub enum CacheAction {
+    /// Show cache size and location
+    Stats,
+    /// Remove all cached audio
+    Clear,
 }
 
 /// Bibo - Fast, local neural text-to-speech
@@ -39,41 +101,107 @@ impl Default for Speed {
     bibo "Hello world"              Just works
     bibo "Hello" -s fast            Fast speech
     bibo -i doc.md                  Read from file
+    git log -3 | bibo               Read from stdin
+    cat notes.md | bibo --format md Read Markdown from stdin
+    bibo -i page.html               Read a saved web page's article
+    bibo -i book.epub -o book/      Audiobook, one WAV per chapter
+    bibo -i README.md --code announce
+                                    Say which code blocks are skipped
+    bibo -i script.ssml             Pauses, rates, and voices from SSML
+    bibo --print-normalized 'Due 2025-03-14, $1,299.99'
+                                    Show how numbers will be read
+    bibo --detect-lang '你好, 안녕하세요, hello'
+                                    Each language in its own voice
+    bibo -i scene.dialogue -o scene.wav
+                                    One voice per character
+    bibo -i doc.md -o doc.wav --watch
+                                    Re-render audio on every save
+    bibo --interactive              Type lines to speak, :help for commands
+    tail -f build.log | bibo --stream --match 'error|warn'
+                                    Speak live log lines
     bibo -d list                    Show available voices
     bibo -d amy                     Download voice
     bibo -l                         List installed voices
+    bibo cache stats                Show synthesis cache usage
+    bibo serve -p 8000              OpenAI-compatible speech API
+    bibo wyoming                    Home Assistant TTS on port 10200
+    bibo mcp                        MCP tools for LLM agents (stdio)
+    bibo daemon &                   Keep engines loaded; bibo "..." queues
+    bibo daemon queue               Show what the daemon is playing
+    bibo --priority urgent "Build failed"
+                                    Interrupt whatever is being read
 
 ENVIRONMENT VARIABLES:
     BIBO_VOICE    Default voice (default: melo)
     BIBO_SPEED    Default speed (default: normal)
+    BIBO_NO_CACHE Disable the synthesis cache
+    BIBO_CACHE_MAX_MB  Synthesis cache size limit (default: 256)
+    BIBO_PRIORITY      Default priority (default: normal)
+    BIBO_NO_DAEMON     Never forward playback to a running daemon
+    BIBO_SOCKET        Daemon socket path
+    BIBO_CONFIG        Config file (default: ~/.config/bibo/config.toml)
 "#)]
 pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Text to speak
     #[arg(value_name = "TEXT")]
     pub text: Option<String>,
 
     /// Voice model to use
-    #[arg(short, long, env = "BIBO_VOICE", default_value = "melo")]
+    #[arg(short, long, env = "BIBO_VOICE", default_value = "melo", global = true)]
     pub voice: String,
 
     /// Speech speed
-    #[arg(short, long, env = "BIBO_SPEED", value_enum, default_value = "normal")]
+    #[arg(
+        short,
+        long,
+        env = "BIBO_SPEED",
+        value_enum,
+        default_value = "normal",
+        global = true
+    )]
     pub speed: Speed,
 
+    /// Speaker ID for multi-speaker voices
+    #[arg(long, value_name = "ID", default_value_t = 0)]
+    pub speaker: u32,
+
     /// Fast mode (shortcut for -s fast)
     #[arg(short = 'f', long)]
     pub fast: bool,
 
-    /// Input file (.md or .txt)
+    /// Input file (.md, .txt, .html, .epub, .ssml, or .dialogue, "-" for stdin)
     #[arg(short, long, value_name = "FILE")]
     pub input: Option<String>,
 
-    /// Output WAV file (plays if not specified)
+    /// Input format (auto detects from file extension)
+    #[arg(long, value_enum, default_value = "auto", value_name = "FORMAT")]
+    pub format: InputFormat,
+
+    /// Read the input as SSML (same as --format ssml)
+    #[arg(long)]
+    pub ssml: bool,
+
+    /// How Markdown code blocks are read (overrides the config file)
+    #[arg(long, value_enum, value_name = "MODE")]
+    pub code: Option<CodeMode>,
+
+    /// Detect each language in the text and speak it in a voice for that language
+    #[arg(long)]
+    pub detect_lang: bool,
+
+    /// Print the text as it will be spoken (numbers, dates, money in words) and exit
+    #[arg(long)]
+    pub print_normalized: bool,
+
+    /// Output WAV file (plays if not specified); a directory for EPUB chapters
     #[arg(short, long, value_name = "FILE")]
     pub output: Option<String>,
 
     /// Quiet mode (no output)
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     pub quiet: bool,
 
     /// List installed voices
@@ -84,9 +212,49 @@ pub struct Cli {
     #[arg(short, long, value_name = "SPEC")]
     pub download: Option<String>,
 
+    /// Always synthesize, bypassing the audio cache
+    #[arg(long, env = "BIBO_NO_CACHE", global = true)]
+    pub no_cache: bool,
+
+    /// Priority in the daemon queue: low, normal, high, urgent
+    #[arg(long, value_enum, env = "BIBO_PRIORITY", default_value = "normal")]
+    pub priority: Priority,
+
+    /// Play in-process even when a daemon is running
+    #[arg(long, env = "BIBO_NO_DAEMON")]
+    pub no_daemon: bool,
+
     /// Remove all downloaded models and engine data
     #[arg(long)]
     pub clean: bool,
+
+    /// Interactive mode: speak each entered line, with :commands
+    #[arg(long, conflicts_with_all = ["text", "input", "output", "stream"])]
+    pub interactive: bool,
+
+    /// Watch the input file and re-render the output on change
+    #[arg(long, requires_all = ["input", "output"], conflicts_with_all = ["stream", "interactive"])]
+    pub watch: bool,
+
+    /// Stream mode: speak each stdin line as it arrives
+    #[arg(long, conflicts_with_all = ["text", "input", "output"])]
+    pub stream: bool,
+
+    /// Maximum lines waiting to be spoken in stream mode
+    #[arg(long, value_name = "N", default_value_t = 8)]
+    pub queue_size: usize,
+
+    /// Policy when input outpaces speech in stream mode
+    #[arg(long, value_enum, default_value = "drop-oldest")]
+    pub overflow: Overflow,
+
+    /// Only speak lines matching this regex (repeatable)
+    #[arg(long = "match", value_name = "REGEX")]
+    pub match_patterns: Vec<String>,
+
+    /// Skip lines matching this regex (repeatable)
+    #[arg(long = "exclude", value_name = "REGEX")]
+    pub exclude_patterns: Vec<String>,
 }
 
 impl Cli {
@@ -95,7 +263,16 @@ impl Cli {
         if self.fast {
             Speed::Fast
         } else {
-            self.speed.clone()
+            self.speed
+        }
+    }
+
+    /// Get effective input format (considering --ssml)
+    pub fn effective_format(&self) -> InputFormat {
+        if self.ssml {
+            InputFormat::Ssml
+        } else {
+            self.format
         }
     }
 }
@@ -0,0 +1,73 @@
+//! User configuration from `config.toml` in the bibo config dir
+//!
+//! Every setting is optional; a missing file means defaults.
+//!
+//! ```toml
+//! [markdown]
+//! heading_pause = 1.0
+//! links = "domain"
+//! ```
+
+use crate::error::{BiboError, Result};
+use crate::text::normalize::symbols;
+use crate::text::{language, markdown};
+use serde::Deserialize;
+use std::fs;
+use std::path::PathBuf;
+
+/// Settings read from `config.toml`
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// How Markdown structure is read aloud
+    pub markdown: markdown::Rules,
+    /// Voices for the languages found in the text
+    pub languages: language::Languages,
+    /// How emoji, symbols, and URLs are read
+    pub symbols: symbols::Rules,
+}
+
+impl Config {
+    /// Config directory (`~/.config/bibo` on Linux)
+    pub fn dir() -> PathBuf {
+        dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("bibo")
+    }
+
+    /// Config file path (BIBO_CONFIG overrides)
+    pub fn path() -> PathBuf {
+        match std::env::var("BIBO_CONFIG") {
+            Ok(path) => PathBuf::from(path),
+            Err(_) => Self::dir().join("config.toml"),
+        }
+    }
+
+    /// File or directory next to the config file
+    fn beside(name: &str) -> PathBuf {
+        match Self::path().parent() {
+            Some(dir) => dir.join(name),
+            None => Self::dir().join(name),
+        }
+    }
+
+    /// User dictionaries, in `dictionaries/` next to the config file
+    pub fn dictionary_dir() -> PathBuf {
+        Self::beside("dictionaries")
+    }
+
+    /// User pronunciations, `pronunciations.toml` next to the config file
+    pub fn pronunciation_path() -> PathBuf {
+        Self::beside("pronunciations.toml")
+    }
+
+    /// Load the config file, or defaults if there is none
+    pub fn load() -> Result<Self> {
+        let path = Self::path();
+        let Ok(content) = fs::read_to_string(&path) else {
+            return Ok(Self::default());
+        };
+        toml::from_str(&content)
+            .map_err(|e| BiboError::ConfigError(format!("{}: {}", path.display(), e.message())))
+    }
+}
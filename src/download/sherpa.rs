@@ -2,38 +2,37 @@
 //!
 //! Auto-download sherpa-onnx TTS engine on first run
 
+use super::{download_file, Progress};
 use crate::error::{BiboError, Result};
 use crate::tts::sherpa::{sherpa_bin_dir, sherpa_download_url, sherpa_tts_path};
-use colored::Colorize;
-use futures_util::StreamExt;
-use indicatif::{ProgressBar, ProgressStyle};
 use std::path::Path;
-use tokio::fs::File;
-use tokio::io::AsyncWriteExt;
+
+/// Name reported in progress events
+const NAME: &str = "sherpa-onnx";
 
 /// Sherpa-onnx downloader
 pub struct SherpaDownloader;
 
 impl SherpaDownloader {
     /// Download and install sherpa-onnx binary
-    pub async fn download(quiet: bool) -> Result<()> {
+    pub async fn download(mut on_progress: impl FnMut(Progress)) -> Result<()> {
         let sherpa_path = sherpa_tts_path();
 
         // Check if already installed
         if sherpa_path.exists() {
-            if !quiet {
-                println!("{} Sherpa-onnx already installed", "✅".green());
-            }
+            on_progress(Progress::AlreadyInstalled {
+                name: NAME.to_string(),
+            });
             return Ok(());
         }
 
         let url = sherpa_download_url();
         let bin_dir = sherpa_bin_dir();
 
-        if !quiet {
-            println!("{} Downloading sherpa-onnx TTS engine...", "📦".cyan());
-            println!("   From: {}", url);
-        }
+        on_progress(Progress::Started {
+            name: NAME.to_string(),
+            url: url.to_string(),
+        });
 
         // Create bin directory
         tokio::fs::create_dir_all(&bin_dir)
@@ -42,12 +41,10 @@ impl SherpaDownloader {
 
         // Download to temp file
         let temp_tar = bin_dir.join("sherpa_temp.tar.bz2");
-        Self::download_file(url, &temp_tar, quiet).await?;
+        download_file(url, &temp_tar, &mut on_progress).await?;
 
         // Extract tar.bz2
-        if !quiet {
-            println!("   {} Extracting...", "📂".cyan());
-        }
+        on_progress(Progress::Extracting);
 
         Self::extract_tar_bz2(&temp_tar, &bin_dir).await?;
 
@@ -73,71 +70,9 @@ impl SherpaDownloader {
             }
         }
 
-        if !quiet {
-            println!("{} Sherpa-onnx installed successfully!", "✅".green());
-        }
-
-        Ok(())
-    }
-
-    /// Download a file with progress
-    async fn download_file(url: &str, dest: &Path, quiet: bool) -> Result<()> {
-        let client = reqwest::Client::new();
-        let response = client
-            .get(url)
-            .header("User-Agent", "Bibo-TTS/1.0")
-            .send()
-            .await
-            .map_err(|e| BiboError::DownloadFailed(e.to_string()))?;
-
-        if !response.status().is_success() {
-            return Err(BiboError::DownloadFailed(format!(
-                "HTTP {}",
-                response.status()
-            )));
-        }
-
-        let total_size = response.content_length().unwrap_or(0);
-
-        // Create progress bar
-        let pb = if !quiet && total_size > 0 {
-            let pb = ProgressBar::new(total_size);
-            pb.set_style(
-                ProgressStyle::default_bar()
-                    .template("   [{bar:30.cyan/blue}] {bytes}/{total_bytes} ({percent}%)")
-                    .unwrap()
-                    .progress_chars("█░"),
-            );
-            Some(pb)
-        } else {
-            None
-        };
-
-        // Download with streaming
-        let mut file = File::create(dest)
-            .await
-            .map_err(|e| BiboError::DownloadFailed(format!("Failed to create file: {}", e)))?;
-
-        let mut stream = response.bytes_stream();
-        let mut downloaded: u64 = 0;
-
-        while let Some(chunk) = stream.next().await {
-            let chunk =
-                chunk.map_err(|e| BiboError::DownloadFailed(format!("Stream error: {}", e)))?;
-            file.write_all(&chunk)
-                .await
-                .map_err(|e| BiboError::DownloadFailed(format!("Write error: {}", e)))?;
-
-            downloaded += chunk.len() as u64;
-            if let Some(ref pb) = pb {
-                pb.set_position(downloaded);
-            }
-        }
-
-        if let Some(pb) = pb {
-            pb.finish_and_clear();
-        }
-
+        on_progress(Progress::Finished {
+            name: NAME.to_string(),
+        });
         Ok(())
     }
 
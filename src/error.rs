@@ -2,9 +2,9 @@
 //!
 //! YC/Silicon Valley Standard: Clear, actionable error messages
 
-use colored::Colorize;
 use thiserror::Error;
 
+/// Errors returned by bibo operations
 #[derive(Error, Debug)]
 pub enum BiboError {
     #[error("Voice '{0}' not found")]
@@ -25,6 +25,15 @@ pub enum BiboError {
     #[error("No text provided")]
     NoTextProvided,
 
+    #[error("Invalid SSML: {0}")]
+    InvalidSsml(String),
+
+    #[error("Invalid dialogue script: {0}")]
+    InvalidDialogue(String),
+
+    #[error("Invalid EPUB: {0}")]
+    InvalidEpub(String),
+
     #[error("Invalid speed: {0}")]
     InvalidSpeed(String),
 
@@ -48,31 +57,19 @@ pub enum BiboError {
 }
 
 impl BiboError {
-    /// Display formatted error with tips
-    pub fn show(&self) {
-        eprintln!("\n{} {}", "❌".red(), self.to_string().red().bold());
-
-        let tips = self.tips();
-        if !tips.is_empty() {
-            eprintln!("\n{}", "💡 How to fix:".yellow());
-            for tip in tips {
-                eprintln!("   {} {}", "→".cyan(), tip);
-            }
-        }
-        eprintln!();
-    }
-
     /// Get actionable tips for each error type
-    fn tips(&self) -> Vec<&str> {
-        match self {
+    pub fn tips(&self) -> Vec<String> {
+        let tips = match self {
             BiboError::VoiceNotFound(_) => vec![
                 "bibo -l          # List installed voices",
                 "bibo -d list     # Show downloadable voices",
             ],
-            BiboError::VoiceNotInstalled(voice) => vec![
-                Box::leak(format!("bibo -d {}  # Download this voice", voice).into_boxed_str()),
-                "bibo -d list     # Show all downloadable voices",
-            ],
+            BiboError::VoiceNotInstalled(voice) => {
+                return vec![
+                    format!("bibo -d {}  # Download this voice", voice),
+                    "bibo -d list     # Show all downloadable voices".to_string(),
+                ]
+            }
             BiboError::FileNotFound(_) => vec![
                 "Check the file path for typos",
                 "Use absolute path: bibo -i /full/path/to/file.md",
@@ -80,6 +77,11 @@ impl BiboError {
             BiboError::UnsupportedFileType(_) => vec![
                 "bibo -i file.md   # Markdown files",
                 "bibo -i file.txt  # Text files",
+                "bibo -i page.html # Web pages",
+                "bibo -i book.epub # E-books",
+                "bibo -i file.ssml # SSML markup",
+                "bibo -i scene.dialogue  # Multi-voice dialogue script",
+                "bibo -i file.log --format txt  # Force a format",
                 "bibo \"text\"       # Or just pass text directly",
             ],
             BiboError::EmptyFile(_) => vec![
@@ -89,12 +91,28 @@ impl BiboError {
             BiboError::NoTextProvided => vec![
                 "bibo \"Hello world\"     # Direct text",
                 "bibo -i README.md      # From file",
+                "echo Hello | bibo      # From stdin",
+            ],
+            BiboError::InvalidSsml(_) => vec![
+                "Check that every tag is closed and attributes are quoted",
+                "<break time=\"500ms\"/>  # Empty elements need the closing slash",
+            ],
+            BiboError::InvalidDialogue(_) => vec![
+                "Start with a cast header: ---, ALICE: amy speed=1.1, BOB: ryan, ---",
+                "Then one line per turn: ALICE: Hello Bob.",
+                "bibo -d list  # Voice IDs for the cast",
+            ],
+            BiboError::InvalidEpub(_) => vec![
+                "Check that the book opens in an e-book reader",
+                "DRM-protected books cannot be read",
+                "Convert other e-book formats to EPUB first (e.g. with Calibre)",
             ],
             BiboError::InvalidSpeed(_) => vec![
                 "bibo \"text\" -s slow   # Slow speed",
                 "bibo \"text\" -s normal # Normal speed",
                 "bibo \"text\" -s fast   # Fast speed",
                 "bibo \"text\" -f        # Fast mode shortcut",
+                ":speed 1.2            # Rate multiplier (0.25-4.0) in interactive mode",
             ],
             BiboError::DownloadFailed(_) => vec![
                 "Check your internet connection",
@@ -110,8 +128,10 @@ impl BiboError {
                 "bibo -d <voice>  # Re-download the voice",
             ],
             BiboError::ConfigError(_) | BiboError::Other(_) => vec!["bibo --help  # Show usage"],
-        }
+        };
+        tips.into_iter().map(String::from).collect()
     }
 }
 
+/// Result type used throughout bibo
 pub type Result<T> = std::result::Result<T, BiboError>;
@@ -0,0 +1,284 @@
+//! C ABI for embedding bibo from other languages
+//!
+//! Declared in `include/bibo.h` and used by the Python bindings. Functions
+//! report failure with a NULL pointer or -1; the message is then available
+//! from `bibo_last_error()` on the same thread. Strings returned by bibo
+//! must be released with `bibo_string_free`.
+
+use crate::audio::AudioBuffer;
+use crate::download::VoiceDownloader;
+use crate::error::{BiboError, Result};
+use crate::input::{preprocess, InputFormat};
+use crate::tts::{parse_length_scale, TtsEngine, VoiceCatalog, VOICE_CATALOG};
+use serde_json::json;
+use std::cell::RefCell;
+use std::ffi::{c_char, c_int, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::ptr;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_error(error: BiboError) {
+    let message = CString::new(error.to_string().replace('\0', "")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+/// Run `f`, storing any error for `bibo_last_error`
+///
+/// Panics must not unwind into the caller, so they are reported as errors too.
+fn catch<T>(f: impl FnOnce() -> Result<T>) -> Option<T> {
+    let result = panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown cause".to_string());
+        Err(BiboError::Other(format!("Internal error: {}", message)))
+    });
+    match result {
+        Ok(value) => Some(value),
+        Err(e) => {
+            set_error(e);
+            None
+        }
+    }
+}
+
+/// Borrow a C string argument
+///
+/// # Safety
+/// `s` must be NULL or a valid NUL-terminated string.
+unsafe fn arg<'a>(s: *const c_char, name: &str) -> Result<&'a str> {
+    if s.is_null() {
+        return Err(BiboError::Other(format!("{} is NULL", name)));
+    }
+    CStr::from_ptr(s)
+        .to_str()
+        .map_err(|_| BiboError::Other(format!("{} is not valid UTF-8", name)))
+}
+
+fn into_c_string(s: String) -> *mut c_char {
+    CString::new(s.replace('\0', ""))
+        .map(CString::into_raw)
+        .unwrap_or(ptr::null_mut())
+}
+
+/// Message of the last failed call on this thread, or NULL.
+/// Valid until the next failing call.
+#[no_mangle]
+pub extern "C" fn bibo_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(ptr::null(), |e| e.as_ptr()))
+}
+
+/// Library version, e.g. "0.3.0"
+#[no_mangle]
+pub extern "C" fn bibo_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+/// Free a string returned by bibo
+///
+/// # Safety
+/// `s` must be NULL or a string returned by bibo, freed only once.
+#[no_mangle]
+pub unsafe extern "C" fn bibo_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// Voice catalog as a JSON array of objects with an `installed` flag
+#[no_mangle]
+pub extern "C" fn bibo_voices_json() -> *mut c_char {
+    catch(|| Ok(voices_json())).map_or(ptr::null_mut(), into_c_string)
+}
+
+fn voices_json() -> String {
+    let installed = VoiceCatalog::installed();
+    let voices: Vec<_> = VOICE_CATALOG
+        .iter()
+        .map(|v| {
+            json!({
+                "id": v.id,
+                "name": v.name,
+                "lang": v.lang,
+                "languages": v.languages(),
+                "gender": v.gender.to_string(),
+                "quality": v.quality,
+                "size_mb": v.size_mb,
+                "num_speakers": v.num_speakers,
+                "installed": installed.iter().any(|dir| dir == v.model_dir),
+            })
+        })
+        .collect();
+    json!(voices).to_string()
+}
+
+/// Download a voice if needed, blocking until installed. 0 on success, -1 on error.
+///
+/// # Safety
+/// `voice_id` must be a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn bibo_download_voice(voice_id: *const c_char) -> c_int {
+    let done = catch(|| {
+        let voice_id = arg(voice_id, "voice_id")?;
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| BiboError::Other(format!("Failed to start runtime: {}", e)))?;
+        runtime.block_on(VoiceDownloader::download_voice(voice_id, |_| {}))
+    });
+    if done.is_some() {
+        0
+    } else {
+        -1
+    }
+}
+
+/// Clean text for speaking; `format` is "md", "html", or "txt".
+/// Returns a string to free with `bibo_string_free`, or NULL.
+///
+/// # Safety
+/// `text` and `format` must be valid NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn bibo_clean_text(
+    text: *const c_char,
+    format: *const c_char,
+) -> *mut c_char {
+    catch(|| {
+        let text = arg(text, "text")?;
+        let format = match arg(format, "format")? {
+            "md" | "markdown" => InputFormat::Md,
+            "html" | "htm" => InputFormat::Html,
+            "txt" | "text" => InputFormat::Txt,
+            other => return Err(BiboError::UnsupportedFileType(other.to_string())),
+        };
+        Ok(preprocess(text.to_string(), format))
+    })
+    .map_or(ptr::null_mut(), into_c_string)
+}
+
+/// Length scale for a preset ("fast") or rate ("1.5"), or -1 if invalid
+///
+/// # Safety
+/// `spec` must be a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn bibo_length_scale(spec: *const c_char) -> f32 {
+    catch(|| parse_length_scale(arg(spec, "spec")?)).unwrap_or(-1.0)
+}
+
+/// Create an engine for an installed voice, or NULL
+///
+/// # Safety
+/// `voice_id` must be a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn bibo_engine_new(voice_id: *const c_char) -> *mut TtsEngine {
+    catch(|| TtsEngine::new(arg(voice_id, "voice_id")?))
+        .map_or(ptr::null_mut(), |engine| Box::into_raw(Box::new(engine)))
+}
+
+/// Free an engine
+///
+/// # Safety
+/// `engine` must be NULL or returned by `bibo_engine_new`, freed only once.
+#[no_mangle]
+pub unsafe extern "C" fn bibo_engine_free(engine: *mut TtsEngine) {
+    if !engine.is_null() {
+        drop(Box::from_raw(engine));
+    }
+}
+
+/// Select the speaker of a multi-speaker voice
+///
+/// # Safety
+/// `engine` must be a live engine from `bibo_engine_new`.
+#[no_mangle]
+pub unsafe extern "C" fn bibo_engine_set_speaker(engine: *mut TtsEngine, speaker: u32) {
+    if let Some(engine) = engine.as_mut() {
+        engine.set_speaker(speaker);
+    }
+}
+
+/// Synthesize to an audio buffer, or NULL
+///
+/// # Safety
+/// `engine` must be a live engine and `text` a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn bibo_synthesize(
+    engine: *const TtsEngine,
+    text: *const c_char,
+    length_scale: f32,
+) -> *mut AudioBuffer {
+    catch(|| {
+        let engine = engine
+            .as_ref()
+            .ok_or_else(|| BiboError::Other("engine is NULL".to_string()))?;
+        engine.synthesize_audio(arg(text, "text")?, length_scale)
+    })
+    .map_or(ptr::null_mut(), |audio| Box::into_raw(Box::new(audio)))
+}
+
+/// Synthesize straight to a WAV file. 0 on success, -1 on error.
+///
+/// # Safety
+/// `engine` must be a live engine; `text` and `path` valid NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn bibo_synthesize_to_file(
+    engine: *const TtsEngine,
+    text: *const c_char,
+    length_scale: f32,
+    path: *const c_char,
+) -> c_int {
+    let audio = bibo_synthesize(engine, text, length_scale);
+    if audio.is_null() {
+        return -1;
+    }
+    let saved = catch(|| (*audio).save(Path::new(arg(path, "path")?)));
+    bibo_audio_free(audio);
+    if saved.is_some() {
+        0
+    } else {
+        -1
+    }
+}
+
+/// 16-bit mono samples of a buffer
+///
+/// # Safety
+/// `audio` must be a live buffer from `bibo_synthesize`.
+#[no_mangle]
+pub unsafe extern "C" fn bibo_audio_samples(audio: *const AudioBuffer) -> *const i16 {
+    audio.as_ref().map_or(ptr::null(), |a| a.samples.as_ptr())
+}
+
+/// Number of samples in a buffer
+///
+/// # Safety
+/// `audio` must be a live buffer from `bibo_synthesize`.
+#[no_mangle]
+pub unsafe extern "C" fn bibo_audio_len(audio: *const AudioBuffer) -> usize {
+    audio.as_ref().map_or(0, |a| a.samples.len())
+}
+
+/// Sample rate of a buffer in Hz
+///
+/// # Safety
+/// `audio` must be a live buffer from `bibo_synthesize`.
+#[no_mangle]
+pub unsafe extern "C" fn bibo_audio_sample_rate(audio: *const AudioBuffer) -> u32 {
+    audio.as_ref().map_or(0, |a| a.sample_rate)
+}
+
+/// Free an audio buffer
+///
+/// # Safety
+/// `audio` must be NULL or returned by `bibo_synthesize`, freed only once.
+#[no_mangle]
+pub unsafe extern "C" fn bibo_audio_free(audio: *mut AudioBuffer) {
+    if !audio.is_null() {
+        drop(Box::from_raw(audio));
+    }
+}
@@ -0,0 +1,140 @@
+//! Streamed input lines - filtering and a bounded queue
+//!
+//! Used by `bibo --stream`: a reader thread pushes accepted lines while the
+//! speaker pops them, and the overflow policy decides what to drop when the
+//! reader gets ahead.
+
+use crate::error::{BiboError, Result};
+use regex_lite::Regex;
+use std::collections::VecDeque;
+use std::sync::{Condvar, Mutex};
+
+/// What to do when streamed input arrives faster than it can be spoken
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum Overflow {
+    /// Drop the oldest queued lines
+    #[default]
+    DropOldest,
+    /// Discard the backlog and skip to the newest line
+    Latest,
+    /// Stop reading input until the queue has room
+    Block,
+}
+
+/// Line filters: speak lines matching any include, skip lines matching any exclude
+pub struct LineFilter {
+    include: Vec<Regex>,
+    exclude: Vec<Regex>,
+}
+
+impl LineFilter {
+    /// Compile include/exclude patterns
+    pub fn new(include: &[String], exclude: &[String]) -> Result<Self> {
+        let compile = |patterns: &[String]| -> Result<Vec<Regex>> {
+            patterns
+                .iter()
+                .map(|p| {
+                    Regex::new(p).map_err(|e| {
+                        BiboError::ConfigError(format!("Invalid regex '{}': {}", p, e))
+                    })
+                })
+                .collect()
+        };
+
+        Ok(Self {
+            include: compile(include)?,
+            exclude: compile(exclude)?,
+        })
+    }
+
+    /// Check whether a line should be spoken
+    pub fn accepts(&self, line: &str) -> bool {
+        if line.trim().is_empty() {
+            return false;
+        }
+        if !self.include.is_empty() && !self.include.iter().any(|r| r.is_match(line)) {
+            return false;
+        }
+        !self.exclude.iter().any(|r| r.is_match(line))
+    }
+}
+
+struct QueueState {
+    items: VecDeque<String>,
+    closed: bool,
+}
+
+/// Bounded line queue shared between the reader thread and the speaker
+pub struct LineQueue {
+    state: Mutex<QueueState>,
+    changed: Condvar,
+    capacity: usize,
+    overflow: Overflow,
+}
+
+impl LineQueue {
+    pub fn new(capacity: usize, overflow: Overflow) -> Self {
+        Self {
+            state: Mutex::new(QueueState {
+                items: VecDeque::new(),
+                closed: false,
+            }),
+            changed: Condvar::new(),
+            capacity: capacity.max(1),
+            overflow,
+        }
+    }
+
+    /// Push a line, applying the overflow policy when full.
+    /// Returns the number of queued lines dropped to make room.
+    pub fn push(&self, line: String) -> usize {
+        let mut state = self.state.lock().unwrap();
+        let mut dropped = 0;
+
+        match self.overflow {
+            Overflow::Block => {
+                while state.items.len() >= self.capacity && !state.closed {
+                    state = self.changed.wait(state).unwrap();
+                }
+            }
+            Overflow::DropOldest => {
+                while state.items.len() >= self.capacity {
+                    state.items.pop_front();
+                    dropped += 1;
+                }
+            }
+            Overflow::Latest => {
+                if state.items.len() >= self.capacity {
+                    dropped = state.items.len();
+                    state.items.clear();
+                }
+            }
+        }
+
+        state.items.push_back(line);
+        self.changed.notify_all();
+        dropped
+    }
+
+    /// Pop the next line, waiting for input. Returns None once closed and drained.
+    pub fn pop(&self) -> Option<String> {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if let Some(line) = state.items.pop_front() {
+                self.changed.notify_all();
+                return Some(line);
+            }
+            if state.closed {
+                return None;
+            }
+            state = self.changed.wait(state).unwrap();
+        }
+    }
+
+    /// Mark end of input
+    pub fn close(&self) {
+        self.state.lock().unwrap().closed = true;
+        self.changed.notify_all();
+    }
+}
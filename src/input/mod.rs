@@ -0,0 +1,156 @@
+//! Text input - files, stdin, and format preprocessing
+
+pub mod lines;
+
+use crate::config::Config;
+use crate::error::BiboError;
+use crate::text::epub::Book;
+use crate::text::{dialogue, html, markdown, ssml, Segment};
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+/// Input text format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum InputFormat {
+    /// Detect from file extension (plain text for stdin)
+    #[default]
+    Auto,
+    /// Plain text, spoken as-is
+    Txt,
+    /// Markdown, formatting stripped before speaking
+    Md,
+    /// HTML page, main content read with its structure
+    Html,
+    /// EPUB book, read chapter by chapter
+    Epub,
+    /// SSML markup with pauses, rates, and voice changes
+    Ssml,
+    /// Dialogue script: a cast header, then `NAME: text` lines
+    Dialogue,
+}
+
+impl InputFormat {
+    /// Detect format from a file extension
+    pub fn from_extension(ext: &str) -> Option<Self> {
+        match ext.to_lowercase().as_str() {
+            "txt" => Some(InputFormat::Txt),
+            "md" | "markdown" => Some(InputFormat::Md),
+            "html" | "htm" | "xhtml" => Some(InputFormat::Html),
+            "epub" => Some(InputFormat::Epub),
+            "ssml" => Some(InputFormat::Ssml),
+            "dialogue" => Some(InputFormat::Dialogue),
+            _ => None,
+        }
+    }
+
+    /// Format used for a path: explicit formats win over the extension
+    pub fn resolve(self, path: impl AsRef<Path>) -> Result<Self, BiboError> {
+        let path = path.as_ref();
+        if self != InputFormat::Auto {
+            return Ok(self);
+        }
+        if path == Path::new("-") {
+            return Ok(InputFormat::Txt);
+        }
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+        InputFormat::from_extension(&ext).ok_or(BiboError::UnsupportedFileType(ext))
+    }
+}
+
+/// Clean markdown formatting for TTS
+pub fn clean_markdown(text: &str) -> String {
+    markdown::to_text(&markdown::parse(text))
+}
+
+/// Plain text for a format (Markdown and HTML stripped; scripts are left as-is)
+pub fn preprocess(content: String, format: InputFormat) -> String {
+    match format {
+        InputFormat::Md => clean_markdown(&content),
+        InputFormat::Html => html::to_text(&content),
+        InputFormat::Txt
+        | InputFormat::Epub
+        | InputFormat::Ssml
+        | InputFormat::Dialogue
+        | InputFormat::Auto => content,
+    }
+}
+
+/// Compile content into segments for `TtsEngine::synthesize_segments`
+///
+/// Markdown and HTML are read with the config's rules, so headings and
+/// list items are paced with pauses.
+pub fn to_segments(
+    content: &str,
+    format: InputFormat,
+    config: &Config,
+) -> Result<Vec<Segment>, BiboError> {
+    let segments = match format {
+        InputFormat::Ssml => ssml::parse(content)?,
+        InputFormat::Dialogue => dialogue::parse(content)?,
+        InputFormat::Md => markdown::render(content, &config.markdown),
+        InputFormat::Html => html::render(content, &config.markdown),
+        InputFormat::Txt | InputFormat::Epub | InputFormat::Auto => vec![Segment::speech(content)],
+    };
+    if !segments.iter().any(|s| matches!(s, Segment::Speech { .. })) {
+        return Err(BiboError::NoTextProvided);
+    }
+    Ok(segments)
+}
+
+/// Whether content has nothing to speak once formatting is removed
+fn is_blank(content: &str, format: InputFormat) -> bool {
+    preprocess(content.to_string(), format).trim().is_empty()
+}
+
+/// Read content from stdin, unprocessed (see `to_segments`)
+pub fn read_stdin_content(format: InputFormat) -> Result<String, BiboError> {
+    let mut content = String::new();
+    io::stdin()
+        .read_to_string(&mut content)
+        .map_err(|e| BiboError::Other(format!("Failed to read stdin: {}", e)))?;
+
+    if is_blank(&content, format) {
+        return Err(BiboError::EmptyFile("stdin".to_string()));
+    }
+
+    Ok(content)
+}
+
+/// Read content from file ("-" reads stdin), unprocessed (see `to_segments`)
+///
+/// An EPUB comes back as the plain text of its chapters; see
+/// [`Book`] to read it chapter by chapter.
+pub fn read_file_content(path: &str, format: InputFormat) -> Result<String, BiboError> {
+    if path == "-" {
+        return read_stdin_content(format);
+    }
+
+    let path = Path::new(path);
+
+    if !path.exists() {
+        return Err(BiboError::FileNotFound(path.display().to_string()));
+    }
+
+    // Explicit --format overrides extension detection
+    let format = format.resolve(path)?;
+
+    // Books are read as the plain text of their chapters
+    let content = if format == InputFormat::Epub {
+        Book::open(path)?.text()
+    } else {
+        fs::read_to_string(path)
+            .map_err(|e| BiboError::FileNotFound(format!("{}: {}", path.display(), e)))?
+    };
+
+    if is_blank(&content, format) {
+        return Err(BiboError::EmptyFile(path.display().to_string()));
+    }
+
+    Ok(content)
+}
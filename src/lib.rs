@@ -0,0 +1,38 @@
+//! Bibo - Fast, local neural text-to-speech
+//!
+//! The library behind the `bibo` CLI: voice catalog, model downloads,
+//! text preprocessing, synthesis through sherpa-onnx, and audio buffers.
+//! Library code never prints or exits; errors are returned as
+//! [`BiboError`] and downloads report [`Progress`] through callbacks.
+//!
+//! ```no_run
+//! use bibo::{Speed, TtsEngine, VoiceDownloader};
+//!
+//! # async fn run() -> bibo::Result<()> {
+//! VoiceDownloader::download_voice("ryan", |_progress| {}).await?;
+//!
+//! let engine = TtsEngine::new("ryan")?;
+//! let audio = engine.synthesize_audio("Hello from bibo", Speed::Normal.to_length_scale())?;
+//! audio.save(std::path::Path::new("hello.wav"))?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Build with `default-features = false` to leave out the CLI and servers.
+//! The [`ffi`] module exposes a C ABI, declared in `include/bibo.h`.
+
+pub mod audio;
+pub mod config;
+pub mod download;
+pub mod error;
+pub mod ffi;
+pub mod input;
+pub mod text;
+pub mod tts;
+
+pub use audio::{AudioBuffer, AudioOutput, AudioPlayer, PlaybackLock};
+pub use config::Config;
+pub use download::{Progress, SherpaDownloader, VoiceDownloader};
+pub use error::{BiboError, Result};
+pub use input::{clean_markdown, InputFormat};
+pub use tts::{AudioCache, Speed, TtsEngine, Voice, VoiceCatalog, VOICE_CATALOG};
@@ -3,150 +3,344 @@
 //! Zero dependencies - powered by sherpa-onnx
 //! Universal binary support for arm64 and x86_64
 
-mod audio;
+mod book;
 mod cli;
-mod download;
-mod error;
-mod tts;
-
+mod repl;
+mod server;
+mod stream;
+mod ui;
+mod watch;
+
+use bibo::audio;
+use bibo::config::Config;
+use bibo::download::{SherpaDownloader, VoiceDownloader};
+use bibo::error::BiboError;
+use bibo::input::lines::LineFilter;
+use bibo::input::{read_file_content, read_stdin_content, to_segments, InputFormat};
+use bibo::text::epub::Book;
+use bibo::text::normalize::{normalize_with, Normalization};
+use bibo::text::{speech_text, Segment};
+use bibo::tts::{self, sherpa_available};
 use clap::Parser;
-use cli::Cli;
+use cli::{CacheAction, Cli, Command, DaemonAction};
 use colored::Colorize;
-use download::{SherpaDownloader, VoiceDownloader};
-use error::BiboError;
 use std::fs;
-use std::path::Path;
-use tts::sherpa_available;
-
-/// Clean markdown formatting for TTS
-fn clean_markdown(text: &str) -> String {
-    let mut text = text.to_string();
-
-    // Remove code blocks
-    text = regex_lite::Regex::new(r"```[\s\S]*?```")
-        .unwrap()
-        .replace_all(&text, "")
-        .to_string();
-
-    // Remove inline code
-    text = regex_lite::Regex::new(r"`([^`]+)`")
-        .unwrap()
-        .replace_all(&text, "$1")
-        .to_string();
-
-    // Remove images
-    text = regex_lite::Regex::new(r"!\[([^\]]*)\]\([^)]+\)")
-        .unwrap()
-        .replace_all(&text, "$1")
-        .to_string();
-
-    // Convert links to text
-    text = regex_lite::Regex::new(r"\[([^\]]+)\]\([^)]+\)")
-        .unwrap()
-        .replace_all(&text, "$1")
-        .to_string();
-
-    // Remove headers
-    text = regex_lite::Regex::new(r"^#{1,6}\s+")
-        .unwrap()
-        .replace_all(&text, "")
-        .to_string();
-
-    // Remove bold/italic
-    text = regex_lite::Regex::new(r"\*\*\*([^*]+)\*\*\*")
-        .unwrap()
-        .replace_all(&text, "$1")
-        .to_string();
-    text = regex_lite::Regex::new(r"\*\*([^*]+)\*\*")
-        .unwrap()
-        .replace_all(&text, "$1")
-        .to_string();
-    text = regex_lite::Regex::new(r"\*([^*]+)\*")
-        .unwrap()
-        .replace_all(&text, "$1")
-        .to_string();
-
-    // Remove list markers
-    text = regex_lite::Regex::new(r"^\s*[-*+]\s+")
-        .unwrap()
-        .replace_all(&text, "")
-        .to_string();
-    text = regex_lite::Regex::new(r"^\s*\d+\.\s+")
-        .unwrap()
-        .replace_all(&text, "")
-        .to_string();
-
-    // Clean up whitespace
-    text = regex_lite::Regex::new(r"\n{3,}")
-        .unwrap()
-        .replace_all(&text, "\n\n")
-        .to_string();
-
-    text.trim().to_string()
+use std::io::{self, IsTerminal};
+use ui::ShowError;
+
+/// Auto-download a catalog voice if it is not installed yet
+async fn ensure_voice(voice: &str, quiet: bool) {
+    if !tts::VoiceCatalog::is_installed(voice) && tts::VoiceCatalog::find(voice).is_some() {
+        if !quiet {
+            println!(
+                "{} Voice '{}' not installed, downloading...",
+                "📥".cyan(),
+                voice
+            );
+        }
+        if let Err(e) = VoiceDownloader::download_voice(voice, ui::voice_progress(quiet)).await {
+            e.show();
+            std::process::exit(1);
+        }
+    }
 }
 
-/// Read content from file
-fn read_file_content(path: &str, quiet: bool) -> Result<String, BiboError> {
-    let path = Path::new(path);
+/// Auto-download the selected voice if needed and create its engine
+async fn prepare_engine(cli: &Cli, config: &Config) -> tts::TtsEngine {
+    ensure_voice(&cli.voice, cli.quiet).await;
 
-    if !path.exists() {
-        return Err(BiboError::FileNotFound(path.display().to_string()));
+    // Create TTS engine
+    let mut engine = match tts::TtsEngine::new(&cli.voice) {
+        Ok(e) => e,
+        Err(e) => {
+            e.show();
+            std::process::exit(1);
+        }
+    };
+    engine.set_speaker(cli.speaker);
+    engine.set_normalization(load_normalization(config));
+    if cli.no_cache {
+        engine.set_cache(None);
     }
+    engine
+}
 
-    let ext = path
-        .extension()
-        .and_then(|e| e.to_str())
-        .unwrap_or("")
-        .to_lowercase();
+/// Load config.toml, exiting on errors
+fn load_config(cli: &Cli) -> Config {
+    let mut config = match Config::load() {
+        Ok(config) => config,
+        Err(e) => {
+            e.show();
+            std::process::exit(1);
+        }
+    };
+    if let Some(code) = cli.code {
+        config.markdown.code = code;
+    }
+    if cli.detect_lang {
+        config.languages.detect = true;
+    }
+    config
+}
 
-    if !["md", "txt", "markdown"].contains(&ext.as_str()) {
-        return Err(BiboError::UnsupportedFileType(ext));
+/// Load the user dictionaries and symbol rules, exiting on errors
+fn load_normalization(config: &Config) -> Normalization {
+    match Normalization::from_config(config) {
+        Ok(normalization) => normalization,
+        Err(e) => {
+            e.show();
+            std::process::exit(1);
+        }
     }
+}
 
-    let content = fs::read_to_string(path)
-        .map_err(|e| BiboError::FileNotFound(format!("{}: {}", path.display(), e)))?;
+/// Engines for server modes, honoring --no-cache and the user's config
+fn engine_pool(cli: &Cli) -> server::EnginePool {
+    let normalization = load_normalization(&load_config(cli));
+    server::EnginePool::new(!cli.no_cache, normalization)
+}
+
+/// Print speech the way the voice will read it (`--print-normalized`)
+fn print_normalized(segments: &[Segment], default_voice: &str, normalization: &Normalization) {
+    let mut blocks = vec![];
+    for segment in segments {
+        let Segment::Speech { text, voice, .. } = segment else {
+            continue;
+        };
+        let voice = voice.as_deref().unwrap_or(default_voice);
+        let Some(found) = tts::VoiceCatalog::find(voice) else {
+            BiboError::VoiceNotFound(voice.to_string()).show();
+            std::process::exit(1);
+        };
+        blocks.push(normalize_with(text, found.lang, normalization));
+    }
+    println!("{}", blocks.join("\n\n"));
+}
 
-    if !quiet {
+/// Read an EPUB chapter by chapter: play it, or write it with `-o`
+async fn run_book(cli: &Cli, path: &str) {
+    let book = match Book::open(std::path::Path::new(path)) {
+        Ok(book) => book,
+        Err(e) => {
+            e.show();
+            std::process::exit(1);
+        }
+    };
+    if !cli.quiet {
         println!(
-            "{} Reading: {} ({} chars)",
-            "📄".cyan(),
-            path.file_name().unwrap_or_default().to_string_lossy(),
-            content.len()
+            "{} Reading: {} ({} chapters)",
+            "📚".cyan(),
+            if book.title.is_empty() {
+                path
+            } else {
+                &book.title
+            },
+            book.chapters.len()
         );
     }
 
-    // Clean markdown
-    let content = if ext == "md" || ext == "markdown" {
-        let cleaned = clean_markdown(&content);
-        if !quiet {
-            println!("{} Cleaned: {} chars", "📝".cyan(), cleaned.len());
+    let config = load_config(cli);
+    let chapters: Vec<book::Chapter> = book
+        .chapters
+        .iter()
+        .map(|chapter| {
+            let segments = chapter.render(&config.markdown);
+            book::Chapter {
+                title: chapter.title.clone(),
+                segments: if config.languages.detect {
+                    config.languages.route(segments, &cli.voice)
+                } else {
+                    segments
+                },
+            }
+        })
+        .collect();
+
+    if cli.print_normalized {
+        let normalization = load_normalization(&config);
+        for chapter in &chapters {
+            println!("# {}\n", chapter.title);
+            print_normalized(&chapter.segments, &cli.voice, &normalization);
+            println!();
         }
-        cleaned
+        return;
+    }
+    for segment in chapters.iter().flat_map(|chapter| &chapter.segments) {
+        if let Segment::Speech {
+            voice: Some(voice), ..
+        } = segment
+        {
+            ensure_voice(voice, cli.quiet).await;
+        }
+    }
+
+    let engine = prepare_engine(cli, &config).await;
+    let length_scale = cli.effective_speed().to_length_scale();
+    if let Err(e) = book::run(
+        &engine,
+        &chapters,
+        cli.output.as_deref(),
+        length_scale,
+        cli.quiet,
+    ) {
+        e.show();
+        std::process::exit(1);
+    }
+}
+
+/// Report what was read from a file or stdin ("-")
+fn report_read(path: &str, content: &str, quiet: bool) {
+    if quiet {
+        return;
+    }
+    let name = if path == "-" {
+        "stdin".to_string()
     } else {
-        content
+        std::path::Path::new(path)
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string()
     };
+    println!(
+        "{} Reading: {} ({} chars)",
+        "📄".cyan(),
+        name,
+        content.len()
+    );
+}
 
-    if content.trim().is_empty() {
-        return Err(BiboError::EmptyFile(path.display().to_string()));
+/// Handle `bibo cache stats|clear`
+fn run_cache_command(action: CacheAction) {
+    let cache = tts::AudioCache::default_location();
+    match action {
+        CacheAction::Stats => {
+            let stats = cache.stats();
+            println!("{}", "🗄️  Synthesis cache:".cyan().bold());
+            println!("   Location: {}", stats.dir.display());
+            println!("   Entries:  {}", stats.entries);
+            println!(
+                "   Size:     {:.1} MB / {:.0} MB",
+                stats.total_bytes as f64 / 1_048_576.0,
+                stats.max_bytes as f64 / 1_048_576.0
+            );
+        }
+        CacheAction::Clear => match cache.clear() {
+            Ok(n) => println!("{} Removed {} cached clips", "🧹".cyan(), n),
+            Err(e) => {
+                e.show();
+                std::process::exit(1);
+            }
+        },
+    }
+}
+
+/// Handle `bibo daemon status|queue|skip|stop|shutdown`
+fn run_daemon_command(action: DaemonAction) {
+    use server::daemon::client;
+
+    let cmd = match action {
+        DaemonAction::Status => "ping",
+        DaemonAction::Queue => "queue",
+        DaemonAction::Skip => "skip",
+        DaemonAction::Stop => "stop",
+        DaemonAction::Shutdown => "shutdown",
+    };
+    let response = match client::request(&serde_json::json!({ "cmd": cmd })) {
+        Ok(response) => response,
+        Err(_) => {
+            println!("{} Daemon not running", "💤".yellow());
+            println!("{} Start it with: bibo daemon &", "💡".yellow());
+            std::process::exit(1);
+        }
+    };
+    if let Some(error) = response["error"].as_str() {
+        eprintln!("{} {}", "❌".red(), error);
+        std::process::exit(1);
     }
 
-    Ok(content)
+    match action {
+        DaemonAction::Status => println!(
+            "{} Daemon running (v{}) on {}",
+            "🛰️".cyan(),
+            response["version"].as_str().unwrap_or("?"),
+            server::daemon::socket_path().display()
+        ),
+        DaemonAction::Queue => {
+            let line = |job: &serde_json::Value| {
+                let text = job["text"].as_str().unwrap_or("");
+                let preview: String = text.chars().take(60).collect();
+                let ellipsis = if text.chars().count() > 60 { "…" } else { "" };
+                format!(
+                    "#{} [{}, {}] {}{}",
+                    job["id"],
+                    job["voice"].as_str().unwrap_or("?"),
+                    job["priority"].as_str().unwrap_or("normal"),
+                    preview,
+                    ellipsis
+                )
+            };
+            match response["current"].as_object() {
+                Some(_) => println!("{} {}", "▶️".cyan(), line(&response["current"])),
+                None => println!("{} Nothing playing", "⏹️".cyan()),
+            }
+            for job in response["queued"].as_array().into_iter().flatten() {
+                println!("   {}", line(job));
+            }
+        }
+        DaemonAction::Skip => {
+            if response["skipped"].as_bool() == Some(true) {
+                println!("{} Skipped", "⏭️".cyan());
+            } else {
+                println!("{} Nothing playing", "⏹️".cyan());
+            }
+        }
+        DaemonAction::Stop => println!(
+            "{} Stopped ({} queued cleared)",
+            "⏹️".cyan(),
+            response["cleared"]
+        ),
+        DaemonAction::Shutdown => println!("{} Daemon stopping", "👋".cyan()),
+    }
 }
 
 #[tokio::main]
 async fn main() {
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
+    // Normalized text goes to stdout alone so it can be piped
+    if cli.print_normalized {
+        cli.quiet = true;
+    }
+
+    if let Some(Command::Cache { action }) = &cli.command {
+        run_cache_command(*action);
+        std::process::exit(0);
+    }
+
+    // Daemon control - talks to a running daemon, needs no engine
+    if let Some(Command::Daemon {
+        action: Some(action),
+    }) = &cli.command
+    {
+        run_daemon_command(*action);
+        std::process::exit(0);
+    }
 
     // Clean mode - remove all data
     if cli.clean {
         let data_dir = dirs::data_dir()
             .unwrap_or_else(|| std::path::PathBuf::from("."))
             .join("bibo");
+        let _ = tts::AudioCache::default_location().clear();
 
         if data_dir.exists() {
             match fs::remove_dir_all(&data_dir) {
                 Ok(_) => {
-                    println!("{} Removed all bibo data: {}", "🧹".cyan(), data_dir.display());
+                    println!(
+                        "{} Removed all bibo data: {}",
+                        "🧹".cyan(),
+                        data_dir.display()
+                    );
                     std::process::exit(0);
                 }
                 Err(e) => {
@@ -160,23 +354,71 @@ async fn main() {
         }
     }
 
+    // Stdio protocol modes - stdout belongs to the protocol, so set up silently
+    if let Some(Command::Mcp | Command::Speechd { .. }) = &cli.command {
+        let setup = if sherpa_available() {
+            Ok(())
+        } else {
+            SherpaDownloader::download(|_| {}).await
+        };
+        let pool = engine_pool(&cli);
+        let served = setup.and_then(|_| match &cli.command {
+            Some(Command::Mcp) => server::mcp::serve(&cli.voice, pool),
+            _ => server::speechd::serve(&cli.voice, pool),
+        });
+        if let Err(e) = served {
+            e.show();
+            std::process::exit(1);
+        }
+        std::process::exit(0);
+    }
+
     // Ensure sherpa-onnx is available (auto-download if needed)
-    if !sherpa_available() {
+    if !sherpa_available() && !cli.print_normalized {
         if !cli.quiet {
             println!(
                 "{} First run setup - downloading sherpa-onnx TTS engine...",
                 "🚀".cyan()
             );
         }
-        if let Err(e) = SherpaDownloader::download(cli.quiet).await {
+        if let Err(e) = SherpaDownloader::download(ui::sherpa_progress(cli.quiet)).await {
+            e.show();
+            std::process::exit(1);
+        }
+    }
+
+    // Server mode
+    if let Some(Command::Serve { host, port }) = &cli.command {
+        let pool = engine_pool(&cli);
+        if let Err(e) = server::http::serve(host, *port, &cli.voice, pool, cli.quiet).await {
+            e.show();
+            std::process::exit(1);
+        }
+        std::process::exit(0);
+    }
+    if let Some(Command::Wyoming { host, port }) = &cli.command {
+        let pool = engine_pool(&cli);
+        let length_scale = cli.effective_speed().to_length_scale();
+        if let Err(e) =
+            server::wyoming::serve(host, *port, &cli.voice, length_scale, pool, cli.quiet).await
+        {
             e.show();
             std::process::exit(1);
         }
+        std::process::exit(0);
+    }
+
+    if let Some(Command::Daemon { action: None }) = &cli.command {
+        if let Err(e) = server::daemon::run(engine_pool(&cli), cli.quiet) {
+            e.show();
+            std::process::exit(1);
+        }
+        std::process::exit(0);
     }
 
     // Download mode
     if let Some(spec) = &cli.download {
-        match VoiceDownloader::download_by_spec(spec, cli.quiet).await {
+        match ui::download_voices(spec, cli.quiet).await {
             Ok(_) => std::process::exit(0),
             Err(e) => {
                 e.show();
@@ -212,51 +454,190 @@ async fn main() {
         std::process::exit(0);
     }
 
+    // Interactive mode - REPL with :commands
+    if cli.interactive {
+        let engine = prepare_engine(&cli, &load_config(&cli)).await;
+        let length_scale = cli.effective_speed().to_length_scale();
+        if let Err(e) = repl::run(engine, length_scale, cli.quiet).await {
+            e.show();
+            std::process::exit(1);
+        }
+        std::process::exit(0);
+    }
+
+    // Watch mode - re-render output whenever the input changes
+    if cli.watch {
+        let config = load_config(&cli);
+        let engine = prepare_engine(&cli, &config).await;
+        let length_scale = cli.effective_speed().to_length_scale();
+        let (Some(input), Some(output)) = (&cli.input, &cli.output) else {
+            unreachable!("clap requires --input and --output with --watch");
+        };
+        if let Err(e) = watch::run(
+            &engine,
+            input,
+            output,
+            cli.effective_format(),
+            &config,
+            length_scale,
+            cli.quiet,
+        ) {
+            e.show();
+            std::process::exit(1);
+        }
+        std::process::exit(0);
+    }
+
+    // Stream mode - speak stdin line by line
+    if cli.stream {
+        let filter = match LineFilter::new(&cli.match_patterns, &cli.exclude_patterns) {
+            Ok(f) => f,
+            Err(e) => {
+                e.show();
+                std::process::exit(1);
+            }
+        };
+        let engine = prepare_engine(&cli, &load_config(&cli)).await;
+        let options = stream::StreamOptions {
+            length_scale: cli.effective_speed().to_length_scale(),
+            queue_size: cli.queue_size,
+            overflow: cli.overflow,
+            filter,
+            quiet: cli.quiet,
+        };
+        if let Err(e) = stream::run(&engine, options) {
+            e.show();
+            std::process::exit(1);
+        }
+        std::process::exit(0);
+    }
+
     // Get text input
-    let text = if let Some(input_file) = &cli.input {
-        match read_file_content(input_file, cli.quiet) {
-            Ok(content) => content,
+    let format = cli.effective_format();
+    // Books are read chapter by chapter
+    if let Some(input_file) = &cli.input {
+        if format.resolve(input_file).ok() == Some(InputFormat::Epub) {
+            run_book(&cli, input_file).await;
+            std::process::exit(0);
+        }
+    }
+    let (text, format) = if let Some(input_file) = &cli.input {
+        match read_file_content(input_file, format) {
+            Ok(content) => {
+                report_read(input_file, &content, cli.quiet);
+                // The extension was already accepted while reading
+                (content, format.resolve(input_file).unwrap_or(format))
+            }
             Err(e) => {
                 e.show();
                 std::process::exit(1);
             }
         }
     } else if let Some(text) = &cli.text {
-        text.clone()
+        (text.clone(), format)
+    } else if !io::stdin().is_terminal() {
+        // Piped input: `git log | bibo`
+        match read_stdin_content(format) {
+            Ok(content) => {
+                report_read("-", &content, cli.quiet);
+                (content, format)
+            }
+            Err(e) => {
+                e.show();
+                std::process::exit(1);
+            }
+        }
     } else {
         BiboError::NoTextProvided.show();
         std::process::exit(1);
     };
 
+    let config = load_config(&cli);
+
+    // Markdown and scripts compile to speech and pauses; plain text is one utterance
+    let segments = match to_segments(&text, format, &config) {
+        Ok(segments) => segments,
+        Err(e) => {
+            e.show();
+            std::process::exit(1);
+        }
+    };
+    // Each language goes to its own voice
+    let segments = if config.languages.detect {
+        config.languages.route(segments, &cli.voice)
+    } else {
+        segments
+    };
+    // Routed voices and pauses only survive in-process playback
+    let scripted = segments.iter().any(|segment| {
+        matches!(
+            segment,
+            Segment::Speech { voice: Some(_), .. } | Segment::Silence(_)
+        )
+    }) || matches!(format, InputFormat::Ssml | InputFormat::Dialogue);
+
+    if cli.print_normalized {
+        print_normalized(&segments, &cli.voice, &load_normalization(&config));
+        std::process::exit(0);
+    }
+
+    // Voices named in the script are fetched up front
+    for segment in &segments {
+        if let Segment::Speech {
+            voice: Some(voice), ..
+        } = segment
+        {
+            ensure_voice(voice, cli.quiet).await;
+        }
+    }
+    // A dialogue's cast replaces --voice
+    if format == InputFormat::Dialogue {
+        if let Some(Segment::Speech {
+            voice: Some(voice), ..
+        }) = segments.first()
+        {
+            cli.voice = voice.clone();
+        }
+    }
+
     // Get speed
     let speed = cli.effective_speed();
     let length_scale = speed.to_length_scale();
 
-    // Auto-download voice if not installed
-    if !tts::VoiceCatalog::is_installed(&cli.voice) {
-        if tts::VoiceCatalog::find(&cli.voice).is_some() {
-            if !cli.quiet {
-                println!(
-                    "{} Voice '{}' not installed, downloading...",
-                    "📥".cyan(),
-                    cli.voice
-                );
+    // Hand playback to a running daemon; fall back to playing in-process.
+    // The daemon queues plain text, so scripts and paused markup play here.
+    if cli.output.is_none() && !cli.no_daemon && !scripted {
+        let job = server::daemon::Job {
+            id: 0,
+            text: speech_text(&segments),
+            voice: cli.voice.clone(),
+            speaker: cli.speaker,
+            length_scale,
+            priority: cli.priority,
+            no_cache: cli.no_cache,
+        };
+        match server::daemon::client::speak(&job) {
+            Ok(Some(response)) => {
+                if !cli.quiet {
+                    println!(
+                        "{} Queued in daemon (#{}, position {})",
+                        "📨".cyan(),
+                        response["id"],
+                        response["position"]
+                    );
+                }
+                std::process::exit(0);
             }
-            if let Err(e) = VoiceDownloader::download_voice(&cli.voice, cli.quiet).await {
-                e.show();
-                std::process::exit(1);
+            Ok(None) => {}
+            Err(e) => {
+                if !cli.quiet {
+                    eprintln!("{} {}, playing locally", "⚠️".yellow(), e);
+                }
             }
         }
     }
 
-    // Create TTS engine
-    let engine = match tts::TtsEngine::new(&cli.voice) {
-        Ok(e) => e,
-        Err(e) => {
-            e.show();
-            std::process::exit(1);
-        }
-    };
+    let engine = prepare_engine(&cli, &config).await;
 
     if !cli.quiet {
         let speed_name = format!("{:?}", speed).to_lowercase();
@@ -264,32 +645,33 @@ async fn main() {
     }
 
     // Synthesize
-    match engine.synthesize(&text, length_scale) {
-        Ok(samples) => {
-            // Output to file or play
-            if let Some(output_path) = &cli.output {
-                // Save to file
-                if let Err(e) = engine.synthesize_to_file(&text, length_scale, output_path) {
-                    e.show();
-                    std::process::exit(1);
-                }
-                if !cli.quiet {
-                    println!("{} Saved: {}", "✅".green(), output_path);
-                }
-            } else {
-                // Play audio
-                if !cli.quiet {
-                    println!("{} Playing...", "▶️".cyan());
-                }
-                if let Err(e) = audio::AudioPlayer::play_samples(samples, engine.sample_rate()) {
-                    e.show();
-                    std::process::exit(1);
-                }
-            }
-        }
+    let audio = match engine.synthesize_segments(&segments, length_scale) {
+        Ok(audio) => audio,
         Err(e) => {
             e.show();
             std::process::exit(1);
         }
+    };
+
+    // Output to file or play
+    if let Some(output_path) = &cli.output {
+        if let Err(e) = audio.save(std::path::Path::new(output_path)) {
+            e.show();
+            std::process::exit(1);
+        }
+        if !cli.quiet {
+            println!("{} Saved: {}", "✅".green(), output_path);
+        }
+    } else {
+        // Play audio
+        if !cli.quiet {
+            println!("{} Playing...", "▶️".cyan());
+        }
+        let played = audio::PlaybackLock::acquire()
+            .and_then(|_lock| audio::AudioPlayer::play_samples(audio.samples, audio.sample_rate));
+        if let Err(e) = played {
+            e.show();
+            std::process::exit(1);
+        }
     }
 }
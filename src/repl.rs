@@ -0,0 +1,251 @@
+//! Interactive mode - speak each entered line with the engine kept ready
+//!
+//! Lines starting with `:` are commands (`:voice ryan`, `:speed 1.2`, ...),
+//! everything else is spoken immediately.
+
+use crate::cli::parse_length_scale;
+use crate::ui::ShowError;
+use bibo::audio::{AudioBuffer, AudioOutput, PlaybackLock};
+use bibo::download::VoiceDownloader;
+use bibo::error::{BiboError, Result};
+use bibo::tts::{TtsEngine, VoiceCatalog, VOICE_CATALOG};
+use colored::Colorize;
+use rustyline::completion::{Completer, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::history::DefaultHistory;
+use rustyline::{Context, Editor, Helper, Highlighter, Hinter, Validator};
+use std::path::{Path, PathBuf};
+
+/// REPL commands (name, argument, description)
+const COMMANDS: &[(&str, &str, &str)] = &[
+    (":voice", "<id>", "Switch voice (downloads if needed)"),
+    (":voices", "", "List installed voices"),
+    (
+        ":speed",
+        "<slow|normal|fast|rate>",
+        "Set speed preset or rate (e.g. 1.2)",
+    ),
+    (
+        ":speaker",
+        "<id>",
+        "Select speaker for multi-speaker voices",
+    ),
+    (":replay", "", "Play the last utterance again"),
+    (":save", "<file.wav>", "Save the last utterance"),
+    (":help", "", "Show commands"),
+    (":quit", "", "Exit"),
+];
+
+/// Tab completion for command names and voice IDs
+#[derive(Helper, Hinter, Highlighter, Validator)]
+struct ReplHelper;
+
+impl Completer for ReplHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let line = &line[..pos];
+
+        // `:voice <partial id>`
+        if let Some(partial) = line.strip_prefix(":voice ") {
+            let candidates = VOICE_CATALOG
+                .iter()
+                .filter(|v| v.id.starts_with(&partial.to_lowercase()))
+                .map(|v| Pair {
+                    display: format!("{} ({})", v.id, v.lang),
+                    replacement: v.id.to_string(),
+                })
+                .collect();
+            return Ok((":voice ".len(), candidates));
+        }
+
+        // `:<partial command>`
+        if line.starts_with(':') && !line.contains(' ') {
+            let candidates = COMMANDS
+                .iter()
+                .filter(|(name, _, _)| name.starts_with(line))
+                .map(|(name, _, _)| Pair {
+                    display: name.to_string(),
+                    replacement: name.to_string(),
+                })
+                .collect();
+            return Ok((0, candidates));
+        }
+
+        Ok((pos, vec![]))
+    }
+}
+
+/// Session state
+struct Session {
+    engine: TtsEngine,
+    length_scale: f32,
+    last: Option<AudioBuffer>,
+    quiet: bool,
+}
+
+impl Session {
+    /// Synthesize and play a line of text
+    fn speak(&mut self, text: &str, output: &AudioOutput) -> Result<()> {
+        let buffer = self.engine.synthesize_audio(text, self.length_scale)?;
+        let _lock = PlaybackLock::acquire()?;
+        output.play(&buffer.samples, buffer.sample_rate);
+        self.last = Some(buffer);
+        Ok(())
+    }
+
+    /// Handle a `:command`. Returns false to exit.
+    async fn command(&mut self, input: &str, output: &AudioOutput) -> Result<bool> {
+        let (name, arg) = match input.split_once(char::is_whitespace) {
+            Some((name, arg)) => (name, arg.trim()),
+            None => (input, ""),
+        };
+
+        match name {
+            ":q" | ":quit" | ":exit" => return Ok(false),
+            ":h" | ":help" => show_help(),
+            ":voice" => {
+                if arg.is_empty() {
+                    println!("{} {}", "🎤".cyan(), self.engine.voice_id());
+                    return Ok(true);
+                }
+                if !VoiceCatalog::is_installed(arg) && VoiceCatalog::find(arg).is_some() {
+                    VoiceDownloader::download_voice(arg, crate::ui::voice_progress(self.quiet))
+                        .await?;
+                }
+                // Keeps the session's --no-cache and normalization settings
+                self.engine = self.engine.for_voice(arg)?;
+                println!("{} Voice: {}", "🎤".cyan(), self.engine.voice_id());
+            }
+            ":voices" => {
+                for dir in VoiceCatalog::installed() {
+                    let id = VOICE_CATALOG
+                        .iter()
+                        .find(|v| v.model_dir == dir)
+                        .map(|v| v.id)
+                        .unwrap_or(dir.as_str());
+                    let prefix = if id.eq_ignore_ascii_case(self.engine.voice_id()) {
+                        "→"
+                    } else {
+                        " "
+                    };
+                    println!("  {} {}", prefix, id);
+                }
+            }
+            ":speed" => {
+                self.length_scale = parse_length_scale(arg)?;
+                println!("{} Length scale: {:.2}", "⏩".cyan(), self.length_scale);
+            }
+            ":speaker" => {
+                let id = arg
+                    .parse::<u32>()
+                    .map_err(|_| BiboError::Other(format!("Invalid speaker ID: {}", arg)))?;
+                self.engine.set_speaker(id);
+                println!("{} Speaker: {}", "🗣️".cyan(), id);
+            }
+            ":replay" | ":r" => match &self.last {
+                Some(buffer) => {
+                    let _lock = PlaybackLock::acquire()?;
+                    output.play(&buffer.samples, buffer.sample_rate);
+                }
+                None => println!("{} Nothing to replay yet", "⚠️".yellow()),
+            },
+            ":save" => {
+                let Some(buffer) = &self.last else {
+                    println!("{} Nothing to save yet", "⚠️".yellow());
+                    return Ok(true);
+                };
+                let path = if arg.is_empty() { "last.wav" } else { arg };
+                buffer.save(Path::new(path))?;
+                println!("{} Saved: {}", "✅".green(), path);
+            }
+            _ => println!("{} Unknown command: {} (try :help)", "⚠️".yellow(), name),
+        }
+
+        Ok(true)
+    }
+}
+
+fn show_help() {
+    println!("{}", "Commands:".cyan().bold());
+    for (name, arg, desc) in COMMANDS {
+        println!("  {:<10} {:<24} {}", name, arg, desc);
+    }
+    println!("  Anything else is spoken.");
+}
+
+/// History file location
+fn history_path() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("bibo")
+        .join("history")
+}
+
+/// Run the interactive prompt until EOF or `:quit`
+pub async fn run(engine: TtsEngine, length_scale: f32, quiet: bool) -> Result<()> {
+    let mut editor: Editor<ReplHelper, DefaultHistory> = Editor::new()
+        .map_err(|e| BiboError::Other(format!("Failed to start line editor: {}", e)))?;
+    editor.set_helper(Some(ReplHelper));
+
+    let history = history_path();
+    let _ = editor.load_history(&history);
+
+    let output = AudioOutput::new()?;
+    let mut session = Session {
+        engine,
+        length_scale,
+        last: None,
+        quiet,
+    };
+
+    if !quiet {
+        println!(
+            "{} Interactive mode - type text to speak, :help for commands",
+            "💬".cyan()
+        );
+    }
+
+    loop {
+        let prompt = format!("{}> ", session.engine.voice_id());
+        let line = match editor.readline(&prompt) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(BiboError::Other(format!("Readline error: {}", e))),
+        };
+
+        let input = line.trim();
+        if input.is_empty() {
+            continue;
+        }
+        let _ = editor.add_history_entry(input);
+
+        let result = if input.starts_with(':') {
+            match session.command(input, &output).await {
+                Ok(true) => Ok(()),
+                Ok(false) => break,
+                Err(e) => Err(e),
+            }
+        } else {
+            session.speak(input, &output)
+        };
+
+        // Errors are reported but keep the session alive
+        if let Err(e) = result {
+            e.show();
+        }
+    }
+
+    if let Some(parent) = history.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    let _ = editor.save_history(&history);
+
+    Ok(())
+}
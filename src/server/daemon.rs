@@ -0,0 +1,397 @@
+//! Background daemon - keeps engines loaded and owns the audio device
+//!
+//! The daemon listens on a Unix domain socket. Each connection sends one
+//! JSON request line and gets one JSON response line. The regular CLI
+//! forwards playback requests here when the daemon is running, so
+//! consecutive `bibo "..."` calls queue up instead of overlapping.
+//!
+//! The queue is ordered by priority, FIFO within a level. An urgent job
+//! preempts lower-priority speech, which resumes from the interrupted
+//! sentence once the urgent one has played.
+
+use crate::cli::Priority;
+use crate::server::{resolve_voice, EnginePool};
+use bibo::audio::{AudioOutput, PlaybackLock};
+use bibo::error::{BiboError, Result};
+use bibo::text::split_sentences;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// How often playback checks for skip/stop requests
+const CONTROL_POLL: Duration = Duration::from_millis(30);
+
+/// How long a client may take to send its request line
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long the CLI waits for an answer before playing in-process
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Get daemon socket path (BIBO_SOCKET overrides)
+pub fn socket_path() -> PathBuf {
+    if let Ok(path) = std::env::var("BIBO_SOCKET") {
+        return PathBuf::from(path);
+    }
+    dirs::runtime_dir()
+        .or_else(dirs::cache_dir)
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("bibo")
+        .join("daemon.sock")
+}
+
+/// A queued utterance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Job {
+    #[serde(default)]
+    pub id: u64,
+    pub text: String,
+    pub voice: String,
+    #[serde(default)]
+    pub speaker: u32,
+    pub length_scale: f32,
+    #[serde(default)]
+    pub priority: Priority,
+    /// Bypass the synthesis cache (`--no-cache`)
+    #[serde(default)]
+    pub no_cache: bool,
+}
+
+/// Whether the playing job should keep going
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum Control {
+    #[default]
+    Continue,
+    /// Drop the rest of the job (skip, stop, shutdown)
+    Abort,
+    /// Pause the job so a more urgent one can play, then resume it
+    Preempt,
+}
+
+#[derive(Default)]
+struct Queue {
+    jobs: VecDeque<Job>,
+    current: Option<Job>,
+    next_id: u64,
+    control: Control,
+    shutdown: bool,
+}
+
+/// Queue shared between socket handlers and the player thread
+#[derive(Clone, Default)]
+struct Shared {
+    queue: Arc<Mutex<Queue>>,
+    changed: Arc<Condvar>,
+}
+
+impl Shared {
+    fn enqueue(&self, mut job: Job) -> (u64, usize) {
+        let mut queue = self.queue.lock().unwrap();
+        queue.next_id += 1;
+        job.id = queue.next_id;
+
+        // Urgent speech cuts off anything less important
+        let preempts = job.priority == Priority::Urgent
+            && queue
+                .current
+                .as_ref()
+                .is_some_and(|current| current.priority < Priority::Urgent);
+        if preempts && queue.control == Control::Continue {
+            queue.control = Control::Preempt;
+        }
+
+        // Behind every job of equal or higher priority
+        let index = queue
+            .jobs
+            .iter()
+            .position(|queued| queued.priority < job.priority)
+            .unwrap_or(queue.jobs.len());
+        queue.jobs.insert(index, job);
+        self.changed.notify_all();
+        (queue.next_id, index + 1)
+    }
+
+    /// Put a preempted job back at the front of its priority level
+    fn requeue(&self, job: Job) {
+        let mut queue = self.queue.lock().unwrap();
+        let index = queue
+            .jobs
+            .iter()
+            .position(|queued| queued.priority <= job.priority)
+            .unwrap_or(queue.jobs.len());
+        queue.jobs.insert(index, job);
+    }
+
+    /// Wait for the next job; None once shutting down
+    fn next(&self) -> Option<Job> {
+        let mut queue = self.queue.lock().unwrap();
+        loop {
+            if queue.shutdown {
+                return None;
+            }
+            if let Some(job) = queue.jobs.pop_front() {
+                queue.current = Some(job.clone());
+                queue.control = Control::Continue;
+                return Some(job);
+            }
+            queue = self.changed.wait(queue).unwrap();
+        }
+    }
+
+    fn finish_current(&self) {
+        self.queue.lock().unwrap().current = None;
+    }
+
+    fn control(&self) -> Control {
+        let queue = self.queue.lock().unwrap();
+        if queue.shutdown {
+            Control::Abort
+        } else {
+            queue.control
+        }
+    }
+}
+
+/// Play jobs one at a time, sentence by sentence, honoring skip/stop
+fn run_player(shared: Shared, pool: EnginePool) {
+    let mut output: Option<AudioOutput> = None;
+
+    while let Some(job) = shared.next() {
+        match play_job(&shared, &pool, &mut output, &job) {
+            Ok(Some(rest)) => shared.requeue(rest),
+            Ok(None) => {}
+            Err(e) => eprintln!("{} Job {}: {}", "⚠️".yellow(), job.id, e),
+        }
+        shared.finish_current();
+    }
+}
+
+/// Play a job; returns the unplayed remainder if it was preempted
+fn play_job(
+    shared: &Shared,
+    pool: &EnginePool,
+    output: &mut Option<AudioOutput>,
+    job: &Job,
+) -> Result<Option<Job>> {
+    let voice_id =
+        resolve_voice(&job.voice).ok_or_else(|| BiboError::VoiceNotFound(job.voice.clone()))?;
+    let mut engine = pool.get(voice_id)?.as_ref().clone();
+    engine.set_speaker(job.speaker);
+    if job.no_cache {
+        engine.set_cache(None);
+    }
+
+    let sentences = split_sentences(&job.text);
+    // Resume from the sentence that was cut off
+    let remainder = |from: usize| Job {
+        text: sentences[from..].join(" "),
+        ..job.clone()
+    };
+
+    for (i, sentence) in sentences.iter().enumerate() {
+        match shared.control() {
+            Control::Continue => {}
+            Control::Abort => return Ok(None),
+            Control::Preempt => return Ok(Some(remainder(i))),
+        }
+        let audio = engine.synthesize_audio(sentence, job.length_scale)?;
+
+        // Held per sentence, so in-process playback can take its turn
+        // between sentences and the queue can still preempt
+        let _lock = PlaybackLock::acquire()?;
+
+        // Opened lazily and kept for the daemon's lifetime
+        if output.is_none() {
+            *output = Some(AudioOutput::new()?);
+        }
+        let Some(output) = output.as_ref() else { break };
+
+        output.append(&audio.samples, audio.sample_rate);
+        while output.is_playing() {
+            match shared.control() {
+                Control::Continue => thread::sleep(CONTROL_POLL),
+                Control::Abort => {
+                    output.stop();
+                    return Ok(None);
+                }
+                Control::Preempt => {
+                    output.stop();
+                    return Ok(Some(remainder(i)));
+                }
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// Handle one request line
+fn handle_request(shared: &Shared, pool: &EnginePool, request: &Value) -> Value {
+    match request["cmd"].as_str().unwrap_or("") {
+        "ping" => json!({ "ok": true, "version": env!("CARGO_PKG_VERSION") }),
+        "speak" => {
+            let job: Job = match serde_json::from_value(request.clone()) {
+                Ok(job) => job,
+                Err(e) => return json!({ "ok": false, "error": format!("Invalid request: {}", e) }),
+            };
+            // Fail fast so the client can fall back to in-process playback
+            let ready = resolve_voice(&job.voice)
+                .ok_or_else(|| BiboError::VoiceNotFound(job.voice.clone()))
+                .and_then(|id| pool.get(id));
+            if let Err(e) = ready {
+                return json!({ "ok": false, "error": e.to_string() });
+            }
+            let (id, position) = shared.enqueue(job);
+            json!({ "ok": true, "id": id, "position": position })
+        }
+        "queue" => {
+            let queue = shared.queue.lock().unwrap();
+            json!({ "ok": true, "current": queue.current, "queued": queue.jobs })
+        }
+        "skip" => {
+            let mut queue = shared.queue.lock().unwrap();
+            let skipped = queue.current.is_some();
+            if skipped {
+                queue.control = Control::Abort;
+            }
+            json!({ "ok": true, "skipped": skipped })
+        }
+        "stop" => {
+            let mut queue = shared.queue.lock().unwrap();
+            let cleared = queue.jobs.len();
+            queue.jobs.clear();
+            if queue.current.is_some() {
+                queue.control = Control::Abort;
+            }
+            json!({ "ok": true, "cleared": cleared })
+        }
+        "shutdown" => {
+            let mut queue = shared.queue.lock().unwrap();
+            queue.shutdown = true;
+            shared.changed.notify_all();
+            json!({ "ok": true })
+        }
+        other => json!({ "ok": false, "error": format!("Unknown command: {}", other) }),
+    }
+}
+
+fn handle_connection(
+    stream: UnixStream,
+    shared: &Shared,
+    pool: &EnginePool,
+) -> std::io::Result<()> {
+    // A client that connects and never writes must not hold a handler
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    stream.set_write_timeout(Some(REQUEST_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+
+    let response = match serde_json::from_str::<Value>(&line) {
+        Ok(request) => handle_request(shared, pool, &request),
+        Err(e) => json!({ "ok": false, "error": format!("Invalid JSON: {}", e) }),
+    };
+
+    let mut stream = stream;
+    writeln!(stream, "{}", response)
+}
+
+/// Run the daemon in the foreground until `bibo daemon shutdown`
+pub fn run(pool: EnginePool, quiet: bool) -> Result<()> {
+    let path = socket_path();
+    if client::request(&json!({ "cmd": "ping" })).is_ok() {
+        return Err(BiboError::Other(format!(
+            "Daemon already running on {}",
+            path.display()
+        )));
+    }
+
+    // A socket file without a listener is left over from a crash
+    let _ = fs::remove_file(&path);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| BiboError::Other(format!("Failed to create socket dir: {}", e)))?;
+    }
+    let listener = UnixListener::bind(&path)
+        .map_err(|e| BiboError::Other(format!("Failed to bind {}: {}", path.display(), e)))?;
+
+    println!("{} Listening on unix://{}", "🛰️".cyan(), path.display());
+    if !quiet {
+        println!("   bibo \"text\" now plays through the daemon (Ctrl+C to stop)");
+    }
+
+    let shared = Shared::default();
+    let player = {
+        let shared = shared.clone();
+        let pool = pool.clone();
+        thread::spawn(move || run_player(shared, pool))
+    };
+
+    // Each connection gets a thread, since loading a voice for a speak
+    // request can take a while
+    for stream in listener.incoming() {
+        if shared.queue.lock().unwrap().shutdown {
+            break;
+        }
+        let Ok(stream) = stream else { continue };
+        let shared = shared.clone();
+        let pool = pool.clone();
+        let path = path.clone();
+        thread::spawn(move || {
+            let _ = handle_connection(stream, &shared, &pool);
+            // Wake the accept loop so it sees the shutdown
+            if shared.queue.lock().unwrap().shutdown {
+                let _ = UnixStream::connect(&path);
+            }
+        });
+    }
+
+    let _ = player.join();
+    let _ = fs::remove_file(&path);
+    Ok(())
+}
+
+/// Client side of the daemon protocol
+pub mod client {
+    use super::*;
+
+    /// Send a request and wait for the response.
+    /// Errors mean the daemon is not reachable or did not answer in time.
+    pub fn request(request: &Value) -> std::io::Result<Value> {
+        let mut stream = UnixStream::connect(socket_path())?;
+        // A stalled daemon must not hang the caller
+        stream.set_read_timeout(Some(RESPONSE_TIMEOUT))?;
+        stream.set_write_timeout(Some(RESPONSE_TIMEOUT))?;
+        writeln!(stream, "{}", request)?;
+
+        let mut line = String::new();
+        BufReader::new(stream).read_line(&mut line)?;
+        serde_json::from_str(&line)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+
+    /// Forward a speak request. Ok(None) when no daemon is running.
+    pub fn speak(job: &Job) -> Result<Option<Value>> {
+        let mut req = serde_json::to_value(job).unwrap_or_default();
+        req["cmd"] = json!("speak");
+
+        let Ok(response) = request(&req) else {
+            return Ok(None);
+        };
+        if response["ok"].as_bool() != Some(true) {
+            let error = response["error"].as_str().unwrap_or("unknown error");
+            return Err(BiboError::Other(format!(
+                "Daemon rejected request: {}",
+                error
+            )));
+        }
+        Ok(Some(response))
+    }
+}
@@ -0,0 +1,242 @@
+//! HTTP server with an OpenAI-compatible speech endpoint
+//!
+//! `POST /v1/audio/speech` accepts the OpenAI request body, so existing
+//! clients can point their base URL at bibo.
+
+use crate::server::{resolve_voice, EnginePool, OPENAI_VOICES};
+use axum::extract::State;
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use bibo::error::{BiboError, Result};
+use bibo::tts::{VoiceCatalog, VOICE_CATALOG};
+use colored::Colorize;
+use serde::Deserialize;
+use serde_json::json;
+
+/// Maximum input length, matching the OpenAI API
+const MAX_INPUT_CHARS: usize = 4096;
+
+/// Models accepted in the `model` field (all map to the selected voice)
+const MODELS: &[&str] = &["bibo", "tts-1", "tts-1-hd"];
+
+#[derive(Clone)]
+struct AppState {
+    pool: EnginePool,
+    default_voice: String,
+}
+
+/// `POST /v1/audio/speech` request body (`model` is accepted and ignored)
+#[derive(Debug, Deserialize)]
+struct SpeechRequest {
+    input: String,
+    #[serde(default)]
+    voice: Option<String>,
+    #[serde(default)]
+    speed: Option<f32>,
+    #[serde(default)]
+    response_format: Option<String>,
+}
+
+/// OpenAI-style error response
+struct ApiError {
+    status: StatusCode,
+    message: String,
+    param: Option<&'static str>,
+}
+
+impl ApiError {
+    fn invalid(message: impl Into<String>, param: &'static str) -> Self {
+        Self {
+            status: StatusCode::BAD_REQUEST,
+            message: message.into(),
+            param: Some(param),
+        }
+    }
+}
+
+impl From<BiboError> for ApiError {
+    fn from(e: BiboError) -> Self {
+        let status = match e {
+            BiboError::VoiceNotFound(_) | BiboError::VoiceNotInstalled(_) => {
+                StatusCode::BAD_REQUEST
+            }
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        Self {
+            status,
+            message: e.to_string(),
+            param: None,
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let kind = if self.status.is_client_error() {
+            "invalid_request_error"
+        } else {
+            "server_error"
+        };
+        let body = json!({
+            "error": {
+                "message": self.message,
+                "type": kind,
+                "param": self.param,
+                "code": null,
+            }
+        });
+        (self.status, Json(body)).into_response()
+    }
+}
+
+async fn speech(
+    State(state): State<AppState>,
+    Json(req): Json<SpeechRequest>,
+) -> std::result::Result<Response, ApiError> {
+    if req.input.trim().is_empty() {
+        return Err(ApiError::invalid("input must not be empty", "input"));
+    }
+    if req.input.chars().count() > MAX_INPUT_CHARS {
+        return Err(ApiError::invalid(
+            format!("input exceeds {} characters", MAX_INPUT_CHARS),
+            "input",
+        ));
+    }
+
+    let requested = req.voice.as_deref().unwrap_or(&state.default_voice);
+    let voice_id = resolve_voice(requested)
+        .ok_or_else(|| ApiError::invalid(format!("Unknown voice: {}", requested), "voice"))?;
+
+    let speed = req.speed.unwrap_or(1.0);
+    if !(0.25..=4.0).contains(&speed) {
+        return Err(ApiError::invalid(
+            "speed must be between 0.25 and 4.0",
+            "speed",
+        ));
+    }
+    let length_scale = 1.0 / speed;
+
+    let format = req
+        .response_format
+        .as_deref()
+        .unwrap_or("wav")
+        .to_lowercase();
+    let content_type = match format.as_str() {
+        "wav" => "audio/wav",
+        "pcm" => "audio/pcm",
+        other => {
+            return Err(ApiError::invalid(
+                format!(
+                    "Unsupported response_format '{}' (supported: wav, pcm)",
+                    other
+                ),
+                "response_format",
+            ))
+        }
+    };
+
+    let engine = state.pool.get(voice_id)?;
+    let input = req.input;
+    let audio = tokio::task::spawn_blocking(move || engine.synthesize_audio(&input, length_scale))
+        .await
+        .map_err(|e| BiboError::SynthesisFailed(e.to_string()))??;
+
+    let body = match format.as_str() {
+        "pcm" => audio.to_pcm_bytes(),
+        _ => audio.to_wav_bytes()?,
+    };
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (
+                header::HeaderName::from_static("x-sample-rate"),
+                audio.sample_rate.to_string(),
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+async fn models() -> Json<serde_json::Value> {
+    let data: Vec<_> = MODELS
+        .iter()
+        .map(|id| json!({ "id": id, "object": "model", "owned_by": "bibo" }))
+        .collect();
+    Json(json!({ "object": "list", "data": data }))
+}
+
+async fn voices() -> Json<serde_json::Value> {
+    let installed = VoiceCatalog::installed();
+    let data: Vec<_> = VOICE_CATALOG
+        .iter()
+        .map(|v| {
+            let aliases: Vec<_> = OPENAI_VOICES
+                .iter()
+                .filter(|(_, id)| *id == v.id)
+                .map(|(alias, _)| *alias)
+                .collect();
+            json!({
+                "id": v.id,
+                "name": v.name,
+                "lang": v.lang,
+                "gender": v.gender.to_string(),
+                "quality": v.quality,
+                "installed": installed.iter().any(|dir| dir == v.model_dir),
+                "aliases": aliases,
+            })
+        })
+        .collect();
+    Json(json!({ "object": "list", "data": data }))
+}
+
+async fn health() -> Json<serde_json::Value> {
+    Json(json!({ "status": "ok", "version": env!("CARGO_PKG_VERSION") }))
+}
+
+/// Build the router
+fn router(state: AppState) -> Router {
+    Router::new()
+        .route("/v1/audio/speech", post(speech))
+        .route("/v1/models", get(models))
+        .route("/v1/voices", get(voices))
+        .route("/health", get(health))
+        .with_state(state)
+}
+
+/// Serve until the process is stopped
+pub async fn serve(
+    host: &str,
+    port: u16,
+    default_voice: &str,
+    pool: EnginePool,
+    quiet: bool,
+) -> Result<()> {
+    let listener = tokio::net::TcpListener::bind((host, port))
+        .await
+        .map_err(|e| BiboError::Other(format!("Failed to bind {}:{}: {}", host, port, e)))?;
+    let addr = listener
+        .local_addr()
+        .map_err(|e| BiboError::Other(e.to_string()))?;
+
+    // Always announce the bound address so `--port 0` callers can find it
+    println!("{} Listening on http://{}", "🌐".cyan(), addr);
+    if !quiet {
+        println!(
+            "   POST /v1/audio/speech  (default voice: {})",
+            default_voice
+        );
+    }
+
+    let state = AppState {
+        pool,
+        default_voice: default_voice.to_string(),
+    };
+
+    axum::serve(listener, router(state))
+        .await
+        .map_err(|e| BiboError::Other(format!("Server error: {}", e)))
+}
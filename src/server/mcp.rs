@@ -0,0 +1,287 @@
+//! MCP (Model Context Protocol) stdio server
+//!
+//! JSON-RPC 2.0 messages, one per line on stdin/stdout. Exposes `speak`,
+//! `list_voices`, and `synthesize_to_file` tools. `speak` queues audio on a
+//! playback thread and returns immediately so agents are never stalled.
+//! Stdout carries protocol messages only; diagnostics go to stderr.
+
+use crate::server::{resolve_voice, EnginePool};
+use bibo::audio::{AudioOutput, PlaybackLock};
+use bibo::error::{BiboError, Result};
+use bibo::tts::{TtsEngine, VoiceCatalog, VOICE_CATALOG};
+use serde_json::{json, Value};
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+/// Protocol revisions we can speak, newest last
+const PROTOCOL_VERSIONS: &[&str] = &["2024-11-05", "2025-03-26", "2025-06-18"];
+
+/// JSON-RPC error codes
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const PARSE_ERROR: i64 = -32700;
+
+/// An utterance waiting for the playback thread
+struct SpeakJob {
+    engine: Arc<TtsEngine>,
+    text: String,
+    length_scale: f32,
+}
+
+/// Server state shared by tool calls
+struct McpServer {
+    pool: EnginePool,
+    default_voice: String,
+    queue: mpsc::Sender<SpeakJob>,
+    pending: Arc<AtomicUsize>,
+}
+
+/// Tool definitions advertised by `tools/list`
+fn tool_definitions() -> Value {
+    json!([
+        {
+            "name": "speak",
+            "description": "Speak text aloud on the user's machine. Returns immediately; audio is queued and played in order.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "text": { "type": "string", "description": "Text to speak" },
+                    "voice": { "type": "string", "description": "Voice ID (see list_voices)" },
+                    "speed": { "type": "number", "description": "Rate multiplier, 0.25-4.0 (default 1.0)" }
+                },
+                "required": ["text"]
+            }
+        },
+        {
+            "name": "list_voices",
+            "description": "List text-to-speech voices with language and install status.",
+            "inputSchema": { "type": "object", "properties": {} }
+        },
+        {
+            "name": "synthesize_to_file",
+            "description": "Synthesize text to a WAV file without playing it.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "text": { "type": "string", "description": "Text to synthesize" },
+                    "path": { "type": "string", "description": "Output .wav path" },
+                    "voice": { "type": "string", "description": "Voice ID (see list_voices)" },
+                    "speed": { "type": "number", "description": "Rate multiplier, 0.25-4.0 (default 1.0)" }
+                },
+                "required": ["text", "path"]
+            }
+        }
+    ])
+}
+
+impl McpServer {
+    /// Resolve engine and length scale from tool arguments
+    fn engine_for(&self, args: &Value) -> Result<(Arc<TtsEngine>, f32)> {
+        let requested = args["voice"].as_str().unwrap_or(&self.default_voice);
+        let voice_id = resolve_voice(requested)
+            .ok_or_else(|| BiboError::VoiceNotFound(requested.to_string()))?;
+
+        let speed = args["speed"].as_f64().unwrap_or(1.0) as f32;
+        if !(0.25..=4.0).contains(&speed) {
+            return Err(BiboError::InvalidSpeed(speed.to_string()));
+        }
+
+        Ok((self.pool.get(voice_id)?, 1.0 / speed))
+    }
+
+    fn speak(&self, args: &Value) -> Result<String> {
+        let text = required_str(args, "text")?;
+        let (engine, length_scale) = self.engine_for(args)?;
+
+        let position = self.pending.fetch_add(1, Ordering::SeqCst) + 1;
+        let job = SpeakJob {
+            engine,
+            text: text.to_string(),
+            length_scale,
+        };
+        self.queue
+            .send(job)
+            .map_err(|_| BiboError::PlaybackFailed("Playback thread stopped".to_string()))?;
+
+        Ok(format!("Queued for playback (position {})", position))
+    }
+
+    fn list_voices(&self) -> String {
+        let installed = VoiceCatalog::installed();
+        let voices: Vec<Value> = VOICE_CATALOG
+            .iter()
+            .map(|v| {
+                json!({
+                    "id": v.id,
+                    "name": v.name,
+                    "lang": v.lang,
+                    "gender": v.gender.to_string(),
+                    "installed": installed.iter().any(|dir| dir == v.model_dir),
+                    "default": v.id.eq_ignore_ascii_case(&self.default_voice),
+                })
+            })
+            .collect();
+        serde_json::to_string_pretty(&voices).unwrap_or_default()
+    }
+
+    fn synthesize_to_file(&self, args: &Value) -> Result<String> {
+        let text = required_str(args, "text")?;
+        let path = required_str(args, "path")?;
+        let (engine, length_scale) = self.engine_for(args)?;
+
+        let audio = engine.synthesize_audio(text, length_scale)?;
+        audio.save(Path::new(path))?;
+
+        Ok(format!(
+            "Saved {} ({:.1}s, {} Hz)",
+            path,
+            audio.duration_secs(),
+            audio.sample_rate
+        ))
+    }
+
+    /// Dispatch a `tools/call` request
+    fn call_tool(&self, params: &Value) -> Value {
+        let args = params.get("arguments").cloned().unwrap_or(json!({}));
+        let result = match params["name"].as_str().unwrap_or("") {
+            "speak" => self.speak(&args),
+            "list_voices" => Ok(self.list_voices()),
+            "synthesize_to_file" => self.synthesize_to_file(&args),
+            other => Err(BiboError::Other(format!("Unknown tool: {}", other))),
+        };
+
+        // Tool failures are results the model can read, not protocol errors
+        match result {
+            Ok(text) => json!({ "content": [{ "type": "text", "text": text }], "isError": false }),
+            Err(e) => {
+                json!({ "content": [{ "type": "text", "text": e.to_string() }], "isError": true })
+            }
+        }
+    }
+
+    /// Handle one JSON-RPC message, returning the response for requests
+    fn handle(&self, message: &Value) -> Option<Value> {
+        // Notifications (no id) never get a response
+        let id = message.get("id")?.clone();
+        let params = message.get("params").cloned().unwrap_or(json!({}));
+
+        let result = match message["method"].as_str().unwrap_or("") {
+            "initialize" => {
+                let requested = params["protocolVersion"].as_str().unwrap_or("");
+                let version = PROTOCOL_VERSIONS
+                    .iter()
+                    .find(|v| **v == requested)
+                    .unwrap_or(PROTOCOL_VERSIONS.last().unwrap());
+                json!({
+                    "protocolVersion": version,
+                    "capabilities": { "tools": {} },
+                    "serverInfo": { "name": "bibo", "version": env!("CARGO_PKG_VERSION") },
+                    "instructions": "Use speak for short spoken status updates; it returns immediately."
+                })
+            }
+            "ping" => json!({}),
+            "tools/list" => json!({ "tools": tool_definitions() }),
+            "tools/call" => {
+                if params["name"].as_str().is_none() {
+                    return Some(error_response(id, INVALID_PARAMS, "Missing tool name"));
+                }
+                self.call_tool(&params)
+            }
+            method => {
+                return Some(error_response(
+                    id,
+                    METHOD_NOT_FOUND,
+                    &format!("Method not found: {}", method),
+                ))
+            }
+        };
+
+        Some(json!({ "jsonrpc": "2.0", "id": id, "result": result }))
+    }
+}
+
+fn required_str<'a>(args: &'a Value, key: &str) -> Result<&'a str> {
+    args[key]
+        .as_str()
+        .filter(|s| !s.trim().is_empty())
+        .ok_or_else(|| BiboError::Other(format!("Missing required argument: {}", key)))
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+/// Play queued utterances in order on a dedicated thread
+fn spawn_player(pending: Arc<AtomicUsize>) -> (mpsc::Sender<SpeakJob>, JoinHandle<()>) {
+    let (tx, rx) = mpsc::channel::<SpeakJob>();
+
+    let handle = thread::spawn(move || {
+        // Opened lazily so servers without an audio device can still write files
+        let mut output: Option<AudioOutput> = None;
+
+        for job in rx {
+            let played = job
+                .engine
+                .synthesize_audio(&job.text, job.length_scale)
+                .and_then(|audio| {
+                    if output.is_none() {
+                        output = Some(AudioOutput::new()?);
+                    }
+                    if let Some(output) = &output {
+                        let _lock = PlaybackLock::acquire()?;
+                        output.play(&audio.samples, audio.sample_rate);
+                    }
+                    Ok(())
+                });
+            if let Err(e) = played {
+                eprintln!("bibo mcp: {}", e);
+            }
+            pending.fetch_sub(1, Ordering::SeqCst);
+        }
+    });
+
+    (tx, handle)
+}
+
+/// Serve MCP over stdin/stdout until stdin closes
+pub fn serve(default_voice: &str, pool: EnginePool) -> Result<()> {
+    let pending = Arc::new(AtomicUsize::new(0));
+    let (queue, player) = spawn_player(Arc::clone(&pending));
+    let server = McpServer {
+        pool,
+        default_voice: default_voice.to_string(),
+        queue,
+        pending,
+    };
+
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+
+    for line in stdin.lock().lines() {
+        let line = line.map_err(|e| BiboError::Other(format!("Failed to read stdin: {}", e)))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<Value>(&line) {
+            Ok(message) => server.handle(&message),
+            Err(e) => Some(error_response(Value::Null, PARSE_ERROR, &e.to_string())),
+        };
+
+        if let Some(response) = response {
+            writeln!(stdout, "{}", response)
+                .and_then(|_| stdout.flush())
+                .map_err(|e| BiboError::Other(format!("Failed to write stdout: {}", e)))?;
+        }
+    }
+
+    // Client disconnected: finish speaking what was already queued
+    drop(server);
+    let _ = player.join();
+
+    Ok(())
+}
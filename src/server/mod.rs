@@ -0,0 +1,80 @@
+//! Server modes - long-running front ends that keep engines warm
+
+pub mod daemon;
+pub mod http;
+pub mod mcp;
+pub mod speechd;
+pub mod wyoming;
+
+use bibo::error::{BiboError, Result};
+use bibo::text::normalize::Normalization;
+use bibo::tts::{TtsEngine, VoiceCatalog};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// OpenAI voice names mapped to the closest catalog voice
+pub const OPENAI_VOICES: &[(&str, &str)] = &[
+    ("alloy", "lessac"),
+    ("ash", "joe"),
+    ("coral", "amy"),
+    ("echo", "ryan"),
+    ("fable", "alan"),
+    ("nova", "amy"),
+    ("onyx", "joe"),
+    ("sage", "alba"),
+    ("shimmer", "alba"),
+];
+
+/// Resolve a requested voice name to a catalog voice ID
+pub fn resolve_voice(name: &str) -> Option<&'static str> {
+    if let Some(voice) = VoiceCatalog::find(name) {
+        return Some(voice.id);
+    }
+    OPENAI_VOICES
+        .iter()
+        .find(|(alias, _)| alias.eq_ignore_ascii_case(name))
+        .map(|(_, id)| *id)
+}
+
+/// Engines kept alive per voice so repeated requests skip engine setup
+#[derive(Clone)]
+pub struct EnginePool {
+    engines: Arc<Mutex<HashMap<String, Arc<TtsEngine>>>>,
+    /// False with `--no-cache`: new engines skip the synthesis cache
+    cache: bool,
+    /// User dictionaries and symbol rules for every engine
+    normalization: Normalization,
+}
+
+impl EnginePool {
+    pub fn new(cache: bool, normalization: Normalization) -> Self {
+        Self {
+            engines: Arc::default(),
+            cache,
+            normalization,
+        }
+    }
+
+    /// Get or create the engine for a voice ID
+    pub fn get(&self, voice_id: &str) -> Result<Arc<TtsEngine>> {
+        let key = voice_id.to_lowercase();
+        let mut engines = self.engines.lock().unwrap();
+
+        if let Some(engine) = engines.get(&key) {
+            return Ok(Arc::clone(engine));
+        }
+
+        if !VoiceCatalog::is_installed(voice_id) {
+            return Err(BiboError::VoiceNotInstalled(voice_id.to_string()));
+        }
+
+        let mut engine = TtsEngine::new(voice_id)?;
+        if !self.cache {
+            engine.set_cache(None);
+        }
+        engine.set_normalization(self.normalization.clone());
+        let engine = Arc::new(engine);
+        engines.insert(key, Arc::clone(&engine));
+        Ok(engine)
+    }
+}
@@ -0,0 +1,417 @@
+//! speech-dispatcher output module
+//!
+//! Speaks the module protocol on stdin/stdout: commands such as `SET`,
+//! `SPEAK`, and `STOP` come in as lines, multi-line payloads end with a
+//! lone `.`, and replies use numeric status codes. Speaking is reported
+//! asynchronously with `701 BEGIN`, `700` index marks, and `702 END`,
+//! `703 STOP`, or `704 PAUSE`. Run through the `sd_bibo` binary.
+
+use crate::server::{resolve_voice, EnginePool};
+use bibo::audio::{AudioOutput, PlaybackLock};
+use bibo::error::{BiboError, Result};
+use bibo::tts::{VoiceCatalog, VOICE_CATALOG};
+use std::io::{self, BufRead, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// How often playback checks for stop/pause
+const CONTROL_POLL: Duration = Duration::from_millis(20);
+
+/// Voice settings sent with `SET`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settings {
+    /// -100 (slowest) to 100 (fastest)
+    pub rate: i32,
+    /// Voice type: MALE1, FEMALE2, CHILD_MALE, ...
+    pub voice_type: String,
+    /// Language code, e.g. "en" or "en-US"
+    pub language: String,
+    /// Voice name from `LIST VOICES`
+    pub synthesis_voice: Option<String>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            rate: 0,
+            voice_type: "MALE1".to_string(),
+            language: "en".to_string(),
+            synthesis_voice: None,
+        }
+    }
+}
+
+impl Settings {
+    /// Apply one `key=value` line
+    fn apply(&mut self, key: &str, value: &str) {
+        match key {
+            "rate" => self.rate = value.parse().unwrap_or(0).clamp(-100, 100),
+            "voice" => self.voice_type = value.to_uppercase(),
+            "language" => self.language = value.to_string(),
+            "synthesis_voice" => {
+                self.synthesis_voice =
+                    (!value.is_empty() && value != "NULL").then(|| value.to_string())
+            }
+            // pitch, volume, punctuation and spelling modes are not supported
+            _ => {}
+        }
+    }
+
+    /// Rate mapped exponentially onto 0.25x-4x, the range `--speed` accepts
+    pub fn length_scale(&self) -> f32 {
+        1.0 / 4f32.powf(self.rate as f32 / 100.0)
+    }
+
+    /// Explicit voice, else an installed voice for the language and gender
+    pub fn voice(&self, default_voice: &str) -> Option<&'static str> {
+        if let Some(voice) = self.synthesis_voice.as_deref().and_then(resolve_voice) {
+            return Some(voice);
+        }
+
+        let language = self.language.replace('-', "_").to_lowercase();
+        let gender = if self.voice_type.contains("FEMALE") {
+            'F'
+        } else {
+            'M'
+        };
+        let installed = VoiceCatalog::installed();
+        let candidates: Vec<_> = VOICE_CATALOG
+            .iter()
+            .filter(|v| installed.iter().any(|dir| dir == v.model_dir))
+            .filter(|v| {
+                v.languages()
+                    .iter()
+                    .any(|l| l.to_lowercase().starts_with(&language))
+            })
+            .collect();
+
+        candidates
+            .iter()
+            .find(|v| v.gender == gender)
+            .or(candidates.first())
+            .map(|v| v.id)
+            .or_else(|| resolve_voice(default_voice))
+    }
+}
+
+/// Piece of a message: text to speak or an index mark to report
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment {
+    Text(String),
+    Mark(String),
+}
+
+/// Parse the SSML subset speech-dispatcher sends: `<speak>`, `<mark/>`,
+/// and entity-escaped text. Other tags are dropped, keeping their text.
+pub fn parse_ssml(message: &str) -> Vec<Segment> {
+    let mut segments = vec![];
+    let mut text = String::new();
+    let mut rest = message;
+
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('>') else {
+            text.push_str(&rest[start..]);
+            rest = "";
+            break;
+        };
+        let tag = &rest[start + 1..start + end];
+        rest = &rest[start + end + 1..];
+
+        if let Some(name) = tag_attribute(tag, "mark", "name") {
+            push_text(&mut segments, &mut text);
+            segments.push(Segment::Mark(name));
+        } else if tag.starts_with("break") {
+            text.push(' ');
+        }
+    }
+    text.push_str(rest);
+    push_text(&mut segments, &mut text);
+    segments
+}
+
+fn push_text(segments: &mut Vec<Segment>, text: &mut String) {
+    let decoded = decode_entities(text);
+    let spoken = decoded.split_whitespace().collect::<Vec<_>>().join(" ");
+    if !spoken.is_empty() {
+        segments.push(Segment::Text(spoken));
+    }
+    text.clear();
+}
+
+/// Attribute value of an element, e.g. `name` from `mark name="m1"/`
+fn tag_attribute(tag: &str, element: &str, attribute: &str) -> Option<String> {
+    let body = tag.strip_prefix(element)?;
+    let start = body.find(&format!("{}=", attribute))? + attribute.len() + 1;
+    let quote = body[start..].chars().next()?;
+    let value = &body[start + 1..];
+    Some(value[..value.find(quote)?].to_string())
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Spoken form of a `KEY` event name such as "shift_a" or "space"
+fn key_text(key: &str) -> String {
+    key.replace('_', " ")
+}
+
+/// A message waiting for the speaker thread
+struct Utterance {
+    id: u64,
+    segments: Vec<Segment>,
+    voice: &'static str,
+    length_scale: f32,
+}
+
+/// Stdout shared by replies and asynchronous events
+#[derive(Clone)]
+struct Output(Arc<Mutex<io::Stdout>>);
+
+impl Output {
+    fn send(&self, lines: &[&str]) {
+        let mut stdout = self.0.lock().unwrap();
+        for line in lines {
+            let _ = writeln!(stdout, "{}", line);
+        }
+        let _ = stdout.flush();
+    }
+}
+
+/// Stop/pause requests, expressed as "every utterance up to this id"
+#[derive(Default)]
+struct Control {
+    stop_upto: AtomicU64,
+    pause_upto: AtomicU64,
+}
+
+enum Interrupt {
+    Stop,
+    Pause,
+}
+
+impl Interrupt {
+    /// Event reported when speech ends this way
+    fn event(&self) -> &'static str {
+        match self {
+            Interrupt::Stop => "703 STOP",
+            Interrupt::Pause => "704 PAUSE",
+        }
+    }
+}
+
+impl Control {
+    fn check(&self, id: u64) -> Option<Interrupt> {
+        if id <= self.stop_upto.load(Ordering::SeqCst) {
+            Some(Interrupt::Stop)
+        } else if id <= self.pause_upto.load(Ordering::SeqCst) {
+            Some(Interrupt::Pause)
+        } else {
+            None
+        }
+    }
+}
+
+/// Speak utterances one at a time, reporting progress events
+fn spawn_speaker(
+    out: Output,
+    control: Arc<Control>,
+    pool: EnginePool,
+) -> (mpsc::Sender<Utterance>, thread::JoinHandle<()>) {
+    let (tx, rx) = mpsc::channel::<Utterance>();
+
+    let handle = thread::spawn(move || {
+        let mut audio_out: Option<AudioOutput> = None;
+        for utterance in rx {
+            if let Err(e) = speak(&out, &control, &pool, &mut audio_out, &utterance) {
+                eprintln!("sd_bibo: {}", e);
+                // Let speech-dispatcher move on to the next message
+                out.send(&[Interrupt::Stop.event()]);
+            }
+        }
+    });
+
+    (tx, handle)
+}
+
+fn speak(
+    out: &Output,
+    control: &Control,
+    pool: &EnginePool,
+    audio_out: &mut Option<AudioOutput>,
+    utterance: &Utterance,
+) -> Result<()> {
+    // Interrupted while still waiting behind another message
+    if let Some(interrupt) = control.check(utterance.id) {
+        out.send(&[interrupt.event()]);
+        return Ok(());
+    }
+    let engine = pool.get(utterance.voice)?;
+    out.send(&["701 BEGIN"]);
+
+    for segment in &utterance.segments {
+        // Pausing is only possible at index marks
+        let interrupt = match segment {
+            Segment::Mark(name) => {
+                out.send(&[&format!("700-{}", name), "700 INDEX MARK"]);
+                control.check(utterance.id)
+            }
+            Segment::Text(text) => {
+                let audio = engine.synthesize_audio(text, utterance.length_scale)?;
+                if audio_out.is_none() {
+                    *audio_out = Some(AudioOutput::new()?);
+                }
+                let Some(audio_out) = audio_out.as_ref() else {
+                    break;
+                };
+                let _lock = PlaybackLock::acquire()?;
+                audio_out.append(&audio.samples, audio.sample_rate);
+
+                let mut stopped = None;
+                while audio_out.is_playing() {
+                    if let Some(Interrupt::Stop) = control.check(utterance.id) {
+                        audio_out.stop();
+                        stopped = Some(Interrupt::Stop);
+                        break;
+                    }
+                    thread::sleep(CONTROL_POLL);
+                }
+                stopped
+            }
+        };
+
+        if let Some(interrupt) = interrupt {
+            out.send(&[interrupt.event()]);
+            return Ok(());
+        }
+    }
+
+    out.send(&["702 END"]);
+    Ok(())
+}
+
+/// Read a multi-line block terminated by a lone `.`
+fn read_block(lines: &mut impl Iterator<Item = io::Result<String>>) -> Result<Vec<String>> {
+    let mut block = vec![];
+    for line in lines {
+        let line = line.map_err(|e| BiboError::Other(format!("Failed to read stdin: {}", e)))?;
+        if line == "." {
+            return Ok(block);
+        }
+        // Lines starting with a dot are escaped by doubling it
+        block.push(line.strip_prefix('.').map(String::from).unwrap_or(line));
+    }
+    Err(BiboError::Other("Unexpected end of input".to_string()))
+}
+
+/// `LIST VOICES` entries: name, language, variant
+fn voice_list() -> Vec<String> {
+    let installed = VoiceCatalog::installed();
+    VOICE_CATALOG
+        .iter()
+        .filter(|v| installed.iter().any(|dir| dir == v.model_dir))
+        .flat_map(|v| {
+            v.languages()
+                .into_iter()
+                .map(move |lang| format!("200-{}\t{}\tnone", v.id, lang.replace('_', "-")))
+        })
+        .collect()
+}
+
+/// Run the module protocol until `QUIT` or end of input
+pub fn serve(default_voice: &str, pool: EnginePool) -> Result<()> {
+    let out = Output(Arc::new(Mutex::new(io::stdout())));
+    let control = Arc::new(Control::default());
+    let (speaker, handle) = spawn_speaker(out.clone(), Arc::clone(&control), pool);
+
+    let mut settings = Settings::default();
+    let mut next_id = 0;
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+
+    while let Some(line) = lines.next() {
+        let line = line.map_err(|e| BiboError::Other(format!("Failed to read stdin: {}", e)))?;
+        let command = line.trim().to_uppercase();
+
+        match command.as_str() {
+            "INIT" => out.send(&[
+                "299-bibo: Initialized successfully.",
+                "299 OK LOADED SUCCESSFULLY",
+            ]),
+            "AUDIO" => {
+                out.send(&["207 OK RECEIVING AUDIO SETTINGS"]);
+                read_block(&mut lines)?;
+                out.send(&["203 OK AUDIO INITIALIZED"]);
+            }
+            "LOGLEVEL" | "DEBUG" => {
+                out.send(&["207 OK RECEIVING LOGLEVEL SETTINGS"]);
+                read_block(&mut lines)?;
+                out.send(&["203 OK LOG LEVEL SET"]);
+            }
+            "SET" => {
+                out.send(&["203 OK RECEIVING SETTINGS"]);
+                for entry in read_block(&mut lines)? {
+                    if let Some((key, value)) = entry.split_once('=') {
+                        settings.apply(key.trim(), value.trim());
+                    }
+                }
+                out.send(&["203 OK SETTINGS RECEIVED"]);
+            }
+            "SPEAK" | "CHAR" | "KEY" | "SOUND_ICON" => {
+                out.send(&["202 OK RECEIVING MESSAGE"]);
+                let message = read_block(&mut lines)?.join("\n");
+                let segments = match command.as_str() {
+                    "SPEAK" => parse_ssml(&message),
+                    "KEY" => vec![Segment::Text(key_text(&message))],
+                    _ => vec![Segment::Text(decode_entities(&message))],
+                };
+
+                let Some(voice) = settings.voice(default_voice) else {
+                    out.send(&["301 ERROR CANT SPEAK"]);
+                    continue;
+                };
+                out.send(&["200 OK SPEAKING"]);
+
+                next_id += 1;
+                let utterance = Utterance {
+                    id: next_id,
+                    segments,
+                    voice,
+                    length_scale: settings.length_scale(),
+                };
+                if speaker.send(utterance).is_err() {
+                    return Err(BiboError::PlaybackFailed(
+                        "Speaker thread stopped".to_string(),
+                    ));
+                }
+            }
+            // No reply; the speaker reports 703 STOP / 704 PAUSE
+            "STOP" => control.stop_upto.store(next_id, Ordering::SeqCst),
+            "PAUSE" => control.pause_upto.store(next_id, Ordering::SeqCst),
+            "LIST VOICES" => {
+                let mut reply = voice_list();
+                reply.push("200 OK VOICE LIST SENT".to_string());
+                let reply: Vec<&str> = reply.iter().map(String::as_str).collect();
+                out.send(&reply);
+            }
+            "QUIT" => {
+                control.stop_upto.store(next_id, Ordering::SeqCst);
+                out.send(&["210 OK QUIT"]);
+                break;
+            }
+            "" => {}
+            _ => out.send(&["300 ERR UNKNOWN COMMAND"]),
+        }
+    }
+
+    drop(speaker);
+    let _ = handle.join();
+    Ok(())
+}
@@ -0,0 +1,351 @@
+//! Wyoming protocol server for Home Assistant
+//!
+//! Wyoming events are a JSON header line, optionally followed by
+//! `data_length` bytes of JSON data and `payload_length` bytes of payload.
+//! A `describe` gets an `info` reply listing installed voices; `synthesize`
+//! is answered with `audio-start`, a series of `audio-chunk`s, and
+//! `audio-stop`, sent sentence by sentence as audio is produced.
+
+use crate::server::{resolve_voice, EnginePool};
+use bibo::error::{BiboError, Result};
+use bibo::text::split_sentences;
+use bibo::tts::{VoiceCatalog, VOICE_CATALOG};
+use colored::Colorize;
+use serde_json::{json, Map, Value};
+use std::io;
+use tokio::io::{
+    AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader,
+};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Wyoming protocol version we speak
+pub const PROTOCOL_VERSION: &str = "1.5.4";
+
+/// Samples per `audio-chunk` event
+const CHUNK_SAMPLES: usize = 1024;
+
+/// Largest `data_length` accepted from a client
+const MAX_DATA_BYTES: u64 = 1 << 20;
+
+/// Largest `payload_length` accepted from a client
+const MAX_PAYLOAD_BYTES: u64 = 4 << 20;
+
+/// A single Wyoming event
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Event {
+    pub kind: String,
+    pub data: Map<String, Value>,
+    pub payload: Vec<u8>,
+}
+
+impl Event {
+    pub fn new(kind: &str, data: Value) -> Self {
+        Self {
+            kind: kind.to_string(),
+            data: data.as_object().cloned().unwrap_or_default(),
+            payload: vec![],
+        }
+    }
+
+    pub fn with_payload(mut self, payload: Vec<u8>) -> Self {
+        self.payload = payload;
+        self
+    }
+}
+
+/// Read the next event, or None at end of stream
+pub async fn read_event<R: AsyncBufRead + Unpin>(reader: &mut R) -> io::Result<Option<Event>> {
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).await? == 0 {
+            return Ok(None);
+        }
+        if !line.trim().is_empty() {
+            break;
+        }
+    }
+
+    let header: Value =
+        serde_json::from_str(&line).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let kind = header["type"]
+        .as_str()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "event without type"))?
+        .to_string();
+
+    // Data may be inline in the header and/or follow it
+    let mut data = header["data"].as_object().cloned().unwrap_or_default();
+    // Lengths are checked before allocating, since any client can send them
+    let data_length = length(&header, "data_length", MAX_DATA_BYTES)?;
+    if data_length > 0 {
+        let mut buf = vec![0; data_length];
+        reader.read_exact(&mut buf).await?;
+        let extra: Value = serde_json::from_slice(&buf)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if let Value::Object(extra) = extra {
+            data.extend(extra);
+        }
+    }
+
+    let payload_length = length(&header, "payload_length", MAX_PAYLOAD_BYTES)?;
+    let mut payload = vec![0; payload_length];
+    if payload_length > 0 {
+        reader.read_exact(&mut payload).await?;
+    }
+
+    Ok(Some(Event {
+        kind,
+        data,
+        payload,
+    }))
+}
+
+/// Length field of an event header, rejected above `max` bytes
+fn length(header: &Value, field: &str, max: u64) -> io::Result<usize> {
+    let length = header[field].as_u64().unwrap_or(0);
+    if length > max {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} {} exceeds {} bytes", field, length, max),
+        ));
+    }
+    Ok(length as usize)
+}
+
+/// Write an event
+pub async fn write_event<W: AsyncWrite + Unpin>(writer: &mut W, event: &Event) -> io::Result<()> {
+    let data = if event.data.is_empty() {
+        vec![]
+    } else {
+        serde_json::to_vec(&event.data)?
+    };
+
+    let mut header = json!({ "type": event.kind, "version": PROTOCOL_VERSION });
+    if !data.is_empty() {
+        header["data_length"] = json!(data.len());
+    }
+    if !event.payload.is_empty() {
+        header["payload_length"] = json!(event.payload.len());
+    }
+
+    let mut bytes = serde_json::to_vec(&header)?;
+    bytes.push(b'\n');
+    bytes.extend_from_slice(&data);
+    bytes.extend_from_slice(&event.payload);
+    writer.write_all(&bytes).await?;
+    writer.flush().await
+}
+
+/// `info` reply describing installed voices
+fn info_event() -> Event {
+    let installed = VoiceCatalog::installed();
+    let attribution =
+        json!({ "name": "sherpa-onnx", "url": "https://github.com/k2-fsa/sherpa-onnx" });
+
+    let voices: Vec<Value> = VOICE_CATALOG
+        .iter()
+        .filter(|v| installed.iter().any(|dir| dir == v.model_dir))
+        .map(|v| {
+            let speakers = (v.num_speakers > 1).then(|| {
+                (0..v.num_speakers)
+                    .map(|id| json!({ "name": id.to_string() }))
+                    .collect::<Vec<_>>()
+            });
+            json!({
+                "name": v.id,
+                "description": format!("{} ({}, {})", v.name, v.lang, v.quality),
+                "attribution": attribution,
+                "installed": true,
+                "version": null,
+                "languages": v.languages(),
+                "speakers": speakers,
+            })
+        })
+        .collect();
+
+    Event::new(
+        "info",
+        json!({
+            "asr": [],
+            "tts": [{
+                "name": "bibo",
+                "description": "Fast, local neural text-to-speech",
+                "attribution": { "name": "bibo", "url": "https://github.com/larrykoo711/bibo" },
+                "installed": true,
+                "version": env!("CARGO_PKG_VERSION"),
+                "voices": voices,
+                "supports_synthesize_streaming": false,
+            }],
+            "handle": [],
+            "intent": [],
+            "wake": [],
+            "mic": [],
+            "snd": [],
+        }),
+    )
+}
+
+/// Pick a voice from the `synthesize` voice settings
+fn select_voice(voice: &Value, default_voice: &str) -> Option<&'static str> {
+    if let Some(name) = voice["name"].as_str() {
+        return resolve_voice(name);
+    }
+
+    // Language only: first installed voice that speaks it
+    if let Some(language) = voice["language"].as_str() {
+        let language = language.replace('-', "_").to_lowercase();
+        let installed = VoiceCatalog::installed();
+        let found = VOICE_CATALOG.iter().find(|v| {
+            installed.iter().any(|dir| dir == v.model_dir)
+                && v.languages()
+                    .iter()
+                    .any(|l| l.to_lowercase().starts_with(&language))
+        });
+        if let Some(v) = found {
+            return Some(v.id);
+        }
+    }
+
+    resolve_voice(default_voice)
+}
+
+/// Synthesize sentence by sentence, streaming audio events as they're ready
+async fn synthesize<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    data: &Map<String, Value>,
+    pool: &EnginePool,
+    default_voice: &str,
+    length_scale: f32,
+) -> Result<()> {
+    let text = data.get("text").and_then(|t| t.as_str()).unwrap_or("");
+    let voice = data.get("voice").cloned().unwrap_or(Value::Null);
+
+    let voice_id = select_voice(&voice, default_voice).ok_or_else(|| {
+        BiboError::VoiceNotFound(voice["name"].as_str().unwrap_or("").to_string())
+    })?;
+
+    let mut engine = pool.get(voice_id)?.as_ref().clone();
+    if let Some(speaker) = voice["speaker"].as_str().and_then(|s| s.parse().ok()) {
+        engine.set_speaker(speaker);
+    }
+
+    let io_err = |e: io::Error| BiboError::Other(format!("Wyoming write failed: {}", e));
+    let mut started = false;
+    let mut rate = engine.sample_rate();
+
+    for sentence in split_sentences(text) {
+        let sentence_engine = engine.clone();
+        let audio = tokio::task::spawn_blocking(move || {
+            sentence_engine.synthesize_audio(&sentence, length_scale)
+        })
+        .await
+        .map_err(|e| BiboError::SynthesisFailed(e.to_string()))??;
+
+        let format = json!({ "rate": audio.sample_rate, "width": 2, "channels": 1 });
+        if !started {
+            rate = audio.sample_rate;
+            write_event(writer, &Event::new("audio-start", format.clone()))
+                .await
+                .map_err(io_err)?;
+            started = true;
+        }
+
+        for chunk in audio.samples.chunks(CHUNK_SAMPLES) {
+            let payload = chunk.iter().flat_map(|s| s.to_le_bytes()).collect();
+            write_event(
+                writer,
+                &Event::new("audio-chunk", format.clone()).with_payload(payload),
+            )
+            .await
+            .map_err(io_err)?;
+        }
+    }
+
+    if !started {
+        let format = json!({ "rate": rate, "width": 2, "channels": 1 });
+        write_event(writer, &Event::new("audio-start", format))
+            .await
+            .map_err(io_err)?;
+    }
+    write_event(writer, &Event::new("audio-stop", json!({})))
+        .await
+        .map_err(io_err)
+}
+
+/// Serve one client connection until it disconnects
+async fn handle_client(
+    stream: TcpStream,
+    pool: EnginePool,
+    default_voice: String,
+    length_scale: f32,
+) -> io::Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader);
+
+    while let Some(event) = read_event(&mut reader).await? {
+        match event.kind.as_str() {
+            "describe" => write_event(&mut writer, &info_event()).await?,
+            "synthesize" => {
+                if let Err(e) = synthesize(
+                    &mut writer,
+                    &event.data,
+                    &pool,
+                    &default_voice,
+                    length_scale,
+                )
+                .await
+                {
+                    let error = Event::new(
+                        "error",
+                        json!({ "text": e.to_string(), "code": "tts-failed" }),
+                    );
+                    write_event(&mut writer, &error).await?;
+                }
+            }
+            // Events for other services are not ours to answer
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Serve until the process is stopped
+pub async fn serve(
+    host: &str,
+    port: u16,
+    default_voice: &str,
+    length_scale: f32,
+    pool: EnginePool,
+    quiet: bool,
+) -> Result<()> {
+    let listener = TcpListener::bind((host, port))
+        .await
+        .map_err(|e| BiboError::Other(format!("Failed to bind {}:{}: {}", host, port, e)))?;
+    let addr = listener
+        .local_addr()
+        .map_err(|e| BiboError::Other(e.to_string()))?;
+
+    // Always announce the bound address so `--port 0` callers can find it
+    println!("{} Listening on tcp://{}", "🏠".cyan(), addr);
+    if !quiet {
+        println!("   Wyoming TTS (default voice: {})", default_voice);
+    }
+
+    loop {
+        let (stream, peer) = listener
+            .accept()
+            .await
+            .map_err(|e| BiboError::Other(format!("Accept failed: {}", e)))?;
+
+        let pool = pool.clone();
+        let default_voice = default_voice.to_string();
+        tokio::spawn(async move {
+            if let Err(e) = handle_client(stream, pool, default_voice, length_scale).await {
+                if !quiet {
+                    eprintln!("{} Wyoming client {}: {}", "⚠️".yellow(), peer, e);
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,81 @@
+//! Streaming mode - speak lines as they arrive on stdin
+//!
+//! `tail -f build.log | bibo --stream`: a reader thread feeds a bounded
+//! queue while the main loop synthesizes and plays one sentence at a time.
+
+use crate::ui::ShowError;
+use bibo::audio::{AudioOutput, PlaybackLock};
+use bibo::error::Result;
+use bibo::input::lines::{LineFilter, LineQueue, Overflow};
+use bibo::text::split_sentences;
+use bibo::tts::TtsEngine;
+use colored::Colorize;
+use std::io::{self, BufRead};
+use std::sync::Arc;
+use std::thread;
+
+/// Streaming mode options
+pub struct StreamOptions {
+    pub length_scale: f32,
+    pub queue_size: usize,
+    pub overflow: Overflow,
+    pub filter: LineFilter,
+    pub quiet: bool,
+}
+
+/// Read stdin line by line and speak each accepted line until EOF
+pub fn run(engine: &TtsEngine, options: StreamOptions) -> Result<()> {
+    let queue = Arc::new(LineQueue::new(options.queue_size, options.overflow));
+    let quiet = options.quiet;
+
+    let reader_queue = Arc::clone(&queue);
+    let filter = options.filter;
+    let reader = thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            let Ok(line) = line else { break };
+            if !filter.accepts(&line) {
+                continue;
+            }
+            let dropped = reader_queue.push(line.trim().to_string());
+            if dropped > 0 && !quiet {
+                eprintln!(
+                    "{} Falling behind, skipped {} line(s)",
+                    "⏭️".yellow(),
+                    dropped
+                );
+            }
+        }
+        reader_queue.close();
+    });
+
+    if !quiet {
+        println!("{} Streaming stdin (Ctrl+C to stop)", "📡".cyan());
+    }
+
+    let output = AudioOutput::new()?;
+
+    while let Some(line) = queue.pop() {
+        if !quiet {
+            println!("{} {}", "🔊".cyan(), line);
+        }
+        // Long lines start playing after their first sentence
+        for sentence in split_sentences(&line) {
+            let played = engine
+                .synthesize_audio(&sentence, options.length_scale)
+                .and_then(|audio| {
+                    let _lock = PlaybackLock::acquire()?;
+                    output.play(&audio.samples, audio.sample_rate);
+                    Ok(())
+                });
+            match played {
+                Ok(()) => {}
+                // One bad sentence should not end the stream
+                Err(e) if !quiet => e.show(),
+                Err(_) => {}
+            }
+        }
+    }
+
+    let _ = reader.join();
+    Ok(())
+}
@@ -0,0 +1,182 @@
+//! Reading source code aloud
+//!
+//! Code blocks can be skipped, announced, read verbatim, or read as
+//! identifiers: `parseHttpRequest(max_len)` becomes "parse http request
+//! open paren max len close paren".
+
+use serde::Deserialize;
+
+/// How code blocks are read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum CodeMode {
+    /// Leave code blocks out
+    #[default]
+    Skip,
+    /// Say that a code block was skipped, with its language and length
+    Announce,
+    /// Read the code as written
+    Verbatim,
+    /// Read identifiers as words and symbols by name
+    Identifiers,
+}
+
+/// Spoken names of symbols, longest first
+const SYMBOLS: &[(&str, &str)] = &[
+    ("...", "ellipsis"),
+    ("::", "double colon"),
+    ("->", "arrow"),
+    ("=>", "fat arrow"),
+    ("==", "equals equals"),
+    ("!=", "not equals"),
+    ("<=", "less or equal"),
+    (">=", "greater or equal"),
+    ("&&", "and and"),
+    ("||", "or or"),
+    ("+=", "plus equals"),
+    ("-=", "minus equals"),
+    ("(", "open paren"),
+    (")", "close paren"),
+    ("[", "open bracket"),
+    ("]", "close bracket"),
+    ("{", "open brace"),
+    ("}", "close brace"),
+    ("<", "less than"),
+    (">", "greater than"),
+    (";", "semicolon"),
+    (":", "colon"),
+    (",", "comma"),
+    (".", "dot"),
+    ("=", "equals"),
+    ("+", "plus"),
+    ("-", "minus"),
+    ("*", "star"),
+    ("/", "slash"),
+    ("\\", "backslash"),
+    ("%", "percent"),
+    ("&", "ampersand"),
+    ("|", "pipe"),
+    ("!", "bang"),
+    ("?", "question mark"),
+    ("#", "hash"),
+    ("@", "at"),
+    ("$", "dollar"),
+    ("^", "caret"),
+    ("~", "tilde"),
+    ("\"", "quote"),
+    ("'", "tick"),
+    ("`", "backtick"),
+];
+
+/// Speakable text for a code block, or None when it should be left out
+pub fn speak(code: &str, lang: Option<&str>, mode: CodeMode) -> Option<String> {
+    let code = code.trim_end();
+    match mode {
+        CodeMode::Skip => None,
+        CodeMode::Announce => {
+            let lines = code.lines().count();
+            let lines = if lines == 1 {
+                "1 line".to_string()
+            } else {
+                format!("{} lines", lines)
+            };
+            Some(match lang.map(language_name) {
+                Some(lang) => format!("Code block in {}, {}, skipped.", lang, lines),
+                None => format!("Code block, {}, skipped.", lines),
+            })
+        }
+        CodeMode::Verbatim => Some(code.to_string()),
+        CodeMode::Identifiers => Some(
+            code.lines()
+                .map(speak_line)
+                .filter(|line| !line.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        ),
+    }
+}
+
+/// One line of code with identifiers split and symbols named
+pub fn speak_line(line: &str) -> String {
+    let mut words: Vec<String> = vec![];
+    let mut rest = line.trim();
+
+    while let Some(c) = rest.chars().next() {
+        if c.is_whitespace() {
+            rest = rest.trim_start();
+        } else if c.is_alphanumeric() || c == '_' {
+            let end = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            words.extend(split_identifier(&rest[..end]));
+            rest = &rest[end..];
+        } else if let Some((symbol, name)) = SYMBOLS.iter().find(|(s, _)| rest.starts_with(s)) {
+            words.push(name.to_string());
+            rest = &rest[symbol.len()..];
+        } else {
+            words.push(c.to_string());
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+
+    words.join(" ")
+}
+
+/// Split snake_case and camelCase identifiers into lowercase words:
+/// "parseHTTPRequest" -> ["parse", "http", "request"]
+pub fn split_identifier(identifier: &str) -> Vec<String> {
+    let mut words = vec![];
+    for part in identifier.split('_').filter(|p| !p.is_empty()) {
+        let chars: Vec<char> = part.chars().collect();
+        let mut word = String::new();
+        for (i, &c) in chars.iter().enumerate() {
+            let prev = i.checked_sub(1).map(|p| chars[p]);
+            let next = chars.get(i + 1);
+            let boundary = match prev {
+                None => false,
+                // fooBar, foo2Bar
+                Some(p) if c.is_uppercase() && (p.is_lowercase() || p.is_ascii_digit()) => true,
+                // HTTPRequest: the R starts a new word
+                Some(p) if c.is_uppercase() && p.is_uppercase() => {
+                    next.is_some_and(|n| n.is_lowercase())
+                }
+                // v2, utf8 stay whole; 2fa splits
+                Some(p) => p.is_ascii_digit() && c.is_alphabetic() && word.len() > 1,
+            };
+            if boundary && !word.is_empty() {
+                words.push(std::mem::take(&mut word).to_lowercase());
+            }
+            word.push(c);
+        }
+        if !word.is_empty() {
+            words.push(word.to_lowercase());
+        }
+    }
+    words
+}
+
+/// Display name of a fence language tag ("rs" -> "Rust")
+fn language_name(tag: &str) -> String {
+    let name = match tag.to_lowercase().as_str() {
+        "rs" | "rust" => "Rust",
+        "py" | "python" => "Python",
+        "js" | "javascript" => "JavaScript",
+        "ts" | "typescript" => "TypeScript",
+        "sh" | "bash" | "zsh" | "shell" | "console" => "shell",
+        "go" | "golang" => "Go",
+        "c" => "C",
+        "cpp" | "c++" | "cc" => "C plus plus",
+        "cs" | "csharp" => "C sharp",
+        "rb" | "ruby" => "Ruby",
+        "kt" | "kotlin" => "Kotlin",
+        "yml" | "yaml" => "YAML",
+        "json" => "JSON",
+        "toml" => "TOML",
+        "sql" => "SQL",
+        "html" => "HTML",
+        "css" => "CSS",
+        _ => return tag.to_string(),
+    };
+    name.to_string()
+}
@@ -0,0 +1,246 @@
+//! Dialogue scripts - one voice per character
+//!
+//! A cast header between `---` lines maps characters to voices, then each
+//! `NAME: text` line is spoken by that character:
+//!
+//! ```text
+//! ---
+//! ALICE: amy speed=1.1
+//! BOB: voice=aishell3 speaker=12
+//! gap: 0.5s
+//! ---
+//! ALICE: Did the deploy go out?
+//! BOB: It did.
+//!   Indented lines continue the previous line.
+//! [pause 2s]
+//! ALICE: Great.
+//! ```
+//!
+//! `gap` is the silence between lines (default 0.4s) and `[pause ...]`
+//! inserts a longer one. Lines starting with `#` are comments.
+
+use super::{parse_duration, Segment};
+use crate::error::{BiboError, Result};
+use crate::tts::{parse_length_scale, VoiceCatalog};
+
+/// Silence between lines unless the header sets `gap`
+const DEFAULT_GAP: f32 = 0.4;
+
+/// Voice settings for one character
+#[derive(Debug)]
+struct Character {
+    name: String,
+    voice: &'static str,
+    speaker: u32,
+    rate: f32,
+}
+
+/// Parse a dialogue script into segments for `TtsEngine::synthesize_segments`
+pub fn parse(input: &str) -> Result<Vec<Segment>> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line))
+        .filter(|(_, line)| !line.trim_start().starts_with('#'))
+        .skip_while(|(_, line)| line.trim().is_empty())
+        .peekable();
+
+    if lines.next_if(|(_, line)| line.trim() == "---").is_none() {
+        return Err(invalid(1, "Missing cast header (--- NAME: voice ---)"));
+    }
+
+    let mut cast: Vec<Character> = vec![];
+    let mut gap = DEFAULT_GAP;
+    let mut closed = false;
+    for (number, line) in lines.by_ref() {
+        let line = line.trim();
+        if line == "---" {
+            closed = true;
+            break;
+        }
+        if line.is_empty() {
+            continue;
+        }
+        let (key, value) = line
+            .split_once(':')
+            .ok_or_else(|| invalid(number, "Expected NAME: voice"))?;
+        let key = key.trim();
+        if key.eq_ignore_ascii_case("gap") {
+            gap = parse_duration(value).ok_or_else(|| invalid(number, "Invalid gap"))?;
+        } else if cast.iter().any(|c| c.name.eq_ignore_ascii_case(key)) {
+            return Err(invalid(number, &format!("{} is cast twice", key)));
+        } else {
+            cast.push(character(number, key, value)?);
+        }
+    }
+    if !closed {
+        return Err(invalid(1, "Cast header is never closed with ---"));
+    }
+    if cast.is_empty() {
+        return Err(invalid(1, "Cast header names no characters"));
+    }
+
+    let mut script = Script {
+        cast,
+        gap,
+        segments: vec![],
+        current: None,
+        paused: true,
+    };
+
+    for (number, line) in lines {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        if let Some(pause) = trimmed
+            .strip_prefix("[pause")
+            .and_then(|rest| rest.strip_suffix(']'))
+        {
+            let secs = parse_duration(pause).ok_or_else(|| invalid(number, "Invalid pause"))?;
+            script.pause(secs);
+            continue;
+        }
+
+        let speaker = trimmed.split_once(':').and_then(|(name, text)| {
+            let index = script
+                .cast
+                .iter()
+                .position(|c| c.name.eq_ignore_ascii_case(name.trim()))?;
+            Some((index, text.trim()))
+        });
+        match speaker {
+            Some((index, text)) => script.start_line(index, text),
+            None if looks_like_name(trimmed) => {
+                let name = trimmed.split(':').next().unwrap_or_default();
+                return Err(invalid(number, &format!("{} is not in the cast", name)));
+            }
+            None => match script.current.as_mut() {
+                Some((_, text)) => {
+                    text.push(' ');
+                    text.push_str(trimmed);
+                }
+                None => return Err(invalid(number, "Expected NAME: text")),
+            },
+        }
+    }
+
+    let segments = script.finish();
+    if segments.is_empty() {
+        return Err(BiboError::NoTextProvided);
+    }
+    Ok(segments)
+}
+
+/// Script body being compiled
+struct Script {
+    cast: Vec<Character>,
+    gap: f32,
+    segments: Vec<Segment>,
+    /// Line being read: (character, text so far)
+    current: Option<(usize, String)>,
+    /// Whether the next line follows a pause instead of a gap
+    paused: bool,
+}
+
+impl Script {
+    fn start_line(&mut self, character: usize, text: &str) {
+        self.end_line();
+        self.current = Some((character, text.to_string()));
+    }
+
+    fn pause(&mut self, secs: f32) {
+        self.end_line();
+        if !self.segments.is_empty() {
+            self.segments.push(Segment::Silence(secs));
+            self.paused = true;
+        }
+    }
+
+    fn end_line(&mut self) {
+        let Some((index, text)) = self.current.take() else {
+            return;
+        };
+        // Lines with nothing to say are dropped
+        if text.is_empty() {
+            return;
+        }
+        if !self.paused {
+            self.segments.push(Segment::Silence(self.gap));
+        }
+        self.paused = false;
+
+        let character = &self.cast[index];
+        self.segments.push(Segment::Speech {
+            text,
+            voice: Some(character.voice.to_string()),
+            speaker: Some(character.speaker),
+            rate: character.rate,
+        });
+    }
+
+    fn finish(mut self) -> Vec<Segment> {
+        self.end_line();
+        self.segments
+    }
+}
+
+/// `amy speed=1.1` or `voice=aishell3 speaker=12`
+fn character(number: usize, name: &str, spec: &str) -> Result<Character> {
+    let mut voice = None;
+    let mut speaker = 0;
+    let mut rate = 1.0;
+
+    for token in spec.split_whitespace() {
+        match token.split_once('=') {
+            None | Some(("voice", _)) => {
+                let id = token.trim_start_matches("voice=");
+                let found = VoiceCatalog::find(id)
+                    .ok_or_else(|| invalid(number, &format!("Unknown voice {}", id)))?;
+                voice = Some(found);
+            }
+            Some(("speaker", value)) => {
+                speaker = value
+                    .parse()
+                    .map_err(|_| invalid(number, &format!("Invalid speaker {}", value)))?;
+            }
+            Some(("speed", value)) => {
+                rate = 1.0
+                    / parse_length_scale(value)
+                        .map_err(|_| invalid(number, &format!("Invalid speed {}", value)))?;
+            }
+            Some((key, _)) => return Err(invalid(number, &format!("Unknown setting {}", key))),
+        }
+    }
+
+    let voice = voice.ok_or_else(|| invalid(number, &format!("No voice for {}", name)))?;
+    if speaker >= voice.num_speakers {
+        return Err(invalid(
+            number,
+            &format!("{} has {} speaker(s)", voice.id, voice.num_speakers),
+        ));
+    }
+    Ok(Character {
+        name: name.to_string(),
+        voice: voice.id,
+        speaker,
+        rate,
+    })
+}
+
+/// An all-caps `NAME:` prefix, as used for characters
+fn looks_like_name(line: &str) -> bool {
+    let Some((name, _)) = line.split_once(':') else {
+        return false;
+    };
+    !name.is_empty()
+        && name.chars().any(|c| c.is_alphabetic())
+        && name
+            .chars()
+            .all(|c| c.is_uppercase() || c.is_ascii_digit() || c == '_' || c == '-')
+}
+
+fn invalid(line: usize, message: &str) -> BiboError {
+    BiboError::InvalidDialogue(format!("line {}: {}", line, message))
+}
@@ -0,0 +1,314 @@
+//! EPUB input - chapters in reading order
+//!
+//! The package file gives the spine (reading order) and the table of
+//! contents gives chapter titles: the EPUB 3 `nav` document, or the EPUB 2
+//! NCX. A spine document without a TOC entry continues the chapter before
+//! it, so chapters split across files are read as one. Chapter documents
+//! are XHTML, read by [`html`](super::html).
+
+use super::html::{self, attr, tokenize, Token};
+use super::markdown::{Hint, Rules};
+use super::Segment;
+use crate::error::{BiboError, Result};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek};
+use std::path::Path;
+use zip::ZipArchive;
+
+/// A book's title and readable chapters
+#[derive(Debug, Clone, PartialEq)]
+pub struct Book {
+    pub title: String,
+    pub chapters: Vec<Chapter>,
+}
+
+/// One chapter: its title and the XHTML documents it spans
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chapter {
+    pub title: String,
+    pub documents: Vec<String>,
+}
+
+/// A manifest entry of the package file
+struct Item {
+    path: String,
+    media_type: String,
+    properties: String,
+}
+
+impl Book {
+    /// Read an `.epub` file
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = File::open(path)
+            .map_err(|e| BiboError::FileNotFound(format!("{}: {}", path.display(), e)))?;
+        Self::read(file)
+    }
+
+    /// Read an EPUB container
+    pub fn read(reader: impl Read + Seek) -> Result<Self> {
+        let mut zip = ZipArchive::new(reader)
+            .map_err(|e| BiboError::InvalidEpub(format!("Not a zip archive: {}", e)))?;
+
+        let container = entry(&mut zip, "META-INF/container.xml")?;
+        let package_path = tokenize(&container)
+            .iter()
+            .find_map(|token| match token {
+                Token::Open { name, attrs, .. } if name == "rootfile" => {
+                    attr(attrs, "full-path").map(str::to_string)
+                }
+                _ => None,
+            })
+            .ok_or_else(|| BiboError::InvalidEpub("No package file in container.xml".into()))?;
+        let package = entry(&mut zip, &package_path)?;
+        let base = parent(&package_path);
+
+        // Title, manifest, and spine from the package file
+        let mut title = String::new();
+        let mut in_title = false;
+        let mut items: HashMap<String, Item> = HashMap::new();
+        let mut spine = vec![];
+        let mut ncx = None;
+        for token in tokenize(&package) {
+            match token {
+                Token::Open { name, attrs, .. } => {
+                    let attr = |key| attr(&attrs, key).unwrap_or("");
+                    match name.as_str() {
+                        "dc:title" => in_title = title.is_empty(),
+                        "item" => {
+                            items.insert(
+                                attr("id").to_string(),
+                                Item {
+                                    path: resolve(base, attr("href")),
+                                    media_type: attr("media-type").to_string(),
+                                    properties: attr("properties").to_string(),
+                                },
+                            );
+                        }
+                        "spine" => ncx = Some(attr("toc").to_string()).filter(|id| !id.is_empty()),
+                        "itemref" if attr("linear") != "no" => {
+                            spine.push(attr("idref").to_string())
+                        }
+                        _ => {}
+                    }
+                }
+                Token::Text(text) if in_title => {
+                    title = html::decode_entities(text.trim());
+                    in_title = false;
+                }
+                _ => {}
+            }
+        }
+
+        // Chapter titles by document, in TOC order
+        let nav = items
+            .values()
+            .find(|item| item.properties.split_whitespace().any(|p| p == "nav"));
+        let ncx = ncx.and_then(|id| items.get(&id)).or_else(|| {
+            items
+                .values()
+                .find(|item| item.media_type == "application/x-dtbncx+xml")
+        });
+        let toc = match (nav, ncx) {
+            (Some(nav), _) => nav_titles(&entry(&mut zip, &nav.path)?, parent(&nav.path)),
+            (None, Some(ncx)) => ncx_titles(&entry(&mut zip, &ncx.path)?, parent(&ncx.path)),
+            (None, None) => HashMap::new(),
+        };
+
+        let mut chapters: Vec<Chapter> = vec![];
+        for id in spine {
+            let Some(item) = items.get(&id) else {
+                continue;
+            };
+            // The navigation document is read for titles, not aloud
+            let is_nav = nav.is_some_and(|nav| nav.path == item.path);
+            if !item.media_type.contains("html") || is_nav {
+                continue;
+            }
+            let document = entry(&mut zip, &item.path)?;
+            let heading = html::parse(&document)
+                .into_iter()
+                .find(|block| matches!(block.hint, Hint::Heading(_)))
+                .map(|block| block.text);
+            match (toc.get(&item.path), chapters.last_mut()) {
+                (None, Some(chapter)) if !toc.is_empty() => chapter.documents.push(document),
+                (found, _) => {
+                    let title = found
+                        .cloned()
+                        .or(heading)
+                        .unwrap_or_else(|| format!("Chapter {}", chapters.len() + 1));
+                    chapters.push(Chapter {
+                        title,
+                        documents: vec![document],
+                    });
+                }
+            }
+        }
+
+        // Covers and image pages have nothing to read
+        chapters.retain(|chapter| !chapter.text().trim().is_empty());
+        if chapters.is_empty() {
+            return Err(BiboError::InvalidEpub("No readable chapters".into()));
+        }
+        Ok(Self { title, chapters })
+    }
+
+    /// Plain text of every chapter, separated by blank lines
+    pub fn text(&self) -> String {
+        self.chapters
+            .iter()
+            .map(Chapter::text)
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+impl Chapter {
+    /// Plain text of the chapter
+    pub fn text(&self) -> String {
+        self.documents
+            .iter()
+            .map(|document| html::to_text(document))
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    /// Speech and pauses for the chapter, paced like Markdown
+    pub fn render(&self, rules: &Rules) -> Vec<Segment> {
+        let mut segments = vec![];
+        for document in &self.documents {
+            let rendered = html::render(document, rules);
+            if rendered.is_empty() {
+                continue;
+            }
+            if !segments.is_empty() && rules.paragraph_pause > 0.0 {
+                segments.push(Segment::Silence(rules.paragraph_pause));
+            }
+            segments.extend(rendered);
+        }
+        segments
+    }
+}
+
+/// Text of a file in the container
+fn entry<R: Read + Seek>(zip: &mut ZipArchive<R>, path: &str) -> Result<String> {
+    let mut file = zip
+        .by_name(path)
+        .map_err(|_| BiboError::InvalidEpub(format!("Missing {}", path)))?;
+    let mut text = String::new();
+    file.read_to_string(&mut text)
+        .map_err(|e| BiboError::InvalidEpub(format!("{}: {}", path, e)))?;
+    Ok(text)
+}
+
+/// Titles from an EPUB 3 navigation document's `toc` nav
+fn nav_titles(document: &str, base: &str) -> HashMap<String, String> {
+    let tokens = tokenize(document);
+    let is_toc = |attrs: &[(String, String)]| attr(attrs, "epub:type") == Some("toc");
+    let has_toc = tokens.iter().any(
+        |token| matches!(token, Token::Open { name, attrs, .. } if name == "nav" && is_toc(attrs)),
+    );
+
+    let mut titles = HashMap::new();
+    let mut in_toc = false;
+    let mut link: Option<(String, String)> = None;
+    for token in tokens {
+        match token {
+            Token::Open { name, attrs, .. } if name == "nav" => {
+                in_toc = !has_toc || is_toc(&attrs);
+            }
+            Token::Close(name) if name == "nav" => in_toc = false,
+            Token::Open { name, attrs, .. } if name == "a" && in_toc => {
+                link = attr(&attrs, "href").map(|href| (resolve(base, href), String::new()));
+            }
+            Token::Text(text) => {
+                if let Some((_, title)) = &mut link {
+                    title.push_str(text);
+                }
+            }
+            Token::Close(name) if name == "a" => {
+                if let Some((path, title)) = link.take() {
+                    add_title(&mut titles, path, &title);
+                }
+            }
+            _ => {}
+        }
+    }
+    titles
+}
+
+/// Titles from an EPUB 2 NCX: `<navLabel><text>` then `<content src>`
+fn ncx_titles(document: &str, base: &str) -> HashMap<String, String> {
+    let mut titles = HashMap::new();
+    let mut in_text = false;
+    let mut label = String::new();
+    for token in tokenize(document) {
+        match token {
+            Token::Open { name, .. } if name == "navpoint" => label.clear(),
+            Token::Open { name, .. } if name == "text" => in_text = true,
+            Token::Close(name) if name == "text" => in_text = false,
+            Token::Text(text) if in_text => label.push_str(text),
+            Token::Open { name, attrs, .. } if name == "content" => {
+                if let Some(src) = attr(&attrs, "src") {
+                    add_title(&mut titles, resolve(base, src), &label);
+                }
+            }
+            _ => {}
+        }
+    }
+    titles
+}
+
+/// Keep the first title of each document; later entries are sections
+fn add_title(titles: &mut HashMap<String, String>, path: String, title: &str) {
+    let title = html::decode_entities(&title.split_whitespace().collect::<Vec<_>>().join(" "));
+    if !title.is_empty() {
+        titles.entry(path).or_insert(title);
+    }
+}
+
+/// Directory of a path inside the container ("" at the root)
+fn parent(path: &str) -> &str {
+    path.rsplit_once('/').map_or("", |(dir, _)| dir)
+}
+
+/// Container path of a link relative to `base`, without its fragment
+fn resolve(base: &str, href: &str) -> String {
+    let href = href.split('#').next().unwrap_or("");
+    let mut parts: Vec<&str> = base.split('/').filter(|p| !p.is_empty()).collect();
+    for part in href.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            part => parts.push(part),
+        }
+    }
+    percent_decode(&parts.join("/"))
+}
+
+/// "Chapter%201.xhtml" -> "Chapter 1.xhtml"
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = (bytes[i] == b'%')
+            .then(|| text.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match hex {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
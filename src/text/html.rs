@@ -0,0 +1,601 @@
+//! HTML input - reads the main content of a page like Markdown
+//!
+//! When a page has `<main>` or `<article>` elements only those are read;
+//! otherwise the whole body. Navigation, page headers and footers, scripts,
+//! styles, forms, and hidden elements are skipped. Headings, paragraphs,
+//! lists, tables, quotes, links, and `<pre>` blocks become the same events
+//! as their Markdown counterparts, so [`Rules`] paces and reads them alike.
+
+use super::markdown::{self, Block, Rules};
+use super::{tag_end, Segment};
+use pulldown_cmark::{CodeBlockKind, CowStr, Event, HeadingLevel, LinkType, Tag, TagEnd};
+
+/// Elements whose content is never read
+const SKIPPED: &[&str] = &[
+    "head", "script", "style", "noscript", "template", "nav", "aside", "form", "button", "select",
+    "svg", "iframe", "object", "canvas", "dialog",
+];
+
+/// Elements without content or an end tag
+const VOID: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// Elements whose content is text, not markup
+const RAW: &[&str] = &["script", "style", "textarea", "title"];
+
+/// Elements that separate blocks of text
+const BLOCKS: &[&str] = &[
+    "address",
+    "article",
+    "body",
+    "caption",
+    "center",
+    "dd",
+    "details",
+    "div",
+    "dl",
+    "dt",
+    "figcaption",
+    "figure",
+    "main",
+    "section",
+    "summary",
+];
+
+/// Parse HTML into speakable blocks
+pub fn parse(html: &str) -> Vec<Block> {
+    parse_with(html, &Rules::default())
+}
+
+/// Parse HTML into speakable blocks, reading links and code per `rules`
+pub fn parse_with(html: &str, rules: &Rules) -> Vec<Block> {
+    markdown::walk(Reader::read(html), rules)
+}
+
+/// Render HTML as speech and pauses
+pub fn render(html: &str, rules: &Rules) -> Vec<Segment> {
+    markdown::render_blocks(&parse_with(html, rules), rules)
+}
+
+/// Plain text of the main content, separated by blank lines
+pub fn to_text(html: &str) -> String {
+    markdown::to_text(&parse(html))
+}
+
+pub(super) enum Token<'a> {
+    Text(&'a str),
+    Open {
+        name: String,
+        attrs: Vec<(String, String)>,
+        self_closing: bool,
+    },
+    Close(String),
+}
+
+/// Split markup into text and tags, skipping comments and declarations
+///
+/// Unlike SSML, pages are often sloppy: a `<` that starts no tag is text,
+/// and an unterminated comment or tag ends the document.
+pub(super) fn tokenize(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            tokens.push(Token::Text(rest));
+            break;
+        };
+        let after = &rest[start + 1..];
+        let opens_tag = after
+            .starts_with(|c: char| c.is_ascii_alphabetic() || c == '/' || c == '!' || c == '?');
+        if !opens_tag {
+            // A stray "<": keep it with the text
+            let end = start + 1;
+            tokens.push(Token::Text(&rest[..end]));
+            rest = &rest[end..];
+            continue;
+        }
+        if start > 0 {
+            tokens.push(Token::Text(&rest[..start]));
+        }
+        rest = &rest[start..];
+
+        if rest.starts_with("<!--") {
+            rest = rest.find("-->").map_or("", |end| &rest[end + 3..]);
+            continue;
+        }
+        let Some(end) = tag_end(rest) else {
+            break;
+        };
+        let tag = rest[1..end].trim();
+        rest = &rest[end + 1..];
+        if tag.starts_with(['!', '?']) {
+            continue;
+        }
+
+        if let Some(name) = tag.strip_prefix('/') {
+            let name = name.split(|c: char| c.is_whitespace()).next().unwrap_or("");
+            tokens.push(Token::Close(name.to_lowercase()));
+            continue;
+        }
+        let (tag, self_closing) = match tag.strip_suffix('/') {
+            Some(tag) => (tag.trim_end(), true),
+            None => (tag, false),
+        };
+        let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+        let name = tag[..name_end].to_lowercase();
+        let attrs = parse_attrs(&tag[name_end..]);
+
+        // Script and style content is text until its end tag
+        if RAW.contains(&name.as_str()) && !self_closing {
+            let close = format!("</{}", name);
+            let end = rest.to_ascii_lowercase().find(&close).unwrap_or(rest.len());
+            tokens.push(Token::Open {
+                name: name.clone(),
+                attrs,
+                self_closing,
+            });
+            tokens.push(Token::Text(&rest[..end]));
+            rest = &rest[end..];
+            continue;
+        }
+        tokens.push(Token::Open {
+            name,
+            attrs,
+            self_closing,
+        });
+    }
+
+    tokens
+}
+
+/// `key="value" key='value' key=value key` attributes, entities decoded
+fn parse_attrs(mut rest: &str) -> Vec<(String, String)> {
+    let mut attrs = vec![];
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            return attrs;
+        }
+        let key_end = rest
+            .find(|c: char| c == '=' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let key = rest[..key_end].to_lowercase();
+        rest = rest[key_end..].trim_start();
+        let Some(value) = rest.strip_prefix('=') else {
+            attrs.push((key, String::new()));
+            continue;
+        };
+        let value = value.trim_start();
+        let (raw, next) = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => match value[1..].find(quote) {
+                Some(close) => (&value[1..close + 1], &value[close + 2..]),
+                None => (&value[1..], ""),
+            },
+            _ => {
+                let end = value.find(char::is_whitespace).unwrap_or(value.len());
+                (&value[..end], &value[end..])
+            }
+        };
+        attrs.push((key, decode_entities(raw)));
+        rest = next;
+    }
+}
+
+/// Decode HTML entities (`&amp;`, `&nbsp;`, `&#39;`, `&#x2014;`, ...);
+/// unknown ones are left as written
+pub fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest[1..]
+            .find(';')
+            .filter(|&end| end <= 32)
+            .map(|end| &rest[1..end + 1]);
+        match entity.and_then(entity_char) {
+            Some(c) => {
+                // Soft hyphens and invalid characters are not read
+                if c != '\u{ad}' && c != '\u{fffd}' {
+                    decoded.push(c);
+                }
+                rest = &rest[entity.map_or(0, str::len) + 2..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+fn entity_char(entity: &str) -> Option<char> {
+    let c = match entity {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" | "ensp" | "emsp" | "thinsp" => ' ',
+        "shy" => '\u{ad}',
+        "ndash" => '–',
+        "mdash" => '—',
+        "hellip" => '…',
+        "lsquo" => '‘',
+        "rsquo" => '’',
+        "ldquo" => '“',
+        "rdquo" => '”',
+        "laquo" => '«',
+        "raquo" => '»',
+        "bull" => '•',
+        "middot" => '·',
+        "copy" => '©',
+        "reg" => '®',
+        "trade" => '™',
+        "deg" => '°',
+        "times" => '×',
+        "divide" => '÷',
+        "plusmn" => '±',
+        "euro" => '€',
+        "pound" => '£',
+        "yen" => '¥',
+        "cent" => '¢',
+        "sect" => '§',
+        "para" => '¶',
+        "rarr" => '→',
+        "larr" => '←',
+        "iexcl" => '¡',
+        "iquest" => '¿',
+        "aacute" => 'á',
+        "agrave" => 'à',
+        "acirc" => 'â',
+        "auml" => 'ä',
+        "ccedil" => 'ç',
+        "eacute" => 'é',
+        "egrave" => 'è',
+        "ecirc" => 'ê',
+        "iacute" => 'í',
+        "ntilde" => 'ñ',
+        "oacute" => 'ó',
+        "ouml" => 'ö',
+        "uacute" => 'ú',
+        "uuml" => 'ü',
+        "szlig" => 'ß',
+        _ => {
+            let code = match entity.strip_prefix("#x").or(entity.strip_prefix("#X")) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                None => entity.strip_prefix('#').and_then(|n| n.parse().ok()),
+            };
+            // NUL, controls, surrogates, and out-of-range codes become U+FFFD,
+            // as browsers do; a NUL would otherwise fail synthesis of the page
+            return code.map(|code| {
+                char::from_u32(code)
+                    .filter(|c| !c.is_control() || matches!(c, '\t' | '\n' | '\r' | '\x0c'))
+                    .unwrap_or('\u{fffd}')
+            });
+        }
+    };
+    Some(c)
+}
+
+/// An open element and the events that end it
+struct Open {
+    name: String,
+    ends: Vec<Event<'static>>,
+    skipped: bool,
+    main: bool,
+}
+
+/// Turns tokens into Markdown events
+#[derive(Default)]
+struct Reader {
+    events: Vec<Event<'static>>,
+    stack: Vec<Open>,
+    /// Only `<main>` and `<article>` content is read
+    main_only: bool,
+    /// Depth of open main content elements
+    main: usize,
+    /// Depth of elements whose content is not read
+    skip: usize,
+    /// Depth of open `<pre>` elements
+    pre: usize,
+    /// Rows read so far in each open table
+    tables: Vec<usize>,
+}
+
+impl Reader {
+    fn read(html: &str) -> Vec<Event<'static>> {
+        let tokens = tokenize(html);
+        let mut reader = Reader {
+            main_only: tokens.iter().any(
+                |token| matches!(token, Token::Open { name, attrs, .. } if is_main(name, attrs)),
+            ),
+            ..Reader::default()
+        };
+        for (i, token) in tokens.iter().enumerate() {
+            match token {
+                Token::Text(text) => reader.text(text),
+                Token::Open {
+                    name,
+                    attrs,
+                    self_closing,
+                } => reader.open(name, attrs, *self_closing, &tokens[i + 1..]),
+                Token::Close(name) => reader.close(name),
+            }
+        }
+        while !reader.stack.is_empty() {
+            reader.pop();
+        }
+        reader.events
+    }
+
+    /// Whether text here is read
+    fn reading(&self) -> bool {
+        self.skip == 0 && (!self.main_only || self.main > 0)
+    }
+
+    fn text(&mut self, text: &str) {
+        if !self.reading() {
+            return;
+        }
+        let text = decode_entities(text);
+        let text = if self.pre > 0 {
+            text
+        } else {
+            // Whitespace in markup is a single space
+            let mut collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+            if text.starts_with(char::is_whitespace) {
+                collapsed.insert(0, ' ');
+            }
+            if text.ends_with(char::is_whitespace) && collapsed != " " {
+                collapsed.push(' ');
+            }
+            collapsed
+        };
+        if !text.is_empty() {
+            self.events.push(Event::Text(CowStr::from(text)));
+        }
+    }
+
+    fn open(&mut self, name: &str, attrs: &[(String, String)], self_closing: bool, next: &[Token]) {
+        self.close_implied(name);
+        let attr = |key| attr(attrs, key);
+
+        let main = is_main(name, attrs);
+        // Headers and footers of the page, not of an article or section
+        let page_part = matches!(name, "header" | "footer")
+            && !self
+                .stack
+                .iter()
+                .any(|open| matches!(open.name.as_str(), "main" | "article" | "section"));
+        let hidden = attr("hidden").is_some() || attr("aria-hidden") == Some("true");
+        let skipped = SKIPPED.contains(&name) || page_part || hidden;
+        let reading = self.skip == 0 && !skipped && (main || !self.main_only || self.main > 0);
+
+        let mut ends = vec![];
+        if reading {
+            ends = self.start(name, attrs, next);
+        }
+        if VOID.contains(&name) || self_closing {
+            self.events.extend(ends);
+            return;
+        }
+        if skipped {
+            self.skip += 1;
+        }
+        if main {
+            self.main += 1;
+        }
+        self.stack.push(Open {
+            name: name.to_string(),
+            ends,
+            skipped,
+            main,
+        });
+    }
+
+    /// Events for an opening tag; returns the events that end it
+    fn start(
+        &mut self,
+        name: &str,
+        attrs: &[(String, String)],
+        next: &[Token],
+    ) -> Vec<Event<'static>> {
+        let attr = |key| attr(attrs, key);
+        let (start, end) = match name {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let level = HeadingLevel::try_from(name[1..].parse::<usize>().unwrap_or(1))
+                    .unwrap_or(HeadingLevel::H1);
+                let heading = Tag::Heading {
+                    level,
+                    id: None,
+                    classes: vec![],
+                    attrs: vec![],
+                };
+                (heading, TagEnd::Heading(level))
+            }
+            "p" => (Tag::Paragraph, TagEnd::Paragraph),
+            "blockquote" => (Tag::BlockQuote(None), TagEnd::BlockQuote(None)),
+            "ul" | "menu" => (Tag::List(None), TagEnd::List(false)),
+            "ol" => {
+                let first = attr("start")
+                    .and_then(|n| n.trim().parse().ok())
+                    .unwrap_or(1);
+                (Tag::List(Some(first)), TagEnd::List(true))
+            }
+            "li" => (Tag::Item, TagEnd::Item),
+            "table" => {
+                self.tables.push(0);
+                (Tag::Table(vec![]), TagEnd::Table)
+            }
+            "tr" => {
+                let first_row = self.tables.last().is_some_and(|rows| *rows == 0);
+                if let Some(rows) = self.tables.last_mut() {
+                    *rows += 1;
+                }
+                let in_head = self.stack.iter().any(|open| open.name == "thead");
+                let header_cells = next.iter().find_map(|token| match token {
+                    Token::Open { name, .. } if name == "td" || name == "th" => Some(name == "th"),
+                    _ => None,
+                });
+                if in_head || (first_row && header_cells == Some(true)) {
+                    (Tag::TableHead, TagEnd::TableHead)
+                } else {
+                    (Tag::TableRow, TagEnd::TableRow)
+                }
+            }
+            "td" | "th" => (Tag::TableCell, TagEnd::TableCell),
+            "pre" => {
+                self.pre += 1;
+                // The language is a class on <pre> or its <code>
+                let code = next
+                    .iter()
+                    .find(|token| !matches!(token, Token::Text(t) if t.trim().is_empty()));
+                let class = match code {
+                    Some(Token::Open { name, attrs, .. }) if name == "code" => {
+                        attr("class").or(self::attr(attrs, "class"))
+                    }
+                    _ => attr("class"),
+                };
+                let lang = class
+                    .unwrap_or("")
+                    .split_whitespace()
+                    .find_map(|c| c.strip_prefix("language-").or(c.strip_prefix("lang-")))
+                    .unwrap_or("")
+                    .to_string();
+                (
+                    Tag::CodeBlock(CodeBlockKind::Fenced(CowStr::from(lang))),
+                    TagEnd::CodeBlock,
+                )
+            }
+            "a" => match attr("href") {
+                Some(href) => {
+                    let link = Tag::Link {
+                        link_type: LinkType::Inline,
+                        dest_url: CowStr::from(href.to_string()),
+                        title: CowStr::from(""),
+                        id: CowStr::from(""),
+                    };
+                    (link, TagEnd::Link)
+                }
+                None => return vec![],
+            },
+            "br" => {
+                let event = if self.pre > 0 {
+                    Event::Text(CowStr::from("\n"))
+                } else {
+                    Event::HardBreak
+                };
+                self.events.push(event);
+                return vec![];
+            }
+            "img" => {
+                if let Some(alt) = attr("alt").filter(|alt| !alt.trim().is_empty()) {
+                    self.events
+                        .push(Event::Text(CowStr::from(format!(" {} ", alt))));
+                }
+                return vec![];
+            }
+            "hr" => {
+                self.events.push(Event::Rule);
+                return vec![];
+            }
+            // A thematic break ends the current block, which is all other
+            // block elements need
+            _ if BLOCKS.contains(&name) => {
+                self.events.push(Event::Rule);
+                return vec![Event::Rule];
+            }
+            _ => return vec![],
+        };
+        self.events.push(Event::Start(start));
+        vec![Event::End(end)]
+    }
+
+    fn close(&mut self, name: &str) {
+        if !self.stack.iter().any(|open| open.name == name) {
+            // A stray end tag
+            return;
+        }
+        while let Some(open) = self.pop() {
+            if open == name {
+                break;
+            }
+        }
+    }
+
+    /// Close elements an opening tag ends, like "<li>" ends the previous item
+    fn close_implied(&mut self, name: &str) {
+        let target = match name {
+            "li" => self.innermost(&["li"], &["ul", "ol", "menu"]),
+            "dt" | "dd" => self.innermost(&["dt", "dd"], &["dl"]),
+            "tr" => self.innermost(&["tr"], &["table", "thead", "tbody", "tfoot"]),
+            "td" | "th" => self.innermost(&["td", "th"], &["tr", "table"]),
+            "thead" | "tbody" | "tfoot" => self.innermost(&["thead", "tbody", "tfoot"], &["table"]),
+            // Blocks end an open paragraph
+            "p" | "ul" | "ol" | "table" | "pre" | "blockquote" | "h1" | "h2" | "h3" | "h4"
+            | "h5" | "h6" | "div" | "section" | "hr" => self.innermost(&["p"], &[]),
+            _ => None,
+        };
+        if let Some(index) = target {
+            while self.stack.len() > index {
+                self.pop();
+            }
+        }
+    }
+
+    /// Index of the innermost open element named in `names`, looking no
+    /// further out than the first element in `within`; `within` empty
+    /// looks only at the innermost element
+    fn innermost(&self, names: &[&str], within: &[&str]) -> Option<usize> {
+        let open = self.stack.iter().enumerate().rev();
+        let mut open = open.take_while(|(_, open)| !within.contains(&open.name.as_str()));
+        if within.is_empty() {
+            let (index, open) = open.next()?;
+            return names.contains(&open.name.as_str()).then_some(index);
+        }
+        open.find(|(_, open)| names.contains(&open.name.as_str()))
+            .map(|(index, _)| index)
+    }
+
+    /// Close the innermost element; returns its name
+    fn pop(&mut self) -> Option<String> {
+        let open = self.stack.pop()?;
+        // Only elements that were read started a code block or table
+        if !open.ends.is_empty() {
+            match open.name.as_str() {
+                "pre" => self.pre -= 1,
+                "table" => {
+                    self.tables.pop();
+                }
+                _ => {}
+            }
+        }
+        self.events.extend(open.ends);
+        if open.skipped {
+            self.skip -= 1;
+        }
+        if open.main {
+            self.main -= 1;
+        }
+        Some(open.name)
+    }
+}
+
+/// Value of an attribute
+pub(super) fn attr<'a>(attrs: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    attrs
+        .iter()
+        .find(|(k, _)| k == key)
+        .map(|(_, value)| value.as_str())
+}
+
+/// `<main>`, `<article>`, or an element with `role="main"`
+fn is_main(name: &str, attrs: &[(String, String)]) -> bool {
+    matches!(name, "main" | "article") || attrs.iter().any(|(k, v)| k == "role" && v == "main")
+}
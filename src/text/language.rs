@@ -0,0 +1,274 @@
+//! Language detection and per-language voice routing
+//!
+//! Text is split into runs by script: Chinese, Korean, Cyrillic, and Latin.
+//! Latin sentences are told apart by accented letters and common words.
+//! Each run is then spoken by a voice for its language.
+
+use super::normalize::Lang;
+use super::Segment;
+use crate::tts::{Voice, VoiceCatalog, VOICE_CATALOG};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Voices for the languages found in the text
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Languages {
+    /// Split text by language and speak each run in its own voice
+    pub detect: bool,
+    /// Voice per language code ("ko" = "kss")
+    pub voices: HashMap<String, String>,
+}
+
+/// Latin runs this short inside other text stay with it ("用 GPU 训练")
+const MAX_EMBEDDED_WORDS: usize = 3;
+
+/// Accented letters and common words of the Latin-script languages
+const LATIN: [(Lang, &str, &[&str]); 5] = [
+    (
+        Lang::En,
+        "",
+        &[
+            "the", "and", "is", "are", "of", "to", "in", "it", "that", "with", "for", "this",
+            "you", "was",
+        ],
+    ),
+    (
+        Lang::De,
+        "äöüß",
+        &[
+            "der", "die", "das", "und", "ist", "nicht", "ein", "eine", "mit", "für", "auf", "ich",
+            "sie", "wir",
+        ],
+    ),
+    (
+        Lang::Fr,
+        "àâçèêëîïôûùœ",
+        &[
+            "le", "la", "les", "et", "est", "une", "des", "du", "pas", "pour", "que", "qui",
+            "avec", "je", "nous", "vous",
+        ],
+    ),
+    (
+        Lang::Es,
+        "ñ¿¡áíóú",
+        &[
+            "el", "los", "las", "y", "es", "una", "del", "que", "por", "con", "para", "pero", "yo",
+            "muy",
+        ],
+    ),
+    (
+        Lang::Vi,
+        "ăđơưạảấầẩẫậắằẳẵặẹẻẽếềểễệỉịọỏốồổỗộớờởỡợụủứừửữựỳỵỷỹ",
+        &[
+            "và", "là", "của", "có", "không", "những", "được", "này", "tôi", "các",
+        ],
+    ),
+];
+
+/// Writing system of a character; None for digits, spaces, and punctuation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Script {
+    Han,
+    Hangul,
+    Cyrillic,
+    Latin,
+}
+
+fn script(c: char) -> Option<Script> {
+    match c {
+        '\u{4e00}'..='\u{9fff}' | '\u{3400}'..='\u{4dbf}' | '\u{3040}'..='\u{30ff}' => {
+            Some(Script::Han)
+        }
+        '\u{ac00}'..='\u{d7af}' | '\u{1100}'..='\u{11ff}' | '\u{3130}'..='\u{318f}' => {
+            Some(Script::Hangul)
+        }
+        '\u{0400}'..='\u{04ff}' => Some(Script::Cyrillic),
+        _ if c.is_alphabetic() => Some(Script::Latin),
+        _ => None,
+    }
+}
+
+/// Split text into runs of one language each
+///
+/// Digits, spaces, and punctuation stay with the run they follow. Latin
+/// text with nothing to tell its language by is taken as `fallback`. The
+/// runs join back into the original text.
+pub fn detect(text: &str, fallback: Lang) -> Vec<(Lang, String)> {
+    // Runs of one script
+    let mut runs: Vec<(Option<Script>, String)> = vec![];
+    for c in text.chars() {
+        let found = script(c);
+        match runs.last_mut() {
+            Some((current, run)) if found.is_none() || *current == found || current.is_none() => {
+                if current.is_none() {
+                    *current = found;
+                }
+                run.push(c);
+            }
+            _ => runs.push((found, c.to_string())),
+        }
+    }
+
+    // Latin runs split into sentences, each with its own language
+    let mut detected: Vec<(Lang, String, bool)> = vec![];
+    for (found, run) in runs {
+        match found {
+            Some(Script::Han) => detected.push((Lang::Zh, run, false)),
+            Some(Script::Hangul) => detected.push((Lang::Ko, run, false)),
+            Some(Script::Cyrillic) => detected.push((Lang::Ru, run, false)),
+            Some(Script::Latin) | None => {
+                let mut previous = None;
+                for sentence in sentences(&run) {
+                    let lang = latin_lang(sentence).or(previous).unwrap_or(fallback);
+                    previous = Some(lang);
+                    detected.push((lang, sentence.to_string(), true));
+                }
+            }
+        }
+    }
+
+    // A few Latin words inside other text are read by its voice
+    for i in 0..detected.len() {
+        let (_, run, latin) = &detected[i];
+        let embedded = *latin
+            && run.split_whitespace().count() <= MAX_EMBEDDED_WORDS
+            && !run.contains(['.', '!', '?']);
+        if !embedded {
+            continue;
+        }
+        let neighbour = [i.checked_sub(1), Some(i + 1)]
+            .into_iter()
+            .flatten()
+            .filter_map(|j| detected.get(j))
+            .find(|(_, _, latin)| !latin)
+            .map(|(lang, _, _)| *lang);
+        if let Some(lang) = neighbour {
+            detected[i].0 = lang;
+            detected[i].2 = false;
+        }
+    }
+
+    let mut merged: Vec<(Lang, String)> = vec![];
+    for (lang, run, _) in detected {
+        match merged.last_mut() {
+            Some((last, text)) if *last == lang => text.push_str(&run),
+            _ => merged.push((lang, run)),
+        }
+    }
+    merged
+}
+
+/// Sentences with their trailing punctuation and spaces
+fn sentences(text: &str) -> Vec<&str> {
+    let mut out = vec![];
+    let mut start = 0;
+    // Sentence punctuation seen, then a space after it
+    let (mut ended, mut gap) = (false, false);
+    for (i, c) in text.char_indices() {
+        if c.is_whitespace() {
+            gap = ended;
+            continue;
+        }
+        if gap {
+            out.push(&text[start..i]);
+            start = i;
+        }
+        ended = matches!(c, '.' | '!' | '?')
+            || (ended && matches!(c, '"' | '\'' | ')' | '”' | '’' | '»'));
+        gap = false;
+    }
+    if start < text.len() {
+        out.push(&text[start..]);
+    }
+    out
+}
+
+/// Language of a Latin-script sentence, if anything gives it away
+fn latin_lang(sentence: &str) -> Option<Lang> {
+    let lower = sentence.to_lowercase();
+    let words: Vec<&str> = lower
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|w| !w.is_empty())
+        .collect();
+    let scores = LATIN.iter().map(|(lang, letters, common)| {
+        let accents = lower.chars().filter(|&c| letters.contains(c)).count();
+        let hits = words.iter().filter(|w| common.contains(w)).count();
+        (*lang, accents * 2 + hits)
+    });
+    // The first language wins a tie, so English is preferred
+    let (lang, score) = scores.fold(
+        (Lang::En, 0),
+        |best, next| {
+            if next.1 > best.1 {
+                next
+            } else {
+                best
+            }
+        },
+    );
+    (score > 0).then_some(lang)
+}
+
+/// Whether a voice speaks a language ("zh_en" speaks both)
+fn speaks(voice: &Voice, lang: Lang) -> bool {
+    voice.lang.split('_').any(|part| part == lang.code())
+}
+
+impl Languages {
+    /// Voice for a language: the configured one, else `default` if it
+    /// speaks the language, else an installed voice, else the first in the
+    /// catalog
+    pub fn voice_for(&self, lang: Lang, default: &str) -> String {
+        if let Some(voice) = self.voices.get(lang.code()) {
+            return voice.clone();
+        }
+        if VoiceCatalog::find(default).is_some_and(|voice| speaks(voice, lang)) {
+            return default.to_string();
+        }
+        let mut speakers = VOICE_CATALOG.iter().filter(|voice| speaks(voice, lang));
+        speakers
+            .clone()
+            .find(|voice| VoiceCatalog::is_installed(voice.id))
+            .or_else(|| speakers.next())
+            .map_or_else(|| default.to_string(), |voice| voice.id.to_string())
+    }
+
+    /// Split speech in the default voice into language runs, each spoken
+    /// by the voice for its language
+    ///
+    /// Speech that already names a voice (dialogue, SSML) is left alone.
+    pub fn route(&self, segments: Vec<Segment>, default: &str) -> Vec<Segment> {
+        let fallback =
+            VoiceCatalog::find(default).map_or(Lang::En, |voice| Lang::for_locale(voice.lang, ""));
+        let mut routed = vec![];
+        for segment in segments {
+            let Segment::Speech {
+                text,
+                voice: None,
+                speaker,
+                rate,
+            } = segment
+            else {
+                routed.push(segment);
+                continue;
+            };
+            for (lang, run) in detect(&text, fallback) {
+                let voice = self.voice_for(lang, default);
+                let (voice, speaker) = if voice.eq_ignore_ascii_case(default) {
+                    (None, speaker)
+                } else {
+                    // Speaker numbers belong to the default voice
+                    (Some(voice), Some(0))
+                };
+                routed.push(Segment::Speech {
+                    text: run.trim().to_string(),
+                    voice,
+                    speaker,
+                    rate,
+                });
+            }
+        }
+        routed
+    }
+}
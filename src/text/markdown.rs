@@ -0,0 +1,484 @@
+//! Markdown to speech - walks the CommonMark/GFM document tree
+//!
+//! The result is a list of blocks of speakable text, each tagged with the
+//! structure it came from so callers can pace headings, list items, and
+//! quotes differently. Raw HTML, footnote definitions, and front matter
+//! are not spoken; code blocks are skipped unless [`Rules::code`] asks for
+//! them to be announced or read.
+//!
+//! [`render`] applies [`Rules`] on top: pauses after headings, ordinal list
+//! items, tables read as "column: value", and announced quotes.
+
+use super::code::{self, CodeMode};
+use super::normalize::symbols::domain;
+use super::Segment;
+use pulldown_cmark::{CodeBlockKind, Event, LinkType, Options, Parser, Tag, TagEnd};
+use serde::Deserialize;
+
+/// How links are read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkMode {
+    /// Only the link text
+    #[default]
+    Text,
+    /// Link text followed by the target's domain; bare URLs become the domain
+    Domain,
+}
+
+/// Speech rendering rules, set in the `[markdown]` config section
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Rules {
+    /// Silence after a heading, in seconds
+    pub heading_pause: f32,
+    /// Silence after a paragraph, quote, or table row
+    pub paragraph_pause: f32,
+    /// Silence after a list item
+    pub item_pause: f32,
+    /// Read numbered list items as "First, ...", "Second, ..."
+    pub ordinals: bool,
+    /// Read table rows as "column: value" instead of reading the header row
+    pub table_headers: bool,
+    /// Words around blockquotes; empty strings turn announcements off
+    pub quote_start: String,
+    pub quote_end: String,
+    pub links: LinkMode,
+    pub code: CodeMode,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            heading_pause: 0.8,
+            paragraph_pause: 0.5,
+            item_pause: 0.3,
+            ordinals: true,
+            table_headers: true,
+            quote_start: "Quote".to_string(),
+            quote_end: "End quote".to_string(),
+            links: LinkMode::Text,
+            code: CodeMode::Skip,
+        }
+    }
+}
+
+/// Where a block of text sits in the document
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    /// Heading of level 1-6
+    Heading(u8),
+    Paragraph,
+    /// List item; `depth` 1 is the outermost list, `number` is set for ordered lists
+    ListItem {
+        depth: usize,
+        number: Option<u64>,
+    },
+    /// Header row of a table
+    TableHeader,
+    /// Body row of a table
+    TableRow,
+    /// Fenced or indented code, as read per [`CodeMode`]
+    CodeBlock,
+}
+
+/// Speakable text of one block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    pub hint: Hint,
+    /// Blockquote nesting (0 = not quoted)
+    pub quote: usize,
+    /// Text, with table cells separated by commas
+    pub text: String,
+    /// Cells of table rows (empty for other blocks)
+    pub cells: Vec<String>,
+}
+
+/// Parse Markdown into speakable blocks
+pub fn parse(markdown: &str) -> Vec<Block> {
+    parse_with(markdown, &Rules::default())
+}
+
+/// Parse Markdown into speakable blocks, reading links per `rules`
+pub fn parse_with(markdown: &str, rules: &Rules) -> Vec<Block> {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_YAML_STYLE_METADATA_BLOCKS
+        | Options::ENABLE_GFM;
+
+    walk(Parser::new_ext(markdown, options), rules)
+}
+
+/// Speakable blocks from document events; HTML input is read as the
+/// same events
+pub(super) fn walk<'a>(events: impl IntoIterator<Item = Event<'a>>, rules: &Rules) -> Vec<Block> {
+    let mut walker = Walker {
+        links: rules.links,
+        code_mode: rules.code,
+        ..Walker::default()
+    };
+    for event in events {
+        walker.event(event);
+    }
+    walker.flush();
+    walker.blocks
+}
+
+/// Plain text of the blocks, separated by blank lines
+pub fn to_text(blocks: &[Block]) -> String {
+    blocks
+        .iter()
+        .map(|block| block.text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Render Markdown as speech and pauses
+pub fn render(markdown: &str, rules: &Rules) -> Vec<Segment> {
+    render_blocks(&parse_with(markdown, rules), rules)
+}
+
+/// Speech and pauses for parsed blocks
+pub(super) fn render_blocks(blocks: &[Block], rules: &Rules) -> Vec<Segment> {
+    let mut segments: Vec<Segment> = vec![];
+    let mut quote = 0;
+    let mut header: &[String] = &[];
+
+    for block in blocks {
+        let mut text = match block.hint {
+            Hint::TableHeader if rules.table_headers => {
+                header = &block.cells;
+                continue;
+            }
+            Hint::TableRow if rules.table_headers && !header.is_empty() => block
+                .cells
+                .iter()
+                .enumerate()
+                .filter(|(_, cell)| !cell.is_empty())
+                .map(|(i, cell)| match header.get(i).filter(|c| !c.is_empty()) {
+                    Some(column) => format!("{}: {}", column, cell),
+                    None => cell.clone(),
+                })
+                .collect::<Vec<_>>()
+                .join(", "),
+            Hint::ListItem {
+                number: Some(n), ..
+            } if rules.ordinals => format!("{}, {}", ordinal_word(n), block.text),
+            _ => block.text.clone(),
+        };
+
+        // Announce quotes as they open and close
+        for _ in block.quote..quote {
+            end_quote(&mut segments, rules);
+        }
+        for _ in quote..block.quote {
+            if !rules.quote_start.is_empty() {
+                text = format!("{}. {}", rules.quote_start, text);
+            }
+        }
+        quote = block.quote;
+
+        push_speech(&mut segments, text);
+        let pause = match block.hint {
+            Hint::Heading(_) => rules.heading_pause,
+            Hint::ListItem { .. } => rules.item_pause,
+            _ => rules.paragraph_pause,
+        };
+        if pause > 0.0 {
+            segments.push(Segment::Silence(pause));
+        }
+    }
+    for _ in 0..quote {
+        end_quote(&mut segments, rules);
+    }
+
+    if let Some(Segment::Silence(_)) = segments.last() {
+        segments.pop();
+    }
+    segments
+}
+
+/// Add speech, joining it to the previous speech when no pause separates them
+fn push_speech(segments: &mut Vec<Segment>, text: String) {
+    match segments.last_mut() {
+        Some(Segment::Speech { text: last, .. }) => {
+            last.push_str("\n\n");
+            last.push_str(&text);
+        }
+        _ => segments.push(Segment::speech(text)),
+    }
+}
+
+/// Close a quote after the last speech
+fn end_quote(segments: &mut [Segment], rules: &Rules) {
+    if rules.quote_end.is_empty() {
+        return;
+    }
+    let last = segments.iter_mut().rev().find_map(|segment| match segment {
+        Segment::Speech { text, .. } => Some(text),
+        Segment::Silence(_) => None,
+    });
+    if let Some(text) = last {
+        text.push_str(&format!(" {}.", rules.quote_end));
+    }
+}
+
+/// "First", "Second", ... then "Number 21"
+fn ordinal_word(n: u64) -> String {
+    const WORDS: [&str; 20] = [
+        "First",
+        "Second",
+        "Third",
+        "Fourth",
+        "Fifth",
+        "Sixth",
+        "Seventh",
+        "Eighth",
+        "Ninth",
+        "Tenth",
+        "Eleventh",
+        "Twelfth",
+        "Thirteenth",
+        "Fourteenth",
+        "Fifteenth",
+        "Sixteenth",
+        "Seventeenth",
+        "Eighteenth",
+        "Nineteenth",
+        "Twentieth",
+    ];
+    match n {
+        1..=20 => WORDS[n as usize - 1].to_string(),
+        _ => format!("Number {}", n),
+    }
+}
+
+#[derive(Default)]
+struct Walker {
+    links: LinkMode,
+    code_mode: CodeMode,
+    /// Language and text of the code block being read
+    code: Option<(Option<String>, String)>,
+    /// Target of the open link and where its text starts
+    link: Option<(String, usize)>,
+    blocks: Vec<Block>,
+    text: String,
+    heading: Option<u8>,
+    quote: usize,
+    /// Next number of each open list (None for bullet lists)
+    lists: Vec<Option<u64>>,
+    /// Hint for the first block of the current list item
+    item: Option<Hint>,
+    /// Cells of the table row being read
+    row: Option<Vec<String>>,
+    /// Depth of elements whose content is not spoken
+    skip: usize,
+}
+
+impl Walker {
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) | Event::Code(text) => self.push(&text),
+            Event::SoftBreak => self.push(" "),
+            Event::HardBreak => self.push("\n"),
+            // Tags like <br> still separate words
+            Event::InlineHtml(_) => self.push(" "),
+            Event::Rule => self.flush(),
+            // Raw HTML, footnote markers, math, and task checkboxes
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Heading { level, .. } => {
+                self.flush();
+                self.heading = Some(level as u8);
+            }
+            Tag::Paragraph | Tag::Table(_) => self.flush(),
+            Tag::BlockQuote(_) => {
+                self.flush();
+                self.quote += 1;
+            }
+            Tag::List(first) => {
+                self.flush();
+                self.lists.push(first);
+            }
+            Tag::Item => {
+                self.flush();
+                let depth = self.lists.len();
+                let number = self.lists.last_mut().and_then(|next| {
+                    let number = *next;
+                    if let Some(n) = next {
+                        *n += 1;
+                    }
+                    number
+                });
+                self.item = Some(Hint::ListItem { depth, number });
+            }
+            Tag::Link {
+                link_type,
+                dest_url,
+                ..
+            } if self.links == LinkMode::Domain && link_type != LinkType::Email => {
+                self.link = Some((dest_url.to_string(), self.buffer().len()));
+            }
+            Tag::TableHead | Tag::TableRow => self.row = Some(vec![]),
+            Tag::TableCell => {
+                if let Some(row) = &mut self.row {
+                    row.push(String::new());
+                }
+            }
+            Tag::CodeBlock(kind) if self.code_mode != CodeMode::Skip && self.skip == 0 => {
+                self.flush();
+                let lang = match kind {
+                    CodeBlockKind::Fenced(info) => info
+                        .split(|c: char| c.is_whitespace() || c == ',' || c == '{')
+                        .next()
+                        .filter(|lang| !lang.is_empty())
+                        .map(str::to_string),
+                    CodeBlockKind::Indented => None,
+                };
+                self.code = Some((lang, String::new()));
+            }
+            Tag::CodeBlock(_)
+            | Tag::HtmlBlock
+            | Tag::FootnoteDefinition(_)
+            | Tag::MetadataBlock(_) => {
+                self.flush();
+                self.skip += 1;
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Heading(_) => {
+                self.flush();
+                self.heading = None;
+            }
+            TagEnd::Paragraph => self.flush(),
+            TagEnd::BlockQuote(_) => {
+                self.flush();
+                self.quote -= 1;
+            }
+            TagEnd::List(_) => {
+                self.flush();
+                self.lists.pop();
+            }
+            TagEnd::Item => {
+                self.flush();
+                self.item = None;
+            }
+            TagEnd::Link => {
+                let Some((url, start)) = self.link.take() else {
+                    return;
+                };
+                let Some(domain) = domain(&url) else {
+                    return;
+                };
+                let buffer = self.buffer();
+                if buffer[start..].trim() == url || buffer[start..].trim().is_empty() {
+                    // Autolinks and bare URLs: the domain replaces the address
+                    buffer.truncate(start);
+                    buffer.push_str(domain);
+                } else {
+                    buffer.push_str(&format!(" ({})", domain));
+                }
+            }
+            TagEnd::TableHead | TagEnd::TableRow => {
+                let hint = match tag {
+                    TagEnd::TableHead => Hint::TableHeader,
+                    _ => Hint::TableRow,
+                };
+                let cells: Vec<_> = self
+                    .row
+                    .take()
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|cell| collapse(cell))
+                    .collect();
+                let text = cells
+                    .iter()
+                    .filter(|cell| !cell.is_empty())
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                if self.emit(hint, text) {
+                    if let Some(block) = self.blocks.last_mut() {
+                        block.cells = cells;
+                    }
+                }
+            }
+            TagEnd::CodeBlock if self.code.is_some() => {
+                let (lang, text) = self.code.take().unwrap_or_default();
+                if let Some(spoken) = code::speak(&text, lang.as_deref(), self.code_mode) {
+                    self.emit(Hint::CodeBlock, collapse(&spoken));
+                }
+            }
+            TagEnd::CodeBlock
+            | TagEnd::HtmlBlock
+            | TagEnd::FootnoteDefinition
+            | TagEnd::MetadataBlock(_) => self.skip -= 1,
+            _ => {}
+        }
+    }
+
+    fn push(&mut self, text: &str) {
+        if self.skip > 0 {
+            return;
+        }
+        match &mut self.code {
+            Some((_, code)) => code.push_str(text),
+            None => self.buffer().push_str(text),
+        }
+    }
+
+    /// Text being collected: the open table cell or the current block
+    fn buffer(&mut self) -> &mut String {
+        match self.row.as_mut().and_then(|row| row.last_mut()) {
+            Some(cell) => cell,
+            None => &mut self.text,
+        }
+    }
+
+    /// End the current block of text
+    fn flush(&mut self) {
+        let text = collapse(&std::mem::take(&mut self.text));
+        let hint = match self.heading {
+            Some(level) => Hint::Heading(level),
+            None => self.item.unwrap_or(Hint::Paragraph),
+        };
+        if self.emit(hint, text) && self.heading.is_none() {
+            // Later paragraphs of the same item are plain paragraphs
+            self.item = self.item.map(|_| Hint::Paragraph);
+        }
+    }
+
+    fn emit(&mut self, hint: Hint, text: String) -> bool {
+        if text.is_empty() {
+            return false;
+        }
+        self.blocks.push(Block {
+            hint,
+            quote: self.quote,
+            text,
+            cells: vec![],
+        });
+        true
+    }
+}
+
+/// Collapse runs of spaces, keeping hard line breaks
+fn collapse(text: &str) -> String {
+    text.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,109 @@
+//! Text processing shared by input modes and servers
+
+pub mod code;
+pub mod dialogue;
+pub mod epub;
+pub mod html;
+pub mod language;
+pub mod markdown;
+pub mod normalize;
+pub mod ssml;
+
+/// One step of a synthesis script: speech or a pause
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment {
+    /// Text to speak, optionally in another voice or at another rate
+    Speech {
+        text: String,
+        /// Voice ID replacing the engine's voice
+        voice: Option<String>,
+        /// Speaker replacing the engine's speaker
+        speaker: Option<u32>,
+        /// Speaking rate multiplier (1.0 = unchanged)
+        rate: f32,
+    },
+    /// Silence in seconds
+    Silence(f32),
+}
+
+impl Segment {
+    /// Plain speech in the engine's own voice and rate
+    pub fn speech(text: impl Into<String>) -> Self {
+        Segment::Speech {
+            text: text.into(),
+            voice: None,
+            speaker: None,
+            rate: 1.0,
+        }
+    }
+}
+
+/// Spoken text of a script without its pauses, one block per paragraph
+pub fn speech_text(segments: &[Segment]) -> String {
+    segments
+        .iter()
+        .filter_map(|segment| match segment {
+            Segment::Speech { text, .. } => Some(text.as_str()),
+            Segment::Silence(_) => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Parse a duration: "500ms", "1.5s", or bare seconds ("0.4")
+pub fn parse_duration(spec: &str) -> Option<f32> {
+    let spec = spec.trim();
+    let (number, scale) = if let Some(ms) = spec.strip_suffix("ms") {
+        (ms, 0.001)
+    } else if let Some(s) = spec.strip_suffix('s') {
+        (s, 1.0)
+    } else {
+        (spec, 1.0)
+    };
+    number
+        .trim()
+        .parse::<f32>()
+        .ok()
+        .filter(|n| n.is_finite() && *n >= 0.0)
+        .map(|n| n * scale)
+}
+
+/// Index of the `>` ending an HTML/SSML tag, skipping quoted attribute values
+pub(crate) fn tag_end(tag: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in tag.char_indices() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '>') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Split text into sentences so audio can start before the whole text is synthesized
+pub fn split_sentences(text: &str) -> Vec<String> {
+    let mut sentences = vec![];
+    let mut current = String::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        current.push(c);
+        let boundary = match c {
+            '。' | '！' | '？' | '\n' => true,
+            '.' | '!' | '?' => chars.peek().is_none_or(|next| next.is_whitespace()),
+            _ => false,
+        };
+        if boundary && !current.trim().is_empty() {
+            sentences.push(current.trim().to_string());
+            current.clear();
+        }
+    }
+    if !current.trim().is_empty() {
+        sentences.push(current.trim().to_string());
+    }
+
+    sentences
+}
@@ -0,0 +1,345 @@
+//! German
+
+use super::{hour24, Builtin, Measure, Period, Speller, Unit, Words};
+
+pub struct German;
+
+const ONES: [&str; 20] = [
+    "null",
+    "eins",
+    "zwei",
+    "drei",
+    "vier",
+    "fünf",
+    "sechs",
+    "sieben",
+    "acht",
+    "neun",
+    "zehn",
+    "elf",
+    "zwölf",
+    "dreizehn",
+    "vierzehn",
+    "fünfzehn",
+    "sechzehn",
+    "siebzehn",
+    "achtzehn",
+    "neunzehn",
+];
+
+const TENS: [&str; 10] = [
+    "", "", "zwanzig", "dreißig", "vierzig", "fünfzig", "sechzig", "siebzig", "achtzig", "neunzig",
+];
+
+/// Scales above a thousand: value, singular, plural
+const SCALES: [(u64, &str, &str); 3] = [
+    (1_000_000_000_000, "Billion", "Billionen"),
+    (1_000_000_000, "Milliarde", "Milliarden"),
+    (1_000_000, "Million", "Millionen"),
+];
+
+const MONTHS: [&str; 12] = [
+    "Januar",
+    "Februar",
+    "März",
+    "April",
+    "Mai",
+    "Juni",
+    "Juli",
+    "August",
+    "September",
+    "Oktober",
+    "November",
+    "Dezember",
+];
+
+const WORDS: Words = Words {
+    point: "Komma",
+    minus: "minus",
+    and: "und",
+    version: "Version",
+    space: " ",
+    decimal: ',',
+    dot: "Punkt",
+    slash: "Schrägstrich",
+    at: "at",
+};
+
+pub const DICTIONARY: Builtin = Builtin {
+    abbreviations: &[
+        ("z.B.", "zum Beispiel"),
+        ("z. B.", "zum Beispiel"),
+        ("d.h.", "das heißt"),
+        ("d. h.", "das heißt"),
+        ("usw.", "und so weiter"),
+        ("bzw.", "beziehungsweise"),
+        ("ca.", "circa"),
+        ("evtl.", "eventuell"),
+        ("ggf.", "gegebenenfalls"),
+        ("inkl.", "inklusive"),
+        ("Nr.", "Nummer"),
+        ("Dr.", "Doktor"),
+        ("Hr.", "Herr"),
+        ("Fr.", "Frau"),
+        ("Prof.", "Professor"),
+    ],
+    units: &[
+        ("km", &["Kilometer"]),
+        ("m", &["Meter"]),
+        ("cm", &["Zentimeter"]),
+        ("mm", &["Millimeter"]),
+        ("km/h", &["Kilometer pro Stunde"]),
+        ("kg", &["Kilogramm"]),
+        ("g", &["Gramm"]),
+        ("mg", &["Milligramm"]),
+        ("l", &["Liter"]),
+        ("L", &["Liter"]),
+        ("ml", &["Milliliter"]),
+        ("h", &["Stunde", "Stunden"]),
+        ("Std.", &["Stunde", "Stunden"]),
+        ("min", &["Minute", "Minuten"]),
+        ("Min.", &["Minute", "Minuten"]),
+        ("s", &["Sekunde", "Sekunden"]),
+        ("Sek.", &["Sekunde", "Sekunden"]),
+        ("ms", &["Millisekunde", "Millisekunden"]),
+        ("KB", &["Kilobyte"]),
+        ("kB", &["Kilobyte"]),
+        ("MB", &["Megabyte"]),
+        ("GB", &["Gigabyte"]),
+        ("TB", &["Terabyte"]),
+        ("Hz", &["Hertz"]),
+        ("MHz", &["Megahertz"]),
+        ("GHz", &["Gigahertz"]),
+        ("W", &["Watt"]),
+        ("kW", &["Kilowatt"]),
+        ("V", &["Volt"]),
+        ("°C", &["Grad Celsius"]),
+    ],
+    feminine: &["h", "Std.", "min", "Min.", "s", "Sek.", "ms"],
+    words: &[],
+    emoji: &[
+        ("😀", "grinsendes Gesicht"),
+        ("😂", "Gesicht mit Freudentränen"),
+        ("🙂", "leicht lächelndes Gesicht"),
+        ("😊", "lächelndes Gesicht mit lachenden Augen"),
+        ("😉", "zwinkerndes Gesicht"),
+        ("😍", "lächelndes Gesicht mit Herzaugen"),
+        ("🤔", "nachdenkendes Gesicht"),
+        ("😅", "grinsendes Gesicht mit Schweißtropfen"),
+        ("😢", "weinendes Gesicht"),
+        ("😭", "heulendes Gesicht"),
+        ("😡", "wütendes Gesicht"),
+        ("😎", "lächelndes Gesicht mit Sonnenbrille"),
+        ("👍", "Daumen hoch"),
+        ("👎", "Daumen runter"),
+        ("👏", "klatschende Hände"),
+        ("🙏", "zusammengelegte Handflächen"),
+        ("👋", "winkende Hand"),
+        ("💪", "angespannter Bizeps"),
+        ("👀", "Augen"),
+        ("❤", "rotes Herz"),
+        ("🔥", "Feuer"),
+        ("✨", "funkelnde Sterne"),
+        ("🎉", "Partyknaller"),
+        ("🚀", "Rakete"),
+        ("✅", "Häkchen"),
+        ("❌", "Kreuz"),
+        ("⚠", "Warnung"),
+        ("💡", "Glühbirne"),
+        ("🐛", "Käfer"),
+        ("⭐", "Stern"),
+    ],
+    symbols: &[
+        ("→", "zu"),
+        ("->", "zu"),
+        ("⇒", "zu"),
+        ("=>", "zu"),
+        ("←", "von"),
+        ("<-", "von"),
+        ("&&", "und"),
+        ("&", "und"),
+        ("||", "oder"),
+        ("≠", "ungleich"),
+        ("!=", "ungleich"),
+        ("≤", "kleiner gleich"),
+        ("<=", "kleiner gleich"),
+        ("≥", "größer gleich"),
+        (">=", "größer gleich"),
+        ("±", "plus minus"),
+        ("×", "mal"),
+    ],
+};
+
+/// "eins" becomes "ein" in compounds ("einhundert", "einundzwanzig")
+fn compound(word: String) -> String {
+    match word.strip_suffix("eins") {
+        Some(stem) => format!("{}ein", stem),
+        None => word,
+    }
+}
+
+fn below_hundred(n: u64) -> String {
+    match n {
+        0..=19 => ONES[n as usize].to_string(),
+        _ if n.is_multiple_of(10) => TENS[n as usize / 10].to_string(),
+        _ => format!(
+            "{}und{}",
+            compound(ONES[n as usize % 10].to_string()),
+            TENS[n as usize / 10]
+        ),
+    }
+}
+
+fn below_thousand(n: u64) -> String {
+    let (hundreds, rest) = (n / 100, n % 100);
+    let mut words = String::new();
+    if hundreds > 0 {
+        words.push_str(&compound(ONES[hundreds as usize].to_string()));
+        words.push_str("hundert");
+    }
+    if rest > 0 || hundreds == 0 {
+        words.push_str(&below_hundred(rest));
+    }
+    words
+}
+
+fn below_million(n: u64) -> String {
+    let (thousands, rest) = (n / 1000, n % 1000);
+    let mut words = String::new();
+    if thousands > 0 {
+        words.push_str(&compound(below_thousand(thousands)));
+        words.push_str("tausend");
+    }
+    if rest > 0 || thousands == 0 {
+        words.push_str(&below_thousand(rest));
+    }
+    words
+}
+
+/// "erste", "dritte", "zwanzigste"
+fn ordinal_stem(n: u64) -> String {
+    let small = match n % 100 {
+        1 => Some("erste"),
+        3 => Some("dritte"),
+        7 => Some("siebte"),
+        8 => Some("achte"),
+        _ => None,
+    };
+    match (n % 100, small) {
+        (_, Some(word)) if n < 100 => word.to_string(),
+        (_, Some(word)) => format!("{}{}", German.cardinal(n - n % 100), word),
+        (1..=19, None) => format!("{}te", German.cardinal(n)),
+        _ => format!("{}ste", German.cardinal(n)),
+    }
+}
+
+impl Speller for German {
+    fn words(&self) -> &Words {
+        &WORDS
+    }
+
+    fn cardinal(&self, n: u64) -> String {
+        let mut parts = vec![];
+        let mut rest = n;
+        for (scale, one, many) in SCALES {
+            let count = rest / scale;
+            match count {
+                0 => {}
+                1 => parts.push(format!("eine {}", one)),
+                _ => parts.push(format!("{} {}", below_million(count), many)),
+            }
+            rest %= scale;
+        }
+        if rest > 0 || parts.is_empty() {
+            parts.push(below_million(rest));
+        }
+        parts.join(" ")
+    }
+
+    fn ordinal(&self, n: u64) -> String {
+        ordinal_stem(n)
+    }
+
+    /// "am 3. Mai" -> "am dritten Mai", the form after "am", "im", "zum"
+    fn ordinal_suffix(&self, n: u64, suffix: &str) -> Option<String> {
+        (suffix == ".").then(|| format!("{}n", ordinal_stem(n)))
+    }
+
+    /// "14. März 2025" -> "vierzehnter März zweitausendfünfundzwanzig"
+    fn date(&self, year: u32, month: u32, day: u32) -> String {
+        let year = match year {
+            1100..=1999 => format!(
+                "{}hundert{}",
+                below_hundred(year as u64 / 100),
+                match year % 100 {
+                    0 => String::new(),
+                    rest => below_hundred(rest as u64),
+                }
+            ),
+            _ => self.cardinal(year as u64),
+        };
+        format!(
+            "{}r {} {}",
+            self.ordinal(day as u64),
+            MONTHS[month as usize - 1],
+            year
+        )
+    }
+
+    /// "15:30" -> "fünfzehn Uhr dreißig"
+    fn time(&self, hour: u32, minute: u32, second: Option<u32>, period: Option<Period>) -> String {
+        let hour = hour24(hour, period);
+        let mut spoken = format!("{} Uhr", compound(self.cardinal(hour as u64)));
+        if minute > 0 {
+            spoken.push(' ');
+            spoken.push_str(&self.cardinal(minute as u64));
+        }
+        if let Some(second) = second {
+            let unit = if second == 1 { "Sekunde" } else { "Sekunden" };
+            spoken.push_str(&format!(
+                " und {} {}",
+                compound(self.cardinal(second as u64)),
+                unit
+            ));
+        }
+        spoken
+    }
+
+    fn unit(&self, unit: Unit, count: Option<u64>) -> &'static str {
+        match unit {
+            Unit::Dollar => "Dollar",
+            Unit::Euro => "Euro",
+            Unit::Pound => "Pfund",
+            Unit::Yen => "Yen",
+            Unit::Won => "Won",
+            Unit::Dong => "Dong",
+            Unit::Ruble => "Rubel",
+            Unit::Cent | Unit::EuroCent => "Cent",
+            Unit::Penny if count == Some(1) => "Penny",
+            Unit::Penny => "Pence",
+            Unit::Kopek if count == Some(1) => "Kopeke",
+            Unit::Kopek => "Kopeken",
+            Unit::Percent => "Prozent",
+        }
+    }
+
+    /// "ein Kilometer", "eine Stunde"
+    fn measure(&self, number: String, unit: &Measure, count: Option<u64>) -> String {
+        let number = match (unit.feminine, count) {
+            (true, Some(1)) => "eine".to_string(),
+            (_, Some(_)) => compound(number),
+            _ => number,
+        };
+        format!("{} {}", number, unit.form(self.plural(count)))
+    }
+
+    /// "ein Dollar", "eine Kopeke"
+    fn with_unit(&self, number: String, unit: Unit, count: Option<u64>) -> String {
+        let number = match (unit, count) {
+            (Unit::Kopek, Some(1)) => "eine".to_string(),
+            (_, Some(_)) => compound(number),
+            _ => number,
+        };
+        format!("{} {}", number, self.unit(unit, count))
+    }
+}
@@ -0,0 +1,320 @@
+//! Abbreviations, units, and acronyms expanded before speech
+//!
+//! Each language has built-in entries. Users add or override entries in
+//! `dictionaries/<lang>.toml` next to `config.toml`:
+//!
+//! ```toml
+//! [abbreviations]
+//! "approx." = "approximately"
+//! "k8s" = "kubernetes"
+//!
+//! [units]
+//! "rpm" = ["revolution per minute", "revolutions per minute"]
+//! "сут" = { forms = ["сутки", "суток", "суток"], feminine = true }
+//!
+//! [acronyms]
+//! spell = ["ONNX"]    # letter by letter: "O N N X"
+//! words = ["SCSI"]    # read as a word
+//! ```
+
+use super::Lang;
+use crate::config::Config;
+use crate::error::{BiboError, Result};
+use regex_lite::Regex;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, LazyLock};
+
+/// Built-in entries of one language
+pub(super) struct Builtin {
+    pub abbreviations: &'static [(&'static str, &'static str)],
+    /// Symbol and its forms (one, then plurals in the order the language counts them)
+    pub units: &'static [(&'static str, &'static [&'static str])],
+    /// Units whose noun is feminine ("eine Stunde", "одна минута")
+    pub feminine: &'static [&'static str],
+    /// Short all-caps words that are not acronyms ("THE", "NOT")
+    pub words: &'static [&'static str],
+    /// Emoji and their CLDR short names
+    pub emoji: &'static [(&'static str, &'static str)],
+    /// Symbols read as words ("->" is "to")
+    pub symbols: &'static [(&'static str, &'static str)],
+}
+
+/// Acronyms read as words in every language
+const ACRONYM_WORDS: &[&str] = &[
+    "ASCII", "CAPTCHA", "COVID", "GIF", "LASER", "NASA", "NATO", "OPEC", "PIN", "RAM", "RADAR",
+    "ROM", "SIM", "UNESCO", "UNICEF",
+];
+
+/// Unit noun, with a form for each plural category
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Measure {
+    pub forms: Vec<String>,
+    pub feminine: bool,
+}
+
+impl Measure {
+    /// Form for a plural category; languages without plurals have one form
+    pub fn form(&self, category: usize) -> &str {
+        let last = self.forms.len().saturating_sub(1);
+        self.forms
+            .get(category.min(last))
+            .map_or("", String::as_str)
+    }
+}
+
+/// Merged dictionary of one language
+pub(super) struct Dictionary {
+    abbreviations: HashMap<String, String>,
+    /// Longest symbol first, so "min" wins over "m"
+    units: Vec<(String, Measure)>,
+    spell: HashSet<String>,
+    words: HashSet<String>,
+    /// Abbreviations, then all-caps tokens
+    pattern: Regex,
+}
+
+/// `dictionaries/<lang>.toml`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct UserDictionary {
+    abbreviations: HashMap<String, String>,
+    units: HashMap<String, UserUnit>,
+    acronyms: UserAcronyms,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum UserUnit {
+    Word(String),
+    Forms(Vec<String>),
+    Full {
+        forms: Vec<String>,
+        #[serde(default)]
+        feminine: bool,
+    },
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct UserAcronyms {
+    spell: Vec<String>,
+    words: Vec<String>,
+}
+
+/// Dictionaries of every language; cheap to clone
+#[derive(Clone)]
+pub struct Dictionaries {
+    by_lang: Arc<HashMap<Lang, Dictionary>>,
+}
+
+/// Built-in entries only, built once and shared
+static BUILTIN: LazyLock<Dictionaries> = LazyLock::new(|| {
+    let by_lang = Lang::ALL
+        .into_iter()
+        .map(|lang| {
+            (
+                lang,
+                Dictionary::new(lang.builtin(), UserDictionary::default()),
+            )
+        })
+        .collect();
+    Dictionaries {
+        by_lang: Arc::new(by_lang),
+    }
+});
+
+impl Dictionaries {
+    /// Built-in entries of every language
+    pub fn builtin() -> Self {
+        BUILTIN.clone()
+    }
+
+    /// Built-in entries extended by the `<lang>.toml` files in `dir`
+    ///
+    /// Missing files are skipped; a file that does not parse is an error.
+    pub fn load(dir: &Path) -> Result<Self> {
+        let by_lang = Lang::ALL
+            .into_iter()
+            .map(|lang| {
+                let user = load_user(&dir.join(format!("{}.toml", lang.code())))?;
+                Ok((lang, Dictionary::new(lang.builtin(), user)))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            by_lang: Arc::new(by_lang),
+        })
+    }
+
+    /// User dictionaries from `Config::dictionary_dir()`
+    pub fn from_config_dir() -> Result<Self> {
+        Self::load(&Config::dictionary_dir())
+    }
+
+    /// Dictionary of a language
+    pub(super) fn get(&self, lang: Lang) -> &Dictionary {
+        &self.by_lang[&lang]
+    }
+}
+
+impl Default for Dictionaries {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl std::fmt::Debug for Dictionaries {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Dictionaries").finish_non_exhaustive()
+    }
+}
+
+fn load_user(path: &Path) -> Result<UserDictionary> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Ok(UserDictionary::default());
+    };
+    toml::from_str(&content)
+        .map_err(|e| BiboError::ConfigError(format!("{}: {}", path.display(), e.message())))
+}
+
+impl Dictionary {
+    fn new(builtin: &Builtin, user: UserDictionary) -> Self {
+        let mut abbreviations: HashMap<String, String> = builtin
+            .abbreviations
+            .iter()
+            .map(|(short, long)| (short.to_string(), long.to_string()))
+            .collect();
+        abbreviations.extend(user.abbreviations);
+
+        let mut units: HashMap<String, Measure> = builtin
+            .units
+            .iter()
+            .map(|(symbol, forms)| {
+                let measure = Measure {
+                    forms: forms.iter().map(|f| f.to_string()).collect(),
+                    feminine: builtin.feminine.contains(symbol),
+                };
+                (symbol.to_string(), measure)
+            })
+            .collect();
+        for (symbol, unit) in user.units {
+            let (forms, feminine) = match unit {
+                UserUnit::Word(word) => (vec![word], false),
+                UserUnit::Forms(forms) => (forms, false),
+                UserUnit::Full { forms, feminine } => (forms, feminine),
+            };
+            units.insert(symbol, Measure { forms, feminine });
+        }
+        let mut units: Vec<_> = units.into_iter().collect();
+        units.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then(a.0.cmp(&b.0)));
+
+        let mut words: HashSet<String> = ACRONYM_WORDS
+            .iter()
+            .chain(builtin.words)
+            .map(|w| w.to_string())
+            .collect();
+        let mut spell = HashSet::new();
+        for word in user.acronyms.words {
+            words.insert(word);
+        }
+        for acronym in user.acronyms.spell {
+            words.remove(&acronym);
+            spell.insert(acronym);
+        }
+
+        let mut keys: Vec<&String> = abbreviations.keys().collect();
+        keys.sort_by(|a, b| b.len().cmp(&a.len()).then(a.cmp(b)));
+        let mut alternatives: Vec<String> = keys.iter().map(|k| regex_lite::escape(k)).collect();
+        alternatives.push("[A-Z]{2,}s?".to_string());
+        let pattern = Regex::new(&alternatives.join("|")).expect("dictionary pattern");
+
+        Self {
+            abbreviations,
+            units,
+            spell,
+            words,
+            pattern,
+        }
+    }
+
+    /// Unit written at the start of `text` ("km/h" in " km/h, ..."), and
+    /// how many bytes it takes up with the space before it
+    pub fn unit_at(&self, text: &str) -> Option<(&Measure, usize)> {
+        let space = text
+            .chars()
+            .next()
+            .filter(|&c| c == ' ' || c == '\u{a0}' || c == '\u{202f}')
+            .map_or(0, char::len_utf8);
+        let rest = &text[space..];
+        self.units.iter().find_map(|(symbol, measure)| {
+            let after = rest.strip_prefix(symbol.as_str())?;
+            (!after.starts_with(char::is_alphanumeric)).then_some((measure, space + symbol.len()))
+        })
+    }
+
+    /// Replace abbreviations and read acronyms letter by letter
+    pub fn expand(&self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut pos = 0;
+
+        while let Some(found) = self.pattern.find_at(text, pos) {
+            let before = text[..found.start()].chars().next_back();
+            let after = text[found.end()..].chars().next();
+            let word = found.as_str();
+            out.push_str(&text[pos..found.start()]);
+
+            // Only whole words: "GPU" but not the "PU" of "GPUx"
+            let glued = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+            if glued(before) || (glued(after) && !word.ends_with('.')) {
+                let skip = word.chars().next().map_or(1, char::len_utf8);
+                out.push_str(&word[..skip]);
+                pos = found.start() + skip;
+                continue;
+            }
+
+            if let Some(long) = self.abbreviations.get(word) {
+                out.push_str(long);
+                // "etc." closing a sentence still ends it
+                if word.ends_with('.') && after.is_none_or(|c| c == '\n') {
+                    out.push('.');
+                }
+            } else {
+                out.push_str(&self.acronym(word));
+            }
+            pos = found.end();
+        }
+        out.push_str(&text[pos..]);
+        out
+    }
+
+    /// "GPU" -> "G P U", "APIs" -> "A P I's"; acronyms said as words are kept
+    fn acronym(&self, word: &str) -> String {
+        let (letters, plural) = match word.strip_suffix('s') {
+            Some(stem) => (stem, true),
+            None => (word, false),
+        };
+        let spelled = if self.spell.contains(letters) {
+            true
+        } else if self.words.contains(letters) {
+            false
+        } else {
+            // Short ones and those without vowels ("HTTPS") are spelled;
+            // "JSON" and "NASA" are pronounceable
+            letters.len() <= 3 || !letters.contains(['A', 'E', 'I', 'O', 'U'])
+        };
+        if !spelled {
+            return word.to_string();
+        }
+        let mut out = letters
+            .chars()
+            .map(String::from)
+            .collect::<Vec<_>>()
+            .join(" ");
+        if plural {
+            out.push_str("'s");
+        }
+        out
+    }
+}
@@ -0,0 +1,323 @@
+//! English
+
+use super::{plural, Builtin, Period, Speller, Unit, Words};
+
+pub struct English;
+
+const ONES: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+const SCALES: [(u64, &str); 4] = [
+    (1_000_000_000_000, "trillion"),
+    (1_000_000_000, "billion"),
+    (1_000_000, "million"),
+    (1_000, "thousand"),
+];
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const WORDS: Words = Words {
+    point: "point",
+    minus: "minus",
+    and: "and",
+    version: "version",
+    space: " ",
+    decimal: '.',
+    dot: "dot",
+    slash: "slash",
+    at: "at",
+};
+
+pub const DICTIONARY: Builtin = Builtin {
+    abbreviations: &[
+        ("e.g.", "for example"),
+        ("i.e.", "that is"),
+        ("etc.", "et cetera"),
+        ("vs.", "versus"),
+        ("approx.", "approximately"),
+        ("Dr.", "Doctor"),
+        ("Mr.", "Mister"),
+        ("Mrs.", "Missus"),
+        ("Ms.", "Miz"),
+        ("Prof.", "Professor"),
+        ("Jr.", "Junior"),
+        ("Sr.", "Senior"),
+        ("OK", "okay"),
+    ],
+    units: &[
+        ("km", &["kilometer", "kilometers"]),
+        ("m", &["meter", "meters"]),
+        ("cm", &["centimeter", "centimeters"]),
+        ("mm", &["millimeter", "millimeters"]),
+        ("mi", &["mile", "miles"]),
+        ("ft", &["foot", "feet"]),
+        ("km/h", &["kilometer per hour", "kilometers per hour"]),
+        ("mph", &["mile per hour", "miles per hour"]),
+        ("kg", &["kilogram", "kilograms"]),
+        ("g", &["gram", "grams"]),
+        ("mg", &["milligram", "milligrams"]),
+        ("lb", &["pound", "pounds"]),
+        ("lbs", &["pound", "pounds"]),
+        ("oz", &["ounce", "ounces"]),
+        ("L", &["liter", "liters"]),
+        ("ml", &["milliliter", "milliliters"]),
+        ("mL", &["milliliter", "milliliters"]),
+        ("h", &["hour", "hours"]),
+        ("min", &["minute", "minutes"]),
+        ("s", &["second", "seconds"]),
+        ("ms", &["millisecond", "milliseconds"]),
+        ("KB", &["kilobyte", "kilobytes"]),
+        ("kB", &["kilobyte", "kilobytes"]),
+        ("MB", &["megabyte", "megabytes"]),
+        ("GB", &["gigabyte", "gigabytes"]),
+        ("TB", &["terabyte", "terabytes"]),
+        ("KiB", &["kibibyte", "kibibytes"]),
+        ("MiB", &["mebibyte", "mebibytes"]),
+        ("GiB", &["gibibyte", "gibibytes"]),
+        ("Hz", &["hertz"]),
+        ("kHz", &["kilohertz"]),
+        ("MHz", &["megahertz"]),
+        ("GHz", &["gigahertz"]),
+        ("W", &["watt", "watts"]),
+        ("kW", &["kilowatt", "kilowatts"]),
+        ("V", &["volt", "volts"]),
+        ("mAh", &["milliamp hour", "milliamp hours"]),
+        ("°C", &["degree Celsius", "degrees Celsius"]),
+        ("°F", &["degree Fahrenheit", "degrees Fahrenheit"]),
+        ("px", &["pixel", "pixels"]),
+        ("fps", &["frame per second", "frames per second"]),
+    ],
+    feminine: &[],
+    words: &[
+        "ADD", "ALL", "AN", "AND", "ANY", "ARE", "AS", "AT", "BAD", "BE", "BIG", "BUT", "BY",
+        "CAN", "DAY", "DO", "END", "FEW", "FOR", "GET", "GO", "HAS", "HE", "HER", "HIS", "HOW",
+        "IF", "IN", "IS", "IT", "ITS", "KEY", "LET", "ME", "MY", "NEW", "NO", "NOT", "NOW", "OF",
+        "OFF", "ON", "ONE", "OR", "OUR", "OUT", "OWN", "PUT", "RUN", "SAY", "SEE", "SET", "SO",
+        "THE", "TO", "TOP", "TRY", "TWO", "UP", "USE", "WAS", "WAY", "WE", "WHO", "WHY", "YES",
+        "YET", "YOU",
+    ],
+    emoji: &[
+        ("😀", "grinning face"),
+        ("😂", "face with tears of joy"),
+        ("🙂", "slightly smiling face"),
+        ("😊", "smiling face with smiling eyes"),
+        ("😉", "winking face"),
+        ("😍", "smiling face with heart-eyes"),
+        ("🤔", "thinking face"),
+        ("😅", "grinning face with sweat"),
+        ("😢", "crying face"),
+        ("😭", "loudly crying face"),
+        ("😡", "enraged face"),
+        ("😎", "smiling face with sunglasses"),
+        ("👍", "thumbs up"),
+        ("👎", "thumbs down"),
+        ("👏", "clapping hands"),
+        ("🙏", "folded hands"),
+        ("👋", "waving hand"),
+        ("💪", "flexed biceps"),
+        ("👀", "eyes"),
+        ("❤", "red heart"),
+        ("🔥", "fire"),
+        ("✨", "sparkles"),
+        ("🎉", "party popper"),
+        ("🚀", "rocket"),
+        ("✅", "check mark"),
+        ("❌", "cross mark"),
+        ("⚠", "warning"),
+        ("💡", "light bulb"),
+        ("🐛", "bug"),
+        ("⭐", "star"),
+    ],
+    symbols: &[
+        ("→", "to"),
+        ("->", "to"),
+        ("⇒", "to"),
+        ("=>", "to"),
+        ("←", "from"),
+        ("<-", "from"),
+        ("&&", "and"),
+        ("&", "and"),
+        ("||", "or"),
+        ("≠", "not equal to"),
+        ("!=", "not equal to"),
+        ("≤", "less than or equal to"),
+        ("<=", "less than or equal to"),
+        ("≥", "greater than or equal to"),
+        (">=", "greater than or equal to"),
+        ("±", "plus or minus"),
+        ("×", "times"),
+    ],
+};
+
+fn below_hundred(n: u64) -> String {
+    match n {
+        0..=19 => ONES[n as usize].to_string(),
+        _ if n.is_multiple_of(10) => TENS[n as usize / 10].to_string(),
+        _ => format!("{}-{}", TENS[n as usize / 10], ONES[n as usize % 10]),
+    }
+}
+
+fn below_thousand(n: u64) -> String {
+    match (n / 100, n % 100) {
+        (0, rest) => below_hundred(rest),
+        (hundreds, 0) => format!("{} hundred", ONES[hundreds as usize]),
+        (hundreds, rest) => format!(
+            "{} hundred {}",
+            ONES[hundreds as usize],
+            below_hundred(rest)
+        ),
+    }
+}
+
+/// "1999" -> "nineteen ninety-nine", "2005" -> "two thousand five"
+fn say_year(y: u32) -> String {
+    let (high, low) = (y / 100, y % 100);
+    match y {
+        1000..=9999 if y % 1000 < 10 => English.cardinal(y as u64),
+        1000..=9999 if low == 0 => format!("{} hundred", below_hundred(high as u64)),
+        1000..=9999 if low < 10 => {
+            format!("{} oh {}", below_hundred(high as u64), ONES[low as usize])
+        }
+        1000..=9999 => format!(
+            "{} {}",
+            below_hundred(high as u64),
+            below_hundred(low as u64)
+        ),
+        _ => English.cardinal(y as u64),
+    }
+}
+
+impl Speller for English {
+    fn words(&self) -> &Words {
+        &WORDS
+    }
+
+    fn cardinal(&self, n: u64) -> String {
+        if n == 0 {
+            return ONES[0].to_string();
+        }
+        let mut parts = vec![];
+        let mut rest = n;
+        for (scale, name) in SCALES {
+            if rest >= scale {
+                parts.push(format!("{} {}", below_thousand(rest / scale), name));
+                rest %= scale;
+            }
+        }
+        if rest > 0 {
+            parts.push(below_thousand(rest));
+        }
+        parts.join(" ")
+    }
+
+    fn ordinal(&self, n: u64) -> String {
+        let cardinal = self.cardinal(n);
+        let split = cardinal.rfind([' ', '-']).map_or(0, |i| i + 1);
+        let (head, last) = cardinal.split_at(split);
+        let last = match last {
+            "one" => "first".to_string(),
+            "two" => "second".to_string(),
+            "three" => "third".to_string(),
+            "five" => "fifth".to_string(),
+            "eight" => "eighth".to_string(),
+            "nine" => "ninth".to_string(),
+            "twelve" => "twelfth".to_string(),
+            word if word.ends_with('y') => format!("{}ieth", &word[..word.len() - 1]),
+            word => format!("{}th", word),
+        };
+        format!("{}{}", head, last)
+    }
+
+    fn date(&self, year: u32, month: u32, day: u32) -> String {
+        format!(
+            "{} {}, {}",
+            MONTHS[month as usize - 1],
+            self.ordinal(day as u64),
+            say_year(year)
+        )
+    }
+
+    fn time(&self, hour: u32, minute: u32, second: Option<u32>, period: Option<Period>) -> String {
+        let mut spoken = match (minute, period) {
+            (0, Some(_)) => self.cardinal(hour as u64),
+            (0, None) if hour <= 12 => format!("{} o'clock", self.cardinal(hour as u64)),
+            (0, None) => format!("{} hundred", self.cardinal(hour as u64)),
+            (1..=9, _) => format!(
+                "{} oh {}",
+                self.cardinal(hour as u64),
+                ONES[minute as usize]
+            ),
+            _ => format!(
+                "{} {}",
+                self.cardinal(hour as u64),
+                below_hundred(minute as u64)
+            ),
+        };
+        if let Some(second) = second {
+            spoken.push_str(&format!(
+                " and {} {}",
+                self.cardinal(second as u64),
+                plural(Some(second as u64), "second", "seconds")
+            ));
+        }
+        match period {
+            Some(Period::Am) => spoken.push_str(" a m"),
+            Some(Period::Pm) => spoken.push_str(" p m"),
+            None => {}
+        }
+        spoken
+    }
+
+    fn unit(&self, unit: Unit, count: Option<u64>) -> &'static str {
+        match unit {
+            Unit::Dollar => plural(count, "dollar", "dollars"),
+            Unit::Euro => plural(count, "euro", "euros"),
+            Unit::Pound => plural(count, "pound", "pounds"),
+            Unit::Yen => "yen",
+            Unit::Won => "won",
+            Unit::Dong => "dong",
+            Unit::Ruble => plural(count, "ruble", "rubles"),
+            Unit::Cent | Unit::EuroCent => plural(count, "cent", "cents"),
+            Unit::Penny => plural(count, "penny", "pence"),
+            Unit::Kopek => plural(count, "kopek", "kopeks"),
+            Unit::Percent => "percent",
+        }
+    }
+}
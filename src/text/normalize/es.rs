@@ -0,0 +1,413 @@
+//! Spanish
+
+use super::{hour24, Builtin, Measure, Period, Speller, Unit, Words};
+
+pub struct Spanish;
+
+const ONES: [&str; 30] = [
+    "cero",
+    "uno",
+    "dos",
+    "tres",
+    "cuatro",
+    "cinco",
+    "seis",
+    "siete",
+    "ocho",
+    "nueve",
+    "diez",
+    "once",
+    "doce",
+    "trece",
+    "catorce",
+    "quince",
+    "dieciséis",
+    "diecisiete",
+    "dieciocho",
+    "diecinueve",
+    "veinte",
+    "veintiuno",
+    "veintidós",
+    "veintitrés",
+    "veinticuatro",
+    "veinticinco",
+    "veintiséis",
+    "veintisiete",
+    "veintiocho",
+    "veintinueve",
+];
+
+const TENS: [&str; 10] = [
+    "",
+    "",
+    "veinte",
+    "treinta",
+    "cuarenta",
+    "cincuenta",
+    "sesenta",
+    "setenta",
+    "ochenta",
+    "noventa",
+];
+
+const HUNDREDS: [&str; 10] = [
+    "",
+    "ciento",
+    "doscientos",
+    "trescientos",
+    "cuatrocientos",
+    "quinientos",
+    "seiscientos",
+    "setecientos",
+    "ochocientos",
+    "novecientos",
+];
+
+const ORDINALS: [&str; 11] = [
+    "", "primero", "segundo", "tercero", "cuarto", "quinto", "sexto", "séptimo", "octavo",
+    "noveno", "décimo",
+];
+
+const ORDINAL_TENS: [&str; 10] = [
+    "",
+    "décimo",
+    "vigésimo",
+    "trigésimo",
+    "cuadragésimo",
+    "quincuagésimo",
+    "sexagésimo",
+    "septuagésimo",
+    "octogésimo",
+    "nonagésimo",
+];
+
+const MONTHS: [&str; 12] = [
+    "enero",
+    "febrero",
+    "marzo",
+    "abril",
+    "mayo",
+    "junio",
+    "julio",
+    "agosto",
+    "septiembre",
+    "octubre",
+    "noviembre",
+    "diciembre",
+];
+
+const WORDS: Words = Words {
+    point: "coma",
+    minus: "menos",
+    and: "con",
+    version: "versión",
+    space: " ",
+    decimal: ',',
+    dot: "punto",
+    slash: "barra",
+    at: "arroba",
+};
+
+pub const DICTIONARY: Builtin = Builtin {
+    abbreviations: &[
+        ("p. ej.", "por ejemplo"),
+        ("etc.", "etcétera"),
+        ("aprox.", "aproximadamente"),
+        ("Sr.", "señor"),
+        ("Sra.", "señora"),
+        ("Srta.", "señorita"),
+        ("Dr.", "doctor"),
+        ("Dra.", "doctora"),
+        ("Ud.", "usted"),
+        ("Uds.", "ustedes"),
+        ("núm.", "número"),
+    ],
+    units: &[
+        ("km", &["kilómetro", "kilómetros"]),
+        ("m", &["metro", "metros"]),
+        ("cm", &["centímetro", "centímetros"]),
+        ("mm", &["milímetro", "milímetros"]),
+        ("km/h", &["kilómetro por hora", "kilómetros por hora"]),
+        ("kg", &["kilogramo", "kilogramos"]),
+        ("g", &["gramo", "gramos"]),
+        ("mg", &["miligramo", "miligramos"]),
+        ("l", &["litro", "litros"]),
+        ("L", &["litro", "litros"]),
+        ("ml", &["mililitro", "mililitros"]),
+        ("h", &["hora", "horas"]),
+        ("min", &["minuto", "minutos"]),
+        ("s", &["segundo", "segundos"]),
+        ("ms", &["milisegundo", "milisegundos"]),
+        ("KB", &["kilobyte", "kilobytes"]),
+        ("kB", &["kilobyte", "kilobytes"]),
+        ("MB", &["megabyte", "megabytes"]),
+        ("GB", &["gigabyte", "gigabytes"]),
+        ("TB", &["terabyte", "terabytes"]),
+        ("Hz", &["hercio", "hercios"]),
+        ("MHz", &["megahercio", "megahercios"]),
+        ("GHz", &["gigahercio", "gigahercios"]),
+        ("W", &["vatio", "vatios"]),
+        ("kW", &["kilovatio", "kilovatios"]),
+        ("V", &["voltio", "voltios"]),
+        ("°C", &["grado Celsius", "grados Celsius"]),
+    ],
+    feminine: &["h"],
+    words: &[],
+    emoji: &[
+        ("😀", "cara sonriendo"),
+        ("😂", "cara llorando de risa"),
+        ("🙂", "cara sonriendo ligeramente"),
+        ("😊", "cara feliz con ojos sonrientes"),
+        ("😉", "cara guiñando el ojo"),
+        ("😍", "cara sonriendo con ojos de corazón"),
+        ("🤔", "cara pensativa"),
+        ("😅", "cara sonriendo con sudor frío"),
+        ("😢", "cara llorando"),
+        ("😭", "cara llorando fuerte"),
+        ("😡", "cara cabreada"),
+        ("😎", "cara sonriendo con gafas de sol"),
+        ("👍", "pulgar hacia arriba"),
+        ("👎", "pulgar hacia abajo"),
+        ("👏", "manos aplaudiendo"),
+        ("🙏", "manos en oración"),
+        ("👋", "mano saludando"),
+        ("💪", "bíceps flexionado"),
+        ("👀", "ojos"),
+        ("❤", "corazón rojo"),
+        ("🔥", "fuego"),
+        ("✨", "chispas"),
+        ("🎉", "cañón de confeti"),
+        ("🚀", "cohete"),
+        ("✅", "marca de verificación"),
+        ("❌", "marca de cruz"),
+        ("⚠", "advertencia"),
+        ("💡", "bombilla"),
+        ("🐛", "insecto"),
+        ("⭐", "estrella"),
+    ],
+    symbols: &[
+        ("→", "a"),
+        ("->", "a"),
+        ("⇒", "a"),
+        ("=>", "a"),
+        ("←", "desde"),
+        ("<-", "desde"),
+        ("&&", "y"),
+        ("&", "y"),
+        ("||", "o"),
+        ("≠", "distinto de"),
+        ("!=", "distinto de"),
+        ("≤", "menor o igual que"),
+        ("<=", "menor o igual que"),
+        ("≥", "mayor o igual que"),
+        (">=", "mayor o igual que"),
+        ("±", "más o menos"),
+        ("×", "por"),
+    ],
+};
+
+/// "uno" shortens before a noun: "un dólar", "veintiún mil"
+fn apocope(words: String) -> String {
+    if let Some(stem) = words.strip_suffix("veintiuno") {
+        return format!("{}veintiún", stem);
+    }
+    match words.strip_suffix("uno") {
+        Some(stem) => format!("{}un", stem),
+        None => words,
+    }
+}
+
+fn below_hundred(n: u64) -> String {
+    match n {
+        0..=29 => ONES[n as usize].to_string(),
+        _ if n.is_multiple_of(10) => TENS[n as usize / 10].to_string(),
+        _ => format!("{} y {}", TENS[n as usize / 10], ONES[n as usize % 10]),
+    }
+}
+
+fn below_thousand(n: u64) -> String {
+    match (n / 100, n % 100) {
+        (0, rest) => below_hundred(rest),
+        (1, 0) => "cien".to_string(),
+        (hundreds, 0) => HUNDREDS[hundreds as usize].to_string(),
+        (hundreds, rest) => format!("{} {}", HUNDREDS[hundreds as usize], below_hundred(rest)),
+    }
+}
+
+fn below_million(n: u64) -> String {
+    match (n / 1000, n % 1000) {
+        (0, rest) => below_thousand(rest),
+        (1, 0) => "mil".to_string(),
+        (1, rest) => format!("mil {}", below_thousand(rest)),
+        (thousands, 0) => format!("{} mil", apocope(below_thousand(thousands))),
+        (thousands, rest) => format!(
+            "{} mil {}",
+            apocope(below_thousand(thousands)),
+            below_thousand(rest)
+        ),
+    }
+}
+
+impl Speller for Spanish {
+    fn words(&self) -> &Words {
+        &WORDS
+    }
+
+    /// A billion is "mil millones"; "billón" is a million millions
+    fn cardinal(&self, n: u64) -> String {
+        let mut parts = vec![];
+        let mut rest = n;
+        for (scale, one, many) in [
+            (1_000_000_000_000, "un billón", "billones"),
+            (1_000_000, "un millón", "millones"),
+        ] {
+            let count = rest / scale;
+            match count {
+                0 => {}
+                1 => parts.push(one.to_string()),
+                _ => parts.push(format!("{} {}", apocope(below_million(count)), many)),
+            }
+            rest %= scale;
+        }
+        if rest > 0 || parts.is_empty() {
+            parts.push(below_million(rest));
+        }
+        parts.join(" ")
+    }
+
+    /// "primero", "vigésimo tercero"; cardinals above a hundred
+    fn ordinal(&self, n: u64) -> String {
+        match n {
+            1..=10 => ORDINALS[n as usize].to_string(),
+            11..=99 if n.is_multiple_of(10) => ORDINAL_TENS[n as usize / 10].to_string(),
+            11..=99 => format!(
+                "{} {}",
+                ORDINAL_TENS[n as usize / 10],
+                ORDINALS[n as usize % 10]
+            ),
+            100 => "centésimo".to_string(),
+            _ => self.cardinal(n),
+        }
+    }
+
+    fn ordinal_suffix(&self, n: u64, suffix: &str) -> Option<String> {
+        match suffix {
+            "º" => Some(self.ordinal(n)),
+            // Feminine: "primera", "vigésima tercera"
+            "ª" => Some(
+                self.ordinal(n)
+                    .split(' ')
+                    .map(|word| match word.strip_suffix('o') {
+                        Some(stem) => format!("{}a", stem),
+                        None => word.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            ),
+            _ => None,
+        }
+    }
+
+    /// "catorce de marzo de dos mil veinticinco"
+    fn date(&self, year: u32, month: u32, day: u32) -> String {
+        let day = match day {
+            1 => "primero".to_string(),
+            _ => self.cardinal(day as u64),
+        };
+        format!(
+            "{} de {} de {}",
+            day,
+            MONTHS[month as usize - 1],
+            self.cardinal(year as u64)
+        )
+    }
+
+    /// "3:45 pm" -> "las tres y cuarenta y cinco de la tarde"
+    fn time(&self, hour: u32, minute: u32, second: Option<u32>, period: Option<Period>) -> String {
+        let hour = hour24(hour, period);
+        let twelve = match hour % 12 {
+            0 => 12,
+            h => h,
+        };
+        let mut spoken = match twelve {
+            1 => "la una".to_string(),
+            h => format!("las {}", self.cardinal(h as u64)),
+        };
+        if minute > 0 {
+            spoken.push_str(&format!(" y {}", self.cardinal(minute as u64)));
+        }
+        if let Some(second) = second {
+            let unit = if second == 1 { "segundo" } else { "segundos" };
+            spoken.push_str(&format!(
+                " y {} {}",
+                apocope(self.cardinal(second as u64)),
+                unit
+            ));
+        }
+        // Only mark the time of day when it was given or is not morning
+        if period.is_some() || hour >= 12 || hour == 0 {
+            spoken.push_str(match hour {
+                0..=5 => " de la madrugada",
+                6..=11 => " de la mañana",
+                12..=19 => " de la tarde",
+                _ => " de la noche",
+            });
+        }
+        spoken
+    }
+
+    fn unit(&self, unit: Unit, count: Option<u64>) -> &'static str {
+        let one = count == Some(1);
+        match unit {
+            Unit::Dollar if one => "dólar",
+            Unit::Dollar => "dólares",
+            Unit::Euro if one => "euro",
+            Unit::Euro => "euros",
+            Unit::Pound if one => "libra",
+            Unit::Pound => "libras",
+            Unit::Yen if one => "yen",
+            Unit::Yen => "yenes",
+            Unit::Won if one => "won",
+            Unit::Won => "wones",
+            Unit::Dong if one => "dong",
+            Unit::Dong => "dongs",
+            Unit::Ruble if one => "rublo",
+            Unit::Ruble => "rublos",
+            Unit::Cent if one => "centavo",
+            Unit::Cent => "centavos",
+            Unit::EuroCent if one => "céntimo",
+            Unit::EuroCent => "céntimos",
+            Unit::Penny if one => "penique",
+            Unit::Penny => "peniques",
+            Unit::Kopek if one => "kopek",
+            Unit::Kopek => "kopeks",
+            Unit::Percent => "por ciento",
+        }
+    }
+
+    /// "un kilómetro", "una hora"
+    fn measure(&self, number: String, unit: &Measure, count: Option<u64>) -> String {
+        let number = match (unit.feminine, count) {
+            (_, None) => number,
+            (true, Some(_)) => match number.strip_suffix("uno") {
+                Some(stem) => format!("{}una", stem),
+                None => number,
+            },
+            (false, Some(_)) => apocope(number),
+        };
+        format!("{} {}", number, unit.form(self.plural(count)))
+    }
+
+    /// "un dólar", "una libra"
+    fn with_unit(&self, number: String, unit: Unit, count: Option<u64>) -> String {
+        let number = match (unit, count) {
+            (Unit::Percent, _) | (_, None) => number,
+            (Unit::Pound, Some(_)) => match number.strip_suffix("uno") {
+                Some(stem) => format!("{}una", stem),
+                None => number,
+            },
+            _ => apocope(number),
+        };
+        format!("{} {}", number, self.unit(unit, count))
+    }
+}
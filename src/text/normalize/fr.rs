@@ -0,0 +1,332 @@
+//! French
+
+use super::{hour24, plural, Builtin, Measure, Period, Speller, Unit, Words};
+
+pub struct French;
+
+const ONES: [&str; 17] = [
+    "zéro", "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf", "dix", "onze",
+    "douze", "treize", "quatorze", "quinze", "seize",
+];
+
+const TENS: [&str; 7] = [
+    "",
+    "",
+    "vingt",
+    "trente",
+    "quarante",
+    "cinquante",
+    "soixante",
+];
+
+/// Scales above a thousand: value, singular, plural
+const SCALES: [(u64, &str, &str); 3] = [
+    (1_000_000_000_000, "billion", "billions"),
+    (1_000_000_000, "milliard", "milliards"),
+    (1_000_000, "million", "millions"),
+];
+
+const MONTHS: [&str; 12] = [
+    "janvier",
+    "février",
+    "mars",
+    "avril",
+    "mai",
+    "juin",
+    "juillet",
+    "août",
+    "septembre",
+    "octobre",
+    "novembre",
+    "décembre",
+];
+
+const WORDS: Words = Words {
+    point: "virgule",
+    minus: "moins",
+    and: "et",
+    version: "version",
+    space: " ",
+    decimal: ',',
+    dot: "point",
+    slash: "slash",
+    at: "arobase",
+};
+
+pub const DICTIONARY: Builtin = Builtin {
+    abbreviations: &[
+        ("p. ex.", "par exemple"),
+        ("c.-à-d.", "c'est-à-dire"),
+        ("etc.", "et cetera"),
+        ("env.", "environ"),
+        ("M.", "Monsieur"),
+        ("MM.", "Messieurs"),
+        ("Mme", "Madame"),
+        ("Mlle", "Mademoiselle"),
+        ("Dr", "Docteur"),
+        ("n°", "numéro"),
+    ],
+    units: &[
+        ("km", &["kilomètre", "kilomètres"]),
+        ("m", &["mètre", "mètres"]),
+        ("cm", &["centimètre", "centimètres"]),
+        ("mm", &["millimètre", "millimètres"]),
+        ("km/h", &["kilomètre par heure", "kilomètres par heure"]),
+        ("kg", &["kilogramme", "kilogrammes"]),
+        ("g", &["gramme", "grammes"]),
+        ("mg", &["milligramme", "milligrammes"]),
+        ("l", &["litre", "litres"]),
+        ("L", &["litre", "litres"]),
+        ("ml", &["millilitre", "millilitres"]),
+        ("h", &["heure", "heures"]),
+        ("min", &["minute", "minutes"]),
+        ("s", &["seconde", "secondes"]),
+        ("ms", &["milliseconde", "millisecondes"]),
+        ("Ko", &["kilo-octet", "kilo-octets"]),
+        ("Mo", &["méga-octet", "méga-octets"]),
+        ("Go", &["giga-octet", "giga-octets"]),
+        ("To", &["téra-octet", "téra-octets"]),
+        ("Hz", &["hertz"]),
+        ("MHz", &["mégahertz"]),
+        ("GHz", &["gigahertz"]),
+        ("W", &["watt", "watts"]),
+        ("kW", &["kilowatt", "kilowatts"]),
+        ("V", &["volt", "volts"]),
+        ("°C", &["degré Celsius", "degrés Celsius"]),
+    ],
+    feminine: &["h", "min", "s", "ms"],
+    words: &[],
+    emoji: &[
+        ("😀", "visage rieur"),
+        ("😂", "visage riant aux larmes"),
+        ("🙂", "visage avec un léger sourire"),
+        ("😊", "visage souriant aux yeux rieurs"),
+        ("😉", "visage qui fait un clin d'œil"),
+        ("😍", "visage souriant avec yeux en forme de cœur"),
+        ("🤔", "visage en pleine réflexion"),
+        ("😅", "visage souriant avec une goutte de sueur"),
+        ("😢", "visage qui pleure"),
+        ("😭", "visage qui pleure à chaudes larmes"),
+        ("😡", "visage boudeur"),
+        ("😎", "visage avec lunettes de soleil"),
+        ("👍", "pouce vers le haut"),
+        ("👎", "pouce vers le bas"),
+        ("👏", "applaudissements"),
+        ("🙏", "mains en prière"),
+        ("👋", "main qui salue"),
+        ("💪", "biceps contracté"),
+        ("👀", "yeux"),
+        ("❤", "cœur rouge"),
+        ("🔥", "feu"),
+        ("✨", "étincelles"),
+        ("🎉", "cotillons"),
+        ("🚀", "fusée"),
+        ("✅", "coche"),
+        ("❌", "croix"),
+        ("⚠", "avertissement"),
+        ("💡", "ampoule"),
+        ("🐛", "insecte"),
+        ("⭐", "étoile"),
+    ],
+    symbols: &[
+        ("→", "vers"),
+        ("->", "vers"),
+        ("⇒", "vers"),
+        ("=>", "vers"),
+        ("←", "depuis"),
+        ("<-", "depuis"),
+        ("&&", "et"),
+        ("&", "et"),
+        ("||", "ou"),
+        ("≠", "différent de"),
+        ("!=", "différent de"),
+        ("≤", "inférieur ou égal à"),
+        ("<=", "inférieur ou égal à"),
+        ("≥", "supérieur ou égal à"),
+        (">=", "supérieur ou égal à"),
+        ("±", "plus ou moins"),
+        ("×", "fois"),
+    ],
+};
+
+fn below_hundred(n: u64) -> String {
+    let (tens, ones) = (n as usize / 10, n as usize % 10);
+    match n {
+        0..=16 => ONES[n as usize].to_string(),
+        17..=19 => format!("dix-{}", ONES[ones]),
+        70..=79 if n == 71 => "soixante et onze".to_string(),
+        70..=79 => format!("soixante-{}", below_hundred(n - 60)),
+        80 => "quatre-vingts".to_string(),
+        81..=99 => format!("quatre-vingt-{}", below_hundred(n - 80)),
+        _ if ones == 0 => TENS[tens].to_string(),
+        _ if ones == 1 => format!("{} et un", TENS[tens]),
+        _ => format!("{}-{}", TENS[tens], ONES[ones]),
+    }
+}
+
+fn below_thousand(n: u64) -> String {
+    match (n / 100, n % 100) {
+        (0, rest) => below_hundred(rest),
+        (1, 0) => "cent".to_string(),
+        (1, rest) => format!("cent {}", below_hundred(rest)),
+        (hundreds, 0) => format!("{} cents", ONES[hundreds as usize]),
+        (hundreds, rest) => format!("{} cent {}", ONES[hundreds as usize], below_hundred(rest)),
+    }
+}
+
+/// "quatre-vingts" and "deux cents" lose their s before "mille" and "-ième"
+fn multiplier(words: String) -> String {
+    match words.strip_suffix("vingts").or(words.strip_suffix("cents")) {
+        Some(_) => words[..words.len() - 1].to_string(),
+        None => words,
+    }
+}
+
+fn below_million(n: u64) -> String {
+    match (n / 1000, n % 1000) {
+        (0, rest) => below_thousand(rest),
+        (1, 0) => "mille".to_string(),
+        (1, rest) => format!("mille {}", below_thousand(rest)),
+        (thousands, 0) => format!("{} mille", multiplier(below_thousand(thousands))),
+        (thousands, rest) => format!(
+            "{} mille {}",
+            multiplier(below_thousand(thousands)),
+            below_thousand(rest)
+        ),
+    }
+}
+
+/// "une" for feminine nouns: "vingt et une heures"
+fn feminine(words: String) -> String {
+    match words.strip_suffix("un") {
+        Some(stem) if stem.is_empty() || stem.ends_with(' ') || stem.ends_with('-') => {
+            format!("{}une", stem)
+        }
+        _ => words,
+    }
+}
+
+impl Speller for French {
+    fn words(&self) -> &Words {
+        &WORDS
+    }
+
+    fn cardinal(&self, n: u64) -> String {
+        let mut parts = vec![];
+        let mut rest = n;
+        for (scale, one, many) in SCALES {
+            let count = rest / scale;
+            if count > 0 {
+                let name = if count == 1 { one } else { many };
+                parts.push(format!("{} {}", below_million(count), name));
+            }
+            rest %= scale;
+        }
+        if rest > 0 || parts.is_empty() {
+            parts.push(below_million(rest));
+        }
+        parts.join(" ")
+    }
+
+    /// "premier", "deuxième", "vingt et unième"
+    fn ordinal(&self, n: u64) -> String {
+        if n == 1 {
+            return "premier".to_string();
+        }
+        let words = multiplier(self.cardinal(n));
+        let stem = match words.as_str() {
+            w if w.ends_with("cinq") => format!("{}u", w),
+            w if w.ends_with("neuf") => format!("{}v", &w[..w.len() - 1]),
+            w if w.ends_with('e') => w[..w.len() - 1].to_string(),
+            w => w.to_string(),
+        };
+        format!("{}ième", stem)
+    }
+
+    fn ordinal_suffix(&self, n: u64, suffix: &str) -> Option<String> {
+        match suffix {
+            "re" if n == 1 => Some("première".to_string()),
+            "er" | "re" | "e" | "ème" | "eme" => Some(self.ordinal(n)),
+            _ => None,
+        }
+    }
+
+    /// "le quatorze mars deux mille vingt-cinq"
+    fn date(&self, year: u32, month: u32, day: u32) -> String {
+        let day = match day {
+            1 => "premier".to_string(),
+            _ => self.cardinal(day as u64),
+        };
+        format!(
+            "le {} {} {}",
+            day,
+            MONTHS[month as usize - 1],
+            self.cardinal(year as u64)
+        )
+    }
+
+    /// "15:30" -> "quinze heures trente"
+    fn time(&self, hour: u32, minute: u32, second: Option<u32>, period: Option<Period>) -> String {
+        let hour = hour24(hour, period);
+        let unit = if hour <= 1 { "heure" } else { "heures" };
+        let mut spoken = format!("{} {}", feminine(self.cardinal(hour as u64)), unit);
+        if minute > 0 {
+            spoken.push(' ');
+            spoken.push_str(&feminine(self.cardinal(minute as u64)));
+        }
+        if let Some(second) = second {
+            let unit = if second <= 1 { "seconde" } else { "secondes" };
+            spoken.push_str(&format!(
+                " et {} {}",
+                feminine(self.cardinal(second as u64)),
+                unit
+            ));
+        }
+        spoken
+    }
+
+    fn unit(&self, unit: Unit, count: Option<u64>) -> &'static str {
+        // French keeps the singular for 0 and 1
+        let count = count.map(|n| n.max(1));
+        match unit {
+            Unit::Dollar => plural(count, "dollar", "dollars"),
+            Unit::Euro => plural(count, "euro", "euros"),
+            Unit::Pound => plural(count, "livre", "livres"),
+            Unit::Yen => plural(count, "yen", "yens"),
+            Unit::Won => plural(count, "won", "wons"),
+            Unit::Dong => plural(count, "dong", "dongs"),
+            Unit::Ruble => plural(count, "rouble", "roubles"),
+            Unit::Cent => plural(count, "cent", "cents"),
+            Unit::EuroCent => plural(count, "centime", "centimes"),
+            Unit::Penny => plural(count, "penny", "pence"),
+            Unit::Kopek => plural(count, "kopeck", "kopecks"),
+            Unit::Percent => "pour cent",
+        }
+    }
+
+    /// "une livre"
+    /// Zero and one take the singular
+    fn plural(&self, count: Option<u64>) -> usize {
+        match count {
+            Some(0 | 1) => 0,
+            _ => 1,
+        }
+    }
+
+    /// "une heure", "vingt et une minutes"
+    fn measure(&self, number: String, unit: &Measure, count: Option<u64>) -> String {
+        let number = match unit.feminine {
+            true => feminine(number),
+            false => number,
+        };
+        format!("{} {}", number, unit.form(self.plural(count)))
+    }
+
+    fn with_unit(&self, number: String, unit: Unit, count: Option<u64>) -> String {
+        let number = match unit {
+            Unit::Pound => feminine(number),
+            _ => number,
+        };
+        format!("{} {}", number, self.unit(unit, count))
+    }
+}
@@ -0,0 +1,210 @@
+//! Korean (Sino-Korean numbers; native numbers for hours)
+
+use super::{hour24, Builtin, Period, Speller, Unit, Words};
+
+pub struct Korean;
+
+const DIGITS: [&str; 10] = ["영", "일", "이", "삼", "사", "오", "육", "칠", "팔", "구"];
+
+/// Native numbers used with 시: 한 시 ... 열두 시
+const HOURS: [&str; 13] = [
+    "영", "한", "두", "세", "네", "다섯", "여섯", "일곱", "여덟", "아홉", "열", "열한", "열두",
+];
+
+const WORDS: Words = Words {
+    point: "점",
+    minus: "마이너스",
+    and: "",
+    version: "버전",
+    space: " ",
+    decimal: '.',
+    dot: "점",
+    slash: "슬래시",
+    at: "골뱅이",
+};
+
+pub const DICTIONARY: Builtin = Builtin {
+    abbreviations: &[],
+    units: &[
+        ("km", &["킬로미터"]),
+        ("m", &["미터"]),
+        ("cm", &["센티미터"]),
+        ("mm", &["밀리미터"]),
+        ("kg", &["킬로그램"]),
+        ("g", &["그램"]),
+        ("L", &["리터"]),
+        ("ml", &["밀리리터"]),
+        ("KB", &["킬로바이트"]),
+        ("MB", &["메가바이트"]),
+        ("GB", &["기가바이트"]),
+        ("TB", &["테라바이트"]),
+        ("Hz", &["헤르츠"]),
+        ("GHz", &["기가헤르츠"]),
+        ("W", &["와트"]),
+        ("V", &["볼트"]),
+        ("°C", &["도"]),
+    ],
+    feminine: &[],
+    words: &[],
+    emoji: &[
+        ("😀", "활짝 웃는 얼굴"),
+        ("😂", "기뻐서 눈물 흘리는 얼굴"),
+        ("🙂", "살짝 미소 짓는 얼굴"),
+        ("😊", "눈웃음 치는 얼굴"),
+        ("😉", "윙크하는 얼굴"),
+        ("😍", "하트 눈 얼굴"),
+        ("🤔", "생각하는 얼굴"),
+        ("😅", "식은땀 흘리며 웃는 얼굴"),
+        ("😢", "우는 얼굴"),
+        ("😭", "엉엉 우는 얼굴"),
+        ("😡", "화난 얼굴"),
+        ("😎", "선글라스 쓴 얼굴"),
+        ("👍", "최고"),
+        ("👎", "별로"),
+        ("👏", "박수"),
+        ("🙏", "합장"),
+        ("👋", "손 흔들기"),
+        ("💪", "알통"),
+        ("👀", "눈"),
+        ("❤", "빨간 하트"),
+        ("🔥", "불"),
+        ("✨", "반짝임"),
+        ("🎉", "파티"),
+        ("🚀", "로켓"),
+        ("✅", "체크 표시"),
+        ("❌", "엑스 표시"),
+        ("⚠", "경고"),
+        ("💡", "전구"),
+        ("🐛", "벌레"),
+        ("⭐", "별"),
+    ],
+    symbols: &[
+        ("→", "에서"),
+        ("->", "에서"),
+        ("⇒", "에서"),
+        ("=>", "에서"),
+        ("←", "부터"),
+        ("<-", "부터"),
+        ("&&", "그리고"),
+        ("&", "그리고"),
+        ("||", "또는"),
+        ("≠", "같지 않음"),
+        ("!=", "같지 않음"),
+        ("≤", "이하"),
+        ("<=", "이하"),
+        ("≥", "이상"),
+        (">=", "이상"),
+        ("±", "플러스 마이너스"),
+        ("×", "곱하기"),
+    ],
+};
+
+/// 1 to 9999: "천이백구십구"; a leading 1 is silent before 십, 백, and 천
+fn below_myriad(n: u64) -> String {
+    let mut words = String::new();
+    for (value, name) in [(1000, "천"), (100, "백"), (10, "십")] {
+        let digit = n / value % 10;
+        if digit > 1 {
+            words.push_str(DIGITS[digit as usize]);
+        }
+        if digit > 0 {
+            words.push_str(name);
+        }
+    }
+    if !n.is_multiple_of(10) {
+        words.push_str(DIGITS[(n % 10) as usize]);
+    }
+    words
+}
+
+impl Speller for Korean {
+    fn words(&self) -> &Words {
+        &WORDS
+    }
+
+    /// Groups of four digits: 일억 이천만, 만 오천
+    fn cardinal(&self, n: u64) -> String {
+        if n == 0 {
+            return DIGITS[0].to_string();
+        }
+        let mut parts = vec![];
+        let mut rest = n;
+        for (scale, name) in [
+            (1_000_000_000_000, "조"),
+            (100_000_000, "억"),
+            (10_000, "만"),
+        ] {
+            let count = rest / scale;
+            match count {
+                0 => {}
+                1 if name == "만" => parts.push(name.to_string()),
+                _ => parts.push(format!("{}{}", below_myriad(count), name)),
+            }
+            rest %= scale;
+        }
+        if rest > 0 {
+            parts.push(below_myriad(rest));
+        }
+        parts.join(" ")
+    }
+
+    /// "제일", "제이"
+    fn ordinal(&self, n: u64) -> String {
+        format!("제{}", self.cardinal(n))
+    }
+
+    /// "이천이십오년 삼월 십사일"
+    fn date(&self, year: u32, month: u32, day: u32) -> String {
+        let month = match month {
+            6 => "유".to_string(),
+            10 => "시".to_string(),
+            m => self.cardinal(m as u64),
+        };
+        format!(
+            "{}년 {}월 {}일",
+            self.cardinal(year as u64),
+            month,
+            self.cardinal(day as u64)
+        )
+    }
+
+    /// "3:45 pm" -> "오후 세 시 사십오 분"
+    fn time(&self, hour: u32, minute: u32, second: Option<u32>, period: Option<Period>) -> String {
+        let hour = hour24(hour, period);
+        let mut parts = vec![];
+        // Say 오전/오후 when given, or when a 24-hour time needs it
+        if period.is_some() || hour >= 13 || hour == 0 {
+            parts.push(if hour < 12 { "오전" } else { "오후" });
+        }
+        let twelve = match hour % 12 {
+            0 => 12,
+            h => h,
+        };
+        parts.push(HOURS[twelve as usize]);
+        parts.push("시");
+        let minute = (minute > 0).then(|| format!("{} 분", self.cardinal(minute as u64)));
+        let second = second.map(|s| format!("{} 초", self.cardinal(s as u64)));
+        let mut spoken = parts.join(" ");
+        for part in [minute, second].into_iter().flatten() {
+            spoken.push(' ');
+            spoken.push_str(&part);
+        }
+        spoken
+    }
+
+    fn unit(&self, unit: Unit, _count: Option<u64>) -> &'static str {
+        match unit {
+            Unit::Dollar => "달러",
+            Unit::Euro => "유로",
+            Unit::Pound => "파운드",
+            Unit::Yen => "엔",
+            Unit::Won => "원",
+            Unit::Dong => "동",
+            Unit::Ruble => "루블",
+            Unit::Cent | Unit::EuroCent => "센트",
+            Unit::Penny => "펜스",
+            Unit::Kopek => "코페이카",
+            Unit::Percent => "퍼센트",
+        }
+    }
+}
@@ -0,0 +1,683 @@
+//! Text normalization - numbers, dates, times, and money spoken as words
+//!
+//! Voices read digits poorly: "2025-03-14", "$1,299.99", "3:45 pm", "1st",
+//! "10%", and "v0.3.0" come out as strings of digits or are skipped. Each
+//! of these is rewritten into words of the voice's language before
+//! synthesis. Abbreviations, units, and acronyms are expanded from
+//! per-language dictionaries, which users extend with
+//! `dictionaries/<lang>.toml` files next to `config.toml`.
+
+mod de;
+mod dictionary;
+mod en;
+mod es;
+mod fr;
+mod ko;
+mod ru;
+pub mod symbols;
+mod vi;
+mod zh;
+
+use crate::config::Config;
+use crate::error::Result;
+use dictionary::{Builtin, Dictionary, Measure};
+use regex_lite::{Captures, Regex};
+use std::sync::LazyLock;
+
+pub use dictionary::Dictionaries;
+
+/// Language whose words numbers are spelled in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Lang {
+    En,
+    Zh,
+    De,
+    Fr,
+    Es,
+    Ru,
+    Ko,
+    Vi,
+}
+
+impl Lang {
+    /// Every supported language
+    pub const ALL: [Lang; 8] = [
+        Lang::En,
+        Lang::Zh,
+        Lang::De,
+        Lang::Fr,
+        Lang::Es,
+        Lang::Ru,
+        Lang::Ko,
+        Lang::Vi,
+    ];
+
+    /// ISO 639-1 code, also the name of the user dictionary file
+    pub fn code(self) -> &'static str {
+        match self {
+            Lang::En => "en",
+            Lang::Zh => "zh",
+            Lang::De => "de",
+            Lang::Fr => "fr",
+            Lang::Es => "es",
+            Lang::Ru => "ru",
+            Lang::Ko => "ko",
+            Lang::Vi => "vi",
+        }
+    }
+
+    /// Language of a voice locale ("en_US", "de_DE"); English if unknown
+    ///
+    /// Bilingual "zh_en" voices read Chinese numbers when the text has
+    /// Chinese characters and English otherwise.
+    pub fn for_locale(locale: &str, text: &str) -> Self {
+        if locale == "zh_en" {
+            return if text.chars().any(is_han) {
+                Lang::Zh
+            } else {
+                Lang::En
+            };
+        }
+        match locale.split(['_', '-']).next().unwrap_or("") {
+            "zh" => Lang::Zh,
+            "de" => Lang::De,
+            "fr" => Lang::Fr,
+            "es" => Lang::Es,
+            "ru" => Lang::Ru,
+            "ko" => Lang::Ko,
+            "vi" => Lang::Vi,
+            _ => Lang::En,
+        }
+    }
+
+    fn speller(self) -> &'static dyn Speller {
+        match self {
+            Lang::En => &en::English,
+            Lang::Zh => &zh::Chinese,
+            Lang::De => &de::German,
+            Lang::Fr => &fr::French,
+            Lang::Es => &es::Spanish,
+            Lang::Ru => &ru::Russian,
+            Lang::Ko => &ko::Korean,
+            Lang::Vi => &vi::Vietnamese,
+        }
+    }
+
+    fn builtin(self) -> &'static Builtin {
+        match self {
+            Lang::En => &en::DICTIONARY,
+            Lang::Zh => &zh::DICTIONARY,
+            Lang::De => &de::DICTIONARY,
+            Lang::Fr => &fr::DICTIONARY,
+            Lang::Es => &es::DICTIONARY,
+            Lang::Ru => &ru::DICTIONARY,
+            Lang::Ko => &ko::DICTIONARY,
+            Lang::Vi => &vi::DICTIONARY,
+        }
+    }
+}
+
+/// User settings normalization follows: dictionaries and symbol rules
+///
+/// The default has the built-in dictionaries and default symbol rules;
+/// [`Normalization::from_config`] adds the user's.
+#[derive(Debug, Clone, Default)]
+pub struct Normalization {
+    /// How emoji, symbols, and URLs are read
+    pub symbols: symbols::Rules,
+    /// Abbreviations, units, and acronyms
+    pub dictionaries: Dictionaries,
+}
+
+impl Normalization {
+    /// The `[symbols]` section of a config and the user dictionaries next
+    /// to the config file, failing on a dictionary that does not parse
+    pub fn from_config(config: &Config) -> Result<Self> {
+        Ok(Self {
+            symbols: config.symbols.clone(),
+            dictionaries: Dictionaries::from_config_dir()?,
+        })
+    }
+}
+
+/// Rewrite numbers, dates, times, money, versions, abbreviations, units,
+/// acronyms, emoji, symbols, and URLs as words
+///
+/// `locale` is the voice's language (`Voice::lang`). Uses built-in
+/// dictionaries and default symbol rules; see [`normalize_with`].
+pub fn normalize(text: &str, locale: &str) -> String {
+    normalize_with(text, locale, &Normalization::default())
+}
+
+/// [`normalize`] with the given dictionaries and emoji, symbol, and URL rules
+pub fn normalize_with(text: &str, locale: &str, normalization: &Normalization) -> String {
+    let lang = Lang::for_locale(locale, text);
+    let speller = lang.speller();
+    let text = symbols::verbalize(
+        text,
+        lang.builtin(),
+        speller.words(),
+        &normalization.symbols,
+    );
+    let dictionary = normalization.dictionaries.get(lang);
+    let spoken = Normalizer {
+        speller,
+        dictionary,
+    }
+    .run(&text);
+    dictionary.expand(&spoken)
+}
+
+/// Unit read after a number
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Unit {
+    Dollar,
+    Euro,
+    Pound,
+    Yen,
+    Won,
+    Dong,
+    Ruble,
+    Cent,
+    EuroCent,
+    Penny,
+    Kopek,
+    Percent,
+}
+
+impl Unit {
+    fn currency(symbol: &str) -> Option<Self> {
+        Some(match symbol {
+            "$" => Unit::Dollar,
+            "€" => Unit::Euro,
+            "£" => Unit::Pound,
+            "¥" => Unit::Yen,
+            "₩" => Unit::Won,
+            "₫" => Unit::Dong,
+            "₽" => Unit::Ruble,
+            _ => return None,
+        })
+    }
+
+    /// Hundredth of a currency, if it is used
+    fn subunit(self) -> Option<Self> {
+        match self {
+            Unit::Dollar => Some(Unit::Cent),
+            Unit::Euro => Some(Unit::EuroCent),
+            Unit::Pound => Some(Unit::Penny),
+            Unit::Ruble => Some(Unit::Kopek),
+            _ => None,
+        }
+    }
+}
+
+/// 12-hour clock marker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Period {
+    Am,
+    Pm,
+}
+
+/// Hour on a 24-hour clock
+fn hour24(hour: u32, period: Option<Period>) -> u32 {
+    match period {
+        Some(Period::Am) if hour == 12 => 0,
+        Some(Period::Pm) if hour < 12 => hour + 12,
+        _ => hour,
+    }
+}
+
+/// Small words each language needs
+struct Words {
+    /// Decimal separator ("point")
+    point: &'static str,
+    minus: &'static str,
+    /// Between a currency and its subunit ("and")
+    and: &'static str,
+    /// Read before "v1.2.3"
+    version: &'static str,
+    /// Between spelled words (empty for Chinese)
+    space: &'static str,
+    /// Decimal separator in writing
+    decimal: char,
+    /// Read in web addresses: "example dot com slash docs", "ann at example dot com"
+    dot: &'static str,
+    slash: &'static str,
+    at: &'static str,
+}
+
+/// Spelling of numbers and number formats in one language
+trait Speller: Sync {
+    fn words(&self) -> &Words;
+
+    fn cardinal(&self, n: u64) -> String;
+
+    fn ordinal(&self, n: u64) -> String;
+
+    /// Ordinal written with a suffix ("1st", "2e", "3-го"), if this language uses it
+    fn ordinal_suffix(&self, _n: u64, _suffix: &str) -> Option<String> {
+        None
+    }
+
+    fn date(&self, year: u32, month: u32, day: u32) -> String;
+
+    fn time(&self, hour: u32, minute: u32, second: Option<u32>, period: Option<Period>) -> String;
+
+    /// Word for a unit; `count` is None for fractional amounts
+    fn unit(&self, unit: Unit, count: Option<u64>) -> &'static str;
+
+    /// Spelled number followed by its unit
+    fn with_unit(&self, number: String, unit: Unit, count: Option<u64>) -> String {
+        format!("{} {}", number, self.unit(unit, count))
+    }
+
+    /// Which of a dictionary unit's forms a count takes: 0 for one, 1 otherwise
+    fn plural(&self, count: Option<u64>) -> usize {
+        match count {
+            Some(1) => 0,
+            _ => 1,
+        }
+    }
+
+    /// Spelled number followed by a dictionary unit ("five kilometers")
+    fn measure(&self, number: String, unit: &Measure, count: Option<u64>) -> String {
+        format!(
+            "{}{}{}",
+            number,
+            self.words().space,
+            unit.form(self.plural(count))
+        )
+    }
+}
+
+/// Number as written: sign, integer digits, and fraction digits
+#[derive(Debug, PartialEq)]
+struct Number {
+    negative: bool,
+    int: String,
+    frac: Option<String>,
+}
+
+impl Number {
+    /// Parse "1,299.99", "1.299,99", or "3,5"; `decimal` settles "1,234"
+    ///
+    /// None when separators do not form a number ("1,2,3").
+    fn parse(text: &str, decimal: char) -> Option<Self> {
+        let (negative, text) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, text),
+        };
+        let groups: Vec<&str> = text.split(|c: char| !c.is_ascii_digit()).collect();
+        let seps: Vec<char> = text.chars().filter(|c| !c.is_ascii_digit()).collect();
+
+        // A separator is the decimal point if it is the last one and used once
+        let decimal = match seps.last() {
+            None => None,
+            Some(&last) if last == '\u{a0}' || last == '\u{202f}' => None,
+            Some(&last) if seps.iter().filter(|&&c| c == last).count() > 1 => None,
+            Some(&last) if seps.iter().any(|&c| c != last) => Some(last),
+            Some(&last) if last != decimal && groups.last().is_some_and(|g| g.len() == 3) => None,
+            Some(&last) => Some(last),
+        };
+        let (int_groups, frac) = match decimal {
+            Some(_) => (
+                &groups[..groups.len() - 1],
+                groups.last().map(|g| g.to_string()),
+            ),
+            None => (&groups[..], None),
+        };
+        if int_groups.iter().skip(1).any(|g| g.len() != 3) {
+            return None;
+        }
+        Some(Number {
+            negative,
+            int: int_groups.concat(),
+            frac,
+        })
+    }
+
+    /// Integer value, unless it is read digit by digit ("007", huge numbers)
+    fn value(&self) -> Option<u64> {
+        if self.int.len() > 15 || (self.int.len() > 1 && self.int.starts_with('0')) {
+            return None;
+        }
+        self.int.parse().ok()
+    }
+
+    /// Count for choosing a unit's form (None for fractions)
+    fn count(&self) -> Option<u64> {
+        match &self.frac {
+            Some(_) => None,
+            None => self.value(),
+        }
+    }
+}
+
+const NUMBER: &str = r"\d+(?:[.,\x{a0}\x{202f}]\d+)*";
+
+static PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    let patterns = [
+        r"(?P<date>\b(?P<year>\d{4})[-/](?P<month>\d{1,2})[-/](?P<day>\d{1,2})\b)".to_string(),
+        r"(?P<time>\b(?P<hour>\d{1,2}):(?P<minute>\d{2})(?::(?P<second>\d{2}))?(?:\s?(?P<period>[AaPp])\.?[Mm]\b)?)".to_string(),
+        r"(?P<version>\b[vV]\d+(?:\.\d+)+\b|\b\d+(?:\.\d+){2,}\b)".to_string(),
+        format!(r"(?P<symbol>[$€£¥₩₫₽])\s?(?P<amount>{})", NUMBER),
+        format!(r"(?P<amount2>{})\s?(?P<symbol2>[$€£¥₩₫₽])", NUMBER),
+        format!(r"(?P<percent>{})\s?%", NUMBER),
+        r"(?P<ordinal>\d+)(?P<suffix>st|nd|rd|th|ème|eme|er|re|e|\.?º|\.?ª|-(?:ого|го|ый|ой|ая|ое|й|я|е))".to_string(),
+        format!(r"(?P<number>-?{})", NUMBER),
+    ];
+    Regex::new(&patterns.join("|")).expect("normalization pattern")
+});
+
+struct Normalizer<'a> {
+    speller: &'static dyn Speller,
+    dictionary: &'a Dictionary,
+}
+
+impl Normalizer<'_> {
+    fn run(&self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut pos = 0;
+
+        while let Some(caps) = PATTERN.captures_at(text, pos) {
+            let whole = caps.get(0).unwrap();
+            out.push_str(&text[pos..whole.start()]);
+            let before = text[..whole.start()].chars().next_back();
+            let after = text[whole.end()..].chars().next();
+
+            // Digits inside words ("mp3", "x86") are left alone
+            if before.is_some_and(is_letter) {
+                let digits = whole.as_str();
+                let end = digits
+                    .find(|c: char| !c.is_ascii_digit())
+                    .filter(|&i| i > 0)
+                    .unwrap_or(digits.len());
+                out.push_str(&digits[..end]);
+                pos = whole.start() + end;
+                continue;
+            }
+
+            // "am 3. Mai" -> "am dritten Mai" where a period marks ordinals
+            let rest = &text[whole.end()..];
+            if let Some(spoken) = caps
+                .name("number")
+                .and_then(|number| self.ordinal_period(number.as_str(), rest))
+            {
+                out.push_str(&spoken);
+                pos = whole.end() + '.'.len_utf8();
+                continue;
+            }
+
+            // "5 km/h" -> "five kilometers per hour"
+            if let Some(number) = caps.name("number") {
+                let sign = number.as_str().starts_with('-');
+                if let Some((unit, len)) = self.dictionary.unit_at(rest) {
+                    if !(sign && before.is_some_and(|c| c.is_alphanumeric())) {
+                        if let Some(spoken) = self.measure(number.as_str(), unit) {
+                            out.push_str(&spoken);
+                            pos = whole.end() + len;
+                            continue;
+                        }
+                    }
+                }
+            }
+
+            // ...and so are digits running into letters ("1e10", "3D")
+            if caps.name("number").is_some() && after.is_some_and(is_letter) {
+                out.push_str(whole.as_str());
+                pos = whole.end();
+                continue;
+            }
+
+            match self.replace(&caps, before, after) {
+                Some(spoken) => {
+                    out.push_str(&spoken);
+                    pos = whole.end();
+                }
+                None => {
+                    // Not what it looked like: read the first run of digits
+                    // on its own and carry on after it
+                    let digits = whole.as_str();
+                    let start = digits.find(|c: char| c.is_ascii_digit()).unwrap_or(0);
+                    let len = digits[start..]
+                        .find(|c: char| !c.is_ascii_digit())
+                        .unwrap_or(digits.len() - start);
+                    let run = &digits[start..start + len];
+                    out.push_str(&digits[..start]);
+                    if text[whole.start() + start + len..]
+                        .chars()
+                        .next()
+                        .is_some_and(is_letter)
+                    {
+                        out.push_str(run);
+                    } else {
+                        out.push_str(&self.integer(run));
+                    }
+                    pos = whole.start() + start + len;
+                }
+            }
+        }
+        out.push_str(&text[pos..]);
+        out
+    }
+
+    fn replace(
+        &self,
+        caps: &Captures,
+        before: Option<char>,
+        after: Option<char>,
+    ) -> Option<String> {
+        let sp = self.speller;
+        let field = |name| caps.name(name).map(|m| m.as_str());
+        let num = |name| field(name).and_then(|s| s.parse::<u32>().ok());
+
+        if field("date").is_some() {
+            let (year, month, day) = (num("year")?, num("month")?, num("day")?);
+            if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+                return None;
+            }
+            return Some(sp.date(year, month, day));
+        }
+        if field("time").is_some() {
+            let (hour, minute) = (num("hour")?, num("minute")?);
+            let second = num("second");
+            let period = field("period").map(|p| match p {
+                "a" | "A" => Period::Am,
+                _ => Period::Pm,
+            });
+            let max_hour = if period.is_some() { 12 } else { 24 };
+            if hour > max_hour || minute > 59 || second.is_some_and(|s| s > 59) {
+                return None;
+            }
+            return Some(sp.time(hour, minute, second, period));
+        }
+        if let Some(version) = field("version") {
+            return self.version(version);
+        }
+        if let (Some(symbol), Some(amount)) = (
+            field("symbol").or(field("symbol2")),
+            field("amount").or(field("amount2")),
+        ) {
+            let number = Number::parse(amount, sp.words().decimal)?;
+            return Some(self.money(Unit::currency(symbol)?, &number));
+        }
+        if let Some(percent) = field("percent") {
+            let number = Number::parse(percent, sp.words().decimal)?;
+            return Some(sp.with_unit(self.number(&number), Unit::Percent, number.count()));
+        }
+        if let (Some(ordinal), Some(suffix)) = (field("ordinal"), field("suffix")) {
+            if after.is_some_and(|c| c.is_alphanumeric()) {
+                return None;
+            }
+            let n = ordinal.parse().ok()?;
+            let suffix = suffix.trim_start_matches('.');
+            // "1st" is understood whatever the language
+            return sp.ordinal_suffix(n, suffix).or_else(|| {
+                ["st", "nd", "rd", "th"]
+                    .contains(&suffix)
+                    .then(|| sp.ordinal(n))
+            });
+        }
+        let text = field("number")?;
+        match text.strip_prefix('-') {
+            // A dash between words or numbers is not a sign ("3-5", "A-4")
+            Some(rest) if before.is_some_and(|c| c.is_alphanumeric()) => Some(format!(
+                "-{}",
+                self.number(&Number::parse(rest, sp.words().decimal)?)
+            )),
+            _ => Some(match Number::parse(text, sp.words().decimal) {
+                Some(number) => self.number(&number),
+                // A list ("1,2,3"): each number on its own
+                None => self.list(text),
+            }),
+        }
+    }
+
+    /// Digit runs read as separate numbers, separators kept
+    fn list(&self, text: &str) -> String {
+        let mut out = String::new();
+        let mut rest = text;
+        while let Some(start) = rest.find(|c: char| c.is_ascii_digit()) {
+            out.push_str(&rest[..start]);
+            rest = &rest[start..];
+            let len = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+            out.push_str(&self.integer(&rest[..len]));
+            rest = &rest[len..];
+        }
+        out.push_str(rest);
+        out
+    }
+
+    /// Ordinal written with a period before a capitalized word, in the
+    /// languages that write them so ("3. Mai")
+    fn ordinal_period(&self, digits: &str, rest: &str) -> Option<String> {
+        let n = digits.parse().ok()?;
+        let next = rest.strip_prefix('.')?;
+        let word = next.trim_start_matches([' ', '\u{a0}']);
+        if word.len() == next.len() || !word.starts_with(char::is_uppercase) {
+            return None;
+        }
+        self.speller.ordinal_suffix(n, ".")
+    }
+
+    /// Whole number, or digit by digit when too long or zero-padded
+    fn integer(&self, digits: &str) -> String {
+        match digits.parse::<u64>() {
+            Ok(n) if digits.len() <= 15 && (digits.len() == 1 || !digits.starts_with('0')) => {
+                self.speller.cardinal(n)
+            }
+            _ => self.digits(digits),
+        }
+    }
+
+    fn digits(&self, digits: &str) -> String {
+        digits
+            .chars()
+            .filter_map(|c| c.to_digit(10))
+            .map(|d| self.speller.cardinal(d as u64))
+            .collect::<Vec<_>>()
+            .join(self.speller.words().space)
+    }
+
+    fn number(&self, number: &Number) -> String {
+        let words = self.speller.words();
+        let mut parts = vec![];
+        if number.negative {
+            parts.push(words.minus.to_string());
+        }
+        parts.push(self.integer(&number.int));
+        if let Some(frac) = &number.frac {
+            parts.push(words.point.to_string());
+            parts.push(self.digits(frac));
+        }
+        parts.join(words.space)
+    }
+
+    fn measure(&self, text: &str, unit: &Measure) -> Option<String> {
+        let number = Number::parse(text, self.speller.words().decimal)?;
+        Some(
+            self.speller
+                .measure(self.number(&number), unit, number.count()),
+        )
+    }
+
+    /// "$1,299.99" -> "one thousand two hundred ninety-nine dollars and ninety-nine cents"
+    fn money(&self, currency: Unit, number: &Number) -> String {
+        let sp = self.speller;
+        let words = sp.words();
+        let cents = number
+            .frac
+            .as_deref()
+            .filter(|f| f.len() == 2)
+            .and_then(|f| f.parse::<u64>().ok());
+
+        match (currency.subunit(), cents, number.value()) {
+            (Some(subunit), Some(cents), Some(whole)) => {
+                let main = sp.with_unit(sp.cardinal(whole), currency, Some(whole));
+                match (whole, cents) {
+                    (_, 0) => main,
+                    (0, _) => sp.with_unit(sp.cardinal(cents), subunit, Some(cents)),
+                    _ => {
+                        let sub = sp.with_unit(sp.cardinal(cents), subunit, Some(cents));
+                        [main, words.and.to_string(), sub]
+                            .into_iter()
+                            .filter(|part| !part.is_empty())
+                            .collect::<Vec<_>>()
+                            .join(words.space)
+                    }
+                }
+            }
+            _ => sp.with_unit(self.number(number), currency, number.count()),
+        }
+    }
+
+    /// "v0.3.0" -> "version zero point three point zero"
+    fn version(&self, text: &str) -> Option<String> {
+        let words = self.speller.words();
+        let (prefixed, digits) = match text.strip_prefix(['v', 'V']) {
+            Some(rest) => (true, rest),
+            None => (false, text),
+        };
+        // "1.234.567" is a number where dots group thousands
+        if !prefixed && words.decimal != '.' {
+            if let Some(number) = Number::parse(digits, words.decimal) {
+                return Some(self.number(&number));
+            }
+        }
+
+        let point = format!("{}{}{}", words.space, words.point, words.space);
+        let parts = digits
+            .split('.')
+            .map(|part| self.integer(part))
+            .collect::<Vec<_>>()
+            .join(&point);
+        Some(match prefixed {
+            true => format!("{}{}{}", words.version, words.space, parts),
+            false => parts,
+        })
+    }
+}
+
+/// Letters that glue digits into a word; CJK text has no spaces, so its
+/// characters do not count
+fn is_letter(c: char) -> bool {
+    c.is_alphabetic() && !is_han(c) && !is_hangul(c) && !('\u{3040}'..='\u{30ff}').contains(&c)
+}
+
+fn is_han(c: char) -> bool {
+    matches!(c, '\u{4e00}'..='\u{9fff}' | '\u{3400}'..='\u{4dbf}')
+}
+
+fn is_hangul(c: char) -> bool {
+    matches!(c, '\u{ac00}'..='\u{d7af}' | '\u{1100}'..='\u{11ff}' | '\u{3130}'..='\u{318f}')
+}
+
+/// Pick a word by count: `one` for 1, `many` for everything else
+fn plural(count: Option<u64>, one: &'static str, many: &'static str) -> &'static str {
+    match count {
+        Some(1) => one,
+        _ => many,
+    }
+}
@@ -0,0 +1,479 @@
+//! Russian
+
+use super::{hour24, Builtin, Measure, Period, Speller, Unit, Words};
+
+pub struct Russian;
+
+const ONES: [&str; 20] = [
+    "ноль",
+    "один",
+    "два",
+    "три",
+    "четыре",
+    "пять",
+    "шесть",
+    "семь",
+    "восемь",
+    "девять",
+    "десять",
+    "одиннадцать",
+    "двенадцать",
+    "тринадцать",
+    "четырнадцать",
+    "пятнадцать",
+    "шестнадцать",
+    "семнадцать",
+    "восемнадцать",
+    "девятнадцать",
+];
+
+const TENS: [&str; 10] = [
+    "",
+    "",
+    "двадцать",
+    "тридцать",
+    "сорок",
+    "пятьдесят",
+    "шестьдесят",
+    "семьдесят",
+    "восемьдесят",
+    "девяносто",
+];
+
+const HUNDREDS: [&str; 10] = [
+    "",
+    "сто",
+    "двести",
+    "триста",
+    "четыреста",
+    "пятьсот",
+    "шестьсот",
+    "семьсот",
+    "восемьсот",
+    "девятьсот",
+];
+
+/// Scales: value, forms for one, few (2-4), many, and whether the noun is feminine
+const SCALES: [(u64, [&str; 3], bool); 4] = [
+    (
+        1_000_000_000_000,
+        ["триллион", "триллиона", "триллионов"],
+        false,
+    ),
+    (
+        1_000_000_000,
+        ["миллиард", "миллиарда", "миллиардов"],
+        false,
+    ),
+    (1_000_000, ["миллион", "миллиона", "миллионов"], false),
+    (1_000, ["тысяча", "тысячи", "тысяч"], true),
+];
+
+const ORDINALS: [&str; 20] = [
+    "",
+    "первый",
+    "второй",
+    "третий",
+    "четвёртый",
+    "пятый",
+    "шестой",
+    "седьмой",
+    "восьмой",
+    "девятый",
+    "десятый",
+    "одиннадцатый",
+    "двенадцатый",
+    "тринадцатый",
+    "четырнадцатый",
+    "пятнадцатый",
+    "шестнадцатый",
+    "семнадцатый",
+    "восемнадцатый",
+    "девятнадцатый",
+];
+
+const ORDINAL_TENS: [&str; 10] = [
+    "",
+    "",
+    "двадцатый",
+    "тридцатый",
+    "сороковой",
+    "пятидесятый",
+    "шестидесятый",
+    "семидесятый",
+    "восьмидесятый",
+    "девяностый",
+];
+
+const ORDINAL_HUNDREDS: [&str; 10] = [
+    "",
+    "сотый",
+    "двухсотый",
+    "трёхсотый",
+    "четырёхсотый",
+    "пятисотый",
+    "шестисотый",
+    "семисотый",
+    "восьмисотый",
+    "девятисотый",
+];
+
+/// Genitive stems for "двухтысячный" and the like
+const THOUSAND_STEMS: [&str; 10] = [
+    "",
+    "",
+    "двух",
+    "трёх",
+    "четырёх",
+    "пяти",
+    "шести",
+    "семи",
+    "восьми",
+    "девяти",
+];
+
+/// Months in the genitive: "14 марта"
+const MONTHS: [&str; 12] = [
+    "января",
+    "февраля",
+    "марта",
+    "апреля",
+    "мая",
+    "июня",
+    "июля",
+    "августа",
+    "сентября",
+    "октября",
+    "ноября",
+    "декабря",
+];
+
+const WORDS: Words = Words {
+    point: "запятая",
+    minus: "минус",
+    and: "",
+    version: "версия",
+    space: " ",
+    decimal: ',',
+    dot: "точка",
+    slash: "слэш",
+    at: "собака",
+};
+
+pub const DICTIONARY: Builtin = Builtin {
+    abbreviations: &[
+        ("т.е.", "то есть"),
+        ("т. е.", "то есть"),
+        ("и т.д.", "и так далее"),
+        ("и т. д.", "и так далее"),
+        ("и т.п.", "и тому подобное"),
+        ("и т. п.", "и тому подобное"),
+        ("т.к.", "так как"),
+        ("т. к.", "так как"),
+        ("напр.", "например"),
+        ("см.", "смотри"),
+    ],
+    units: &[
+        ("км", &["километр", "километра", "километров"]),
+        ("м", &["метр", "метра", "метров"]),
+        ("см", &["сантиметр", "сантиметра", "сантиметров"]),
+        ("мм", &["миллиметр", "миллиметра", "миллиметров"]),
+        (
+            "км/ч",
+            &["километр в час", "километра в час", "километров в час"],
+        ),
+        ("кг", &["килограмм", "килограмма", "килограммов"]),
+        ("л", &["литр", "литра", "литров"]),
+        ("ч", &["час", "часа", "часов"]),
+        ("мин", &["минута", "минуты", "минут"]),
+        ("сек", &["секунда", "секунды", "секунд"]),
+        ("мс", &["миллисекунда", "миллисекунды", "миллисекунд"]),
+        ("КБ", &["килобайт", "килобайта", "килобайт"]),
+        ("Кб", &["килобайт", "килобайта", "килобайт"]),
+        ("МБ", &["мегабайт", "мегабайта", "мегабайт"]),
+        ("Мб", &["мегабайт", "мегабайта", "мегабайт"]),
+        ("ГБ", &["гигабайт", "гигабайта", "гигабайт"]),
+        ("Гб", &["гигабайт", "гигабайта", "гигабайт"]),
+        ("ТБ", &["терабайт", "терабайта", "терабайт"]),
+        ("Гц", &["герц", "герца", "герц"]),
+        ("МГц", &["мегагерц", "мегагерца", "мегагерц"]),
+        ("ГГц", &["гигагерц", "гигагерца", "гигагерц"]),
+        ("Вт", &["ватт", "ватта", "ватт"]),
+        ("кВт", &["киловатт", "киловатта", "киловатт"]),
+        ("В", &["вольт", "вольта", "вольт"]),
+        (
+            "°C",
+            &["градус Цельсия", "градуса Цельсия", "градусов Цельсия"],
+        ),
+    ],
+    feminine: &["мин", "сек", "мс"],
+    words: &[],
+    emoji: &[
+        ("😀", "широко улыбается"),
+        ("😂", "смеется до слез"),
+        ("🙂", "слегка улыбается"),
+        ("😊", "улыбается глазами"),
+        ("😉", "подмигивает"),
+        ("😍", "влюблен"),
+        ("🤔", "задумался"),
+        ("😅", "улыбается в холодном поту"),
+        ("😢", "плачет"),
+        ("😭", "рыдает"),
+        ("😡", "злится"),
+        ("😎", "улыбается в темных очках"),
+        ("👍", "нравится"),
+        ("👎", "не нравится"),
+        ("👏", "аплодирует"),
+        ("🙏", "сложенные ладони"),
+        ("👋", "машет рукой"),
+        ("💪", "бицепс"),
+        ("👀", "глаза"),
+        ("❤", "красное сердце"),
+        ("🔥", "огонь"),
+        ("✨", "блестки"),
+        ("🎉", "хлопушка"),
+        ("🚀", "ракета"),
+        ("✅", "галочка"),
+        ("❌", "крестик"),
+        ("⚠", "внимание"),
+        ("💡", "лампочка"),
+        ("🐛", "жук"),
+        ("⭐", "звезда"),
+    ],
+    symbols: &[
+        ("→", "в"),
+        ("->", "в"),
+        ("⇒", "в"),
+        ("=>", "в"),
+        ("←", "из"),
+        ("<-", "из"),
+        ("&&", "и"),
+        ("&", "и"),
+        ("||", "или"),
+        ("≠", "не равно"),
+        ("!=", "не равно"),
+        ("≤", "меньше или равно"),
+        ("<=", "меньше или равно"),
+        ("≥", "больше или равно"),
+        (">=", "больше или равно"),
+        ("±", "плюс минус"),
+        ("×", "умножить на"),
+    ],
+};
+
+/// Form of a noun after a count: один рубль, два рубля, пять рублей
+fn form<T: Copy>(count: Option<u64>, forms: [T; 3]) -> T {
+    let Some(n) = count else {
+        // Fractions take the genitive singular
+        return forms[1];
+    };
+    match (n % 10, n % 100) {
+        (_, 11..=14) => forms[2],
+        (1, _) => forms[0],
+        (2..=4, _) => forms[1],
+        _ => forms[2],
+    }
+}
+
+/// "одна", "две" for feminine nouns
+fn feminine(words: String) -> String {
+    if let Some(stem) = words.strip_suffix("один") {
+        return format!("{}одна", stem);
+    }
+    match words.strip_suffix("два") {
+        Some(stem) if stem.is_empty() || stem.ends_with(' ') => format!("{}две", stem),
+        _ => words,
+    }
+}
+
+fn below_thousand(n: u64) -> String {
+    let (hundreds, tens, ones) = (n / 100, n % 100 / 10, n % 10);
+    let mut words = vec![];
+    if hundreds > 0 {
+        words.push(HUNDREDS[hundreds as usize]);
+    }
+    match n % 100 {
+        0 if hundreds > 0 => {}
+        rest @ 0..=19 => words.push(ONES[rest as usize]),
+        _ => {
+            words.push(TENS[tens as usize]);
+            if ones > 0 {
+                words.push(ONES[ones as usize]);
+            }
+        }
+    }
+    words.join(" ")
+}
+
+/// Case of an ordinal written "-й", "-го", "-е", or "-я"
+#[derive(Clone, Copy)]
+enum Case {
+    Masculine,
+    Genitive,
+    Neuter,
+    Feminine,
+}
+
+/// Decline a masculine ordinal: "третий" -> "третьего", "пятый" -> "пятое"
+fn decline(word: &str, case: Case) -> String {
+    let (stem, endings) = if let Some(stem) = word.strip_suffix("ий") {
+        (stem, ["ий", "ьего", "ье", "ья"])
+    } else if let Some(stem) = word.strip_suffix("ой") {
+        (stem, ["ой", "ого", "ое", "ая"])
+    } else if let Some(stem) = word.strip_suffix("ый") {
+        (stem, ["ый", "ого", "ое", "ая"])
+    } else {
+        return word.to_string();
+    };
+    format!("{}{}", stem, endings[case as usize])
+}
+
+impl Russian {
+    /// Ordinal in a case: only the last word changes ("две тысячи двадцать пятого")
+    fn ordinal_in(&self, n: u64, case: Case) -> String {
+        let (head, last) = if (1..=19).contains(&(n % 100)) {
+            (n - n % 100, ORDINALS[(n % 100) as usize].to_string())
+        } else if !n.is_multiple_of(10) {
+            (n - n % 10, ORDINALS[(n % 10) as usize].to_string())
+        } else if !n.is_multiple_of(100) {
+            (
+                n - n % 100,
+                ORDINAL_TENS[(n % 100 / 10) as usize].to_string(),
+            )
+        } else if !n.is_multiple_of(1000) {
+            (
+                n - n % 1000,
+                ORDINAL_HUNDREDS[(n % 1000 / 100) as usize].to_string(),
+            )
+        } else if !n.is_multiple_of(1_000_000) && (n / 1000) % 1000 < 10 {
+            let thousands = (n / 1000 % 1000) as usize;
+            (
+                n - n % 1_000_000,
+                format!("{}тысячный", THOUSAND_STEMS[thousands]),
+            )
+        } else {
+            return self.cardinal(n);
+        };
+        let last = decline(&last, case);
+        match head {
+            0 => last,
+            _ => format!("{} {}", self.cardinal(head), last),
+        }
+    }
+}
+
+impl Speller for Russian {
+    fn words(&self) -> &Words {
+        &WORDS
+    }
+
+    fn cardinal(&self, n: u64) -> String {
+        if n == 0 {
+            return ONES[0].to_string();
+        }
+        let mut parts = vec![];
+        let mut rest = n;
+        for (scale, forms, female) in SCALES {
+            let count = rest / scale;
+            if count > 0 {
+                let noun = form(Some(count), forms);
+                // "тысяча", not "одна тысяча"
+                if count == 1 && female {
+                    parts.push(noun.to_string());
+                } else if female {
+                    parts.push(format!("{} {}", feminine(below_thousand(count)), noun));
+                } else {
+                    parts.push(format!("{} {}", below_thousand(count), noun));
+                }
+            }
+            rest %= scale;
+        }
+        if rest > 0 {
+            parts.push(below_thousand(rest));
+        }
+        parts.join(" ")
+    }
+
+    fn ordinal(&self, n: u64) -> String {
+        self.ordinal_in(n, Case::Masculine)
+    }
+
+    fn ordinal_suffix(&self, n: u64, suffix: &str) -> Option<String> {
+        let case = match suffix {
+            "-й" | "-ый" | "-ой" => Case::Masculine,
+            "-го" | "-ого" => Case::Genitive,
+            "-е" | "-ое" => Case::Neuter,
+            "-я" | "-ая" => Case::Feminine,
+            _ => return None,
+        };
+        Some(self.ordinal_in(n, case))
+    }
+
+    /// "четырнадцатое марта две тысячи двадцать пятого года"
+    fn date(&self, year: u32, month: u32, day: u32) -> String {
+        format!(
+            "{} {} {} года",
+            self.ordinal_in(day as u64, Case::Neuter),
+            MONTHS[month as usize - 1],
+            self.ordinal_in(year as u64, Case::Genitive)
+        )
+    }
+
+    /// "15:30" -> "пятнадцать часов тридцать минут"
+    fn time(&self, hour: u32, minute: u32, second: Option<u32>, period: Option<Period>) -> String {
+        let hour = hour24(hour, period) as u64;
+        let mut parts = vec![
+            self.cardinal(hour),
+            form(Some(hour), ["час", "часа", "часов"]).to_string(),
+        ];
+        if minute > 0 {
+            let minute = minute as u64;
+            parts.push(feminine(self.cardinal(minute)));
+            parts.push(form(Some(minute), ["минута", "минуты", "минут"]).to_string());
+        }
+        if let Some(second) = second {
+            let second = second as u64;
+            parts.push(feminine(self.cardinal(second)));
+            parts.push(form(Some(second), ["секунда", "секунды", "секунд"]).to_string());
+        }
+        parts.join(" ")
+    }
+
+    fn unit(&self, unit: Unit, count: Option<u64>) -> &'static str {
+        match unit {
+            Unit::Dollar => form(count, ["доллар", "доллара", "долларов"]),
+            Unit::Euro => "евро",
+            Unit::Pound => form(count, ["фунт", "фунта", "фунтов"]),
+            Unit::Yen => form(count, ["иена", "иены", "иен"]),
+            Unit::Won => form(count, ["вона", "воны", "вон"]),
+            Unit::Dong => form(count, ["донг", "донга", "донгов"]),
+            Unit::Ruble => form(count, ["рубль", "рубля", "рублей"]),
+            Unit::Cent | Unit::EuroCent => form(count, ["цент", "цента", "центов"]),
+            Unit::Penny => "пенни",
+            Unit::Kopek => form(count, ["копейка", "копейки", "копеек"]),
+            Unit::Percent => form(count, ["процент", "процента", "процентов"]),
+        }
+    }
+
+    fn plural(&self, count: Option<u64>) -> usize {
+        form(count, [0, 1, 2])
+    }
+
+    /// "одна минута", "две секунды"
+    fn measure(&self, number: String, unit: &Measure, count: Option<u64>) -> String {
+        let number = match (unit.feminine, count) {
+            (true, Some(_)) => feminine(number),
+            _ => number,
+        };
+        format!("{} {}", number, unit.form(self.plural(count)))
+    }
+
+    /// "одна копейка", "две иены"
+    fn with_unit(&self, number: String, unit: Unit, count: Option<u64>) -> String {
+        let number = match (unit, count) {
+            (Unit::Yen | Unit::Won | Unit::Kopek, Some(_)) => feminine(number),
+            _ => number,
+        };
+        format!("{} {}", number, self.unit(unit, count))
+    }
+}
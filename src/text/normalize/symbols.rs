@@ -0,0 +1,229 @@
+//! Emoji, symbols, and web addresses read as words
+//!
+//! Emoji are read by their CLDR short name ("👍" is "thumbs up"), symbols
+//! such as "->" and "&&" by what they mean, and URLs by their domain. Each
+//! can be dropped instead, set in the `[symbols]` config section.
+
+use super::{Builtin, Words};
+use regex_lite::Regex;
+use serde::Deserialize;
+use std::sync::LazyLock;
+
+/// How emoji and symbols are read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SymbolMode {
+    /// By name: "thumbs up", "to"
+    #[default]
+    Describe,
+    /// Left out
+    Drop,
+}
+
+/// How web addresses and email addresses are read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UrlMode {
+    /// Only the domain: "github dot com"
+    #[default]
+    Domain,
+    /// Domain and path: "github dot com slash bibo"
+    Describe,
+    /// Left out
+    Drop,
+}
+
+/// Verbalization rules, set in the `[symbols]` config section
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Rules {
+    pub emoji: SymbolMode,
+    pub symbols: SymbolMode,
+    pub urls: UrlMode,
+}
+
+static ADDRESS: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(concat!(
+        r"(?P<url>\b(?:[a-zA-Z][a-zA-Z0-9+.-]*://|www\.)[^\s<>`]+)",
+        r"|(?P<email>\b[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)+)",
+    ))
+    .expect("address pattern")
+});
+
+/// Host of a URL without "www.", or None for relative and mailto links
+pub(crate) fn domain(url: &str) -> Option<&str> {
+    let (_, rest) = url.split_once("://")?;
+    let host = rest.split(['/', '?', '#']).next()?;
+    let host = host.rsplit('@').next()?;
+    let host = host.split(':').next()?;
+    let host = host.strip_prefix("www.").unwrap_or(host);
+    (!host.is_empty()).then_some(host)
+}
+
+/// Read emoji, symbols, URLs, and email addresses as words
+pub(super) fn verbalize(text: &str, builtin: &Builtin, words: &Words, rules: &Rules) -> String {
+    let text = addresses(text, words, rules.urls);
+    let mut symbols: Vec<&(&str, &str)> = builtin.symbols.iter().collect();
+    symbols.sort_by_key(|(symbol, _)| std::cmp::Reverse(symbol.len()));
+
+    let mut out = String::with_capacity(text.len());
+    let mut last_emoji = None;
+    let mut joined = false;
+    let mut rest = text.as_str();
+
+    while let Some(c) = rest.chars().next() {
+        if let Some((symbol, word)) = symbols.iter().find(|(s, _)| rest.starts_with(*s)) {
+            rest = &rest[symbol.len()..];
+            match rules.symbols {
+                SymbolMode::Describe => push_word(&mut out, word, rest, words.space),
+                SymbolMode::Drop => push_word(&mut out, "", rest, words.space),
+            }
+            last_emoji = None;
+            continue;
+        }
+        rest = &rest[c.len_utf8()..];
+
+        if is_modifier(c) {
+            joined |= c == '\u{200d}';
+            continue;
+        }
+        if !is_emoji(c) {
+            out.push(c);
+            if !c.is_whitespace() {
+                last_emoji = None;
+            }
+            continue;
+        }
+        // Parts of a joined emoji ("👨‍💻") and repeats ("🔥🔥🔥") are read once
+        if std::mem::take(&mut joined) || last_emoji == Some(c) {
+            continue;
+        }
+        last_emoji = Some(c);
+        let name = builtin
+            .emoji
+            .iter()
+            .find(|(emoji, _)| emoji.starts_with(c))
+            .map(|(_, name)| *name);
+        match (rules.emoji, name) {
+            (SymbolMode::Describe, Some(name)) => push_word(&mut out, name, rest, words.space),
+            _ => push_word(&mut out, "", rest, words.space),
+        }
+    }
+    out
+}
+
+/// Put a word in place of a symbol, with spaces where the text needs them
+/// to stay apart ("a->b" is "a to b"); an empty word leaves a single space
+fn push_word(out: &mut String, word: &str, rest: &str, space: &str) {
+    let glued_after = rest.chars().next().is_some_and(|c| c.is_alphanumeric());
+    if word.is_empty() {
+        drop_space(out, rest);
+        if out.chars().next_back().is_some_and(char::is_alphanumeric) && glued_after {
+            out.push_str(space);
+        }
+        return;
+    }
+    let glued_before = out.chars().next_back().is_some_and(|c| !c.is_whitespace());
+    if glued_before {
+        out.push_str(space);
+    }
+    out.push_str(word);
+    if glued_after {
+        out.push_str(space);
+    }
+}
+
+/// Remove the space before something left out when another follows it
+fn drop_space(out: &mut String, rest: &str) {
+    if rest.is_empty() || rest.starts_with([' ', '\t']) {
+        out.truncate(out.trim_end_matches([' ', '\t']).len());
+    }
+}
+
+/// Replace URLs and email addresses
+fn addresses(text: &str, words: &Words, mode: UrlMode) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut pos = 0;
+    while let Some(caps) = ADDRESS.captures_at(text, pos) {
+        let whole = caps.get(0).unwrap();
+        // Sentence punctuation after an address is not part of it
+        let address = whole
+            .as_str()
+            .trim_end_matches(['.', ',', ';', ':', '!', '?', ')', ']', '}', '"', '\'']);
+        out.push_str(&text[pos..whole.start()]);
+        pos = whole.start() + address.len();
+
+        let spoken = match (mode, caps.name("url").is_some()) {
+            (UrlMode::Drop, _) => {
+                drop_space(&mut out, &text[pos..]);
+                continue;
+            }
+            (_, false) => email(address, words),
+            (UrlMode::Domain, true) => host(address).map_or_else(String::new, |h| dotted(h, words)),
+            (UrlMode::Describe, true) => url(address, words),
+        };
+        out.push_str(&spoken);
+    }
+    out.push_str(&text[pos..]);
+    out
+}
+
+/// Host of a URL, also when it starts with "www." and has no scheme
+fn host(url: &str) -> Option<&str> {
+    match url.strip_prefix("www.") {
+        Some(rest) => rest.split(['/', '?', '#', ':']).next(),
+        None => domain(url),
+    }
+}
+
+/// "docs.rs" -> "docs dot rs"
+fn dotted(name: &str, words: &Words) -> String {
+    let dot = format!("{}{}{}", words.space, words.dot, words.space);
+    name.split('.')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(&dot)
+}
+
+/// "https://github.com/ann/bibo?tab=readme" -> "github dot com slash ann slash bibo"
+fn url(url: &str, words: &Words) -> String {
+    let Some(host) = host(url) else {
+        return String::new();
+    };
+    let path = url
+        .split_once(host)
+        .map_or("", |(_, path)| path)
+        .split(['?', '#'])
+        .next()
+        .unwrap_or("");
+    let mut parts = vec![dotted(host, words)];
+    for segment in path.split('/').filter(|s| !s.is_empty()) {
+        parts.push(words.slash.to_string());
+        parts.push(dotted(&segment.replace(['-', '_'], " "), words));
+    }
+    parts.join(words.space)
+}
+
+/// "ann.lee@example.com" -> "ann dot lee at example dot com"
+fn email(address: &str, words: &Words) -> String {
+    let (user, host) = address.split_once('@').unwrap_or((address, ""));
+    [
+        dotted(user, words),
+        words.at.to_string(),
+        dotted(host, words),
+    ]
+    .join(words.space)
+}
+
+fn is_emoji(c: char) -> bool {
+    matches!(c,
+        '\u{1f000}'..='\u{1faff}' | '\u{2600}'..='\u{27bf}' | '\u{2b00}'..='\u{2bff}' | '\u{2300}'..='\u{23ff}')
+}
+
+/// Variation selectors, joiners, skin tones, and keycaps shaping an emoji
+fn is_modifier(c: char) -> bool {
+    matches!(
+        c,
+        '\u{fe0e}' | '\u{fe0f}' | '\u{200d}' | '\u{20e3}' | '\u{1f3fb}'..='\u{1f3ff}'
+    )
+}
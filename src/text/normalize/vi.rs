@@ -0,0 +1,224 @@
+//! Vietnamese
+
+use super::{hour24, Builtin, Period, Speller, Unit, Words};
+
+pub struct Vietnamese;
+
+const DIGITS: [&str; 10] = [
+    "không", "một", "hai", "ba", "bốn", "năm", "sáu", "bảy", "tám", "chín",
+];
+
+const WORDS: Words = Words {
+    point: "phẩy",
+    minus: "âm",
+    and: "",
+    version: "phiên bản",
+    space: " ",
+    decimal: ',',
+    dot: "chấm",
+    slash: "gạch chéo",
+    at: "a còng",
+};
+
+pub const DICTIONARY: Builtin = Builtin {
+    abbreviations: &[
+        ("v.v.", "vân vân"),
+        ("TP.", "thành phố"),
+        ("Tp.", "thành phố"),
+        ("TS.", "tiến sĩ"),
+        ("ThS.", "thạc sĩ"),
+    ],
+    units: &[
+        ("km", &["ki lô mét"]),
+        ("m", &["mét"]),
+        ("cm", &["xăng ti mét"]),
+        ("mm", &["mi li mét"]),
+        ("km/h", &["ki lô mét trên giờ"]),
+        ("kg", &["ki lô gam"]),
+        ("g", &["gam"]),
+        ("l", &["lít"]),
+        ("ml", &["mi li lít"]),
+        ("h", &["giờ"]),
+        ("KB", &["ki lô bai"]),
+        ("MB", &["mê ga bai"]),
+        ("GB", &["gi ga bai"]),
+        ("W", &["oát"]),
+        ("V", &["vôn"]),
+        ("°C", &["độ C"]),
+    ],
+    feminine: &[],
+    words: &[],
+    emoji: &[
+        ("😀", "mặt cười toe toét"),
+        ("😂", "mặt cười ra nước mắt"),
+        ("🙂", "mặt hơi mỉm cười"),
+        ("😊", "mặt cười với mắt cười"),
+        ("😉", "mặt nháy mắt"),
+        ("😍", "mặt cười với mắt hình trái tim"),
+        ("🤔", "mặt suy nghĩ"),
+        ("😅", "mặt cười toát mồ hôi"),
+        ("😢", "mặt khóc"),
+        ("😭", "mặt khóc to"),
+        ("😡", "mặt giận dữ"),
+        ("😎", "mặt cười đeo kính râm"),
+        ("👍", "ngón tay cái hướng lên"),
+        ("👎", "ngón tay cái hướng xuống"),
+        ("👏", "vỗ tay"),
+        ("🙏", "chắp tay"),
+        ("👋", "vẫy tay"),
+        ("💪", "cơ bắp tay"),
+        ("👀", "đôi mắt"),
+        ("❤", "trái tim đỏ"),
+        ("🔥", "lửa"),
+        ("✨", "lấp lánh"),
+        ("🎉", "pháo giấy"),
+        ("🚀", "tên lửa"),
+        ("✅", "dấu kiểm"),
+        ("❌", "dấu nhân"),
+        ("⚠", "cảnh báo"),
+        ("💡", "bóng đèn"),
+        ("🐛", "con bọ"),
+        ("⭐", "ngôi sao"),
+    ],
+    symbols: &[
+        ("→", "đến"),
+        ("->", "đến"),
+        ("⇒", "đến"),
+        ("=>", "đến"),
+        ("←", "từ"),
+        ("<-", "từ"),
+        ("&&", "và"),
+        ("&", "và"),
+        ("||", "hoặc"),
+        ("≠", "khác"),
+        ("!=", "khác"),
+        ("≤", "nhỏ hơn hoặc bằng"),
+        ("<=", "nhỏ hơn hoặc bằng"),
+        ("≥", "lớn hơn hoặc bằng"),
+        (">=", "lớn hơn hoặc bằng"),
+        ("±", "cộng trừ"),
+        ("×", "nhân"),
+    ],
+};
+
+/// "mười lăm", "hai mươi mốt"
+fn below_hundred(n: u64) -> String {
+    let (tens, ones) = ((n / 10) as usize, (n % 10) as usize);
+    let tens_word = match tens {
+        0 => return DIGITS[ones].to_string(),
+        1 => "mười".to_string(),
+        t => format!("{} mươi", DIGITS[t]),
+    };
+    match ones {
+        0 => tens_word,
+        1 if tens > 1 => format!("{} mốt", tens_word),
+        5 => format!("{} lăm", tens_word),
+        o => format!("{} {}", tens_word, DIGITS[o]),
+    }
+}
+
+/// Hundreds; inside a larger number zeros are read: "không trăm lẻ năm"
+fn below_thousand(n: u64, inner: bool) -> String {
+    let (hundreds, rest) = (n / 100, n % 100);
+    let mut words = vec![];
+    if hundreds > 0 || inner {
+        words.push(format!("{} trăm", DIGITS[hundreds as usize]));
+    }
+    match rest {
+        0 => {}
+        1..=9 if !words.is_empty() => words.push(format!("lẻ {}", DIGITS[rest as usize])),
+        _ => words.push(below_hundred(rest)),
+    }
+    words.join(" ")
+}
+
+impl Speller for Vietnamese {
+    fn words(&self) -> &Words {
+        &WORDS
+    }
+
+    fn cardinal(&self, n: u64) -> String {
+        if n == 0 {
+            return DIGITS[0].to_string();
+        }
+        let mut parts = vec![];
+        let mut rest = n;
+        for (scale, name) in [
+            (1_000_000_000, "tỷ"),
+            (1_000_000, "triệu"),
+            (1_000, "nghìn"),
+            (1, ""),
+        ] {
+            let count = rest / scale;
+            rest %= scale;
+            if count == 0 {
+                continue;
+            }
+            let group = if count >= 1000 {
+                self.cardinal(count)
+            } else {
+                below_thousand(count, !parts.is_empty())
+            };
+            parts.push(format!("{} {}", group, name).trim_end().to_string());
+        }
+        parts.join(" ")
+    }
+
+    /// "thứ nhất", "thứ tư", "thứ năm"
+    fn ordinal(&self, n: u64) -> String {
+        match n {
+            1 => "thứ nhất".to_string(),
+            4 => "thứ tư".to_string(),
+            _ => format!("thứ {}", self.cardinal(n)),
+        }
+    }
+
+    /// "ngày mười bốn tháng ba năm hai nghìn không trăm hai mươi lăm"
+    fn date(&self, year: u32, month: u32, day: u32) -> String {
+        let month = match month {
+            4 => "tư".to_string(),
+            m => self.cardinal(m as u64),
+        };
+        format!(
+            "ngày {} tháng {} năm {}",
+            self.cardinal(day as u64),
+            month,
+            self.cardinal(year as u64)
+        )
+    }
+
+    /// "3:45 pm" -> "ba giờ bốn mươi lăm phút chiều"
+    fn time(&self, hour: u32, minute: u32, second: Option<u32>, period: Option<Period>) -> String {
+        let mut spoken = format!("{} giờ", self.cardinal(hour as u64));
+        if minute > 0 {
+            spoken.push_str(&format!(" {} phút", self.cardinal(minute as u64)));
+        }
+        if let Some(second) = second {
+            spoken.push_str(&format!(" {} giây", self.cardinal(second as u64)));
+        }
+        if period.is_some() {
+            spoken.push_str(match hour24(hour, period) {
+                0..=10 => " sáng",
+                11..=13 => " trưa",
+                14..=17 => " chiều",
+                _ => " tối",
+            });
+        }
+        spoken
+    }
+
+    fn unit(&self, unit: Unit, _count: Option<u64>) -> &'static str {
+        match unit {
+            Unit::Dollar => "đô la",
+            Unit::Euro => "euro",
+            Unit::Pound => "bảng Anh",
+            Unit::Yen => "yên",
+            Unit::Won => "won",
+            Unit::Dong => "đồng",
+            Unit::Ruble => "rúp",
+            Unit::Cent | Unit::EuroCent | Unit::Penny => "xu",
+            Unit::Kopek => "kopek",
+            Unit::Percent => "phần trăm",
+        }
+    }
+}
@@ -0,0 +1,235 @@
+//! Chinese (Mandarin, simplified)
+
+use super::{hour24, Builtin, Period, Speller, Unit, Words};
+
+pub struct Chinese;
+
+const DIGITS: [&str; 10] = ["零", "一", "二", "三", "四", "五", "六", "七", "八", "九"];
+
+const WORDS: Words = Words {
+    point: "点",
+    minus: "负",
+    and: "",
+    version: "版本",
+    space: "",
+    decimal: '.',
+    dot: "点",
+    slash: "斜杠",
+    at: "at",
+};
+
+pub const DICTIONARY: Builtin = Builtin {
+    abbreviations: &[],
+    units: &[
+        ("km", &["公里"]),
+        ("m", &["米"]),
+        ("cm", &["厘米"]),
+        ("mm", &["毫米"]),
+        ("km/h", &["公里每小时"]),
+        ("kg", &["公斤"]),
+        ("g", &["克"]),
+        ("mg", &["毫克"]),
+        ("L", &["升"]),
+        ("ml", &["毫升"]),
+        ("KB", &["千字节"]),
+        ("MB", &["兆字节"]),
+        ("GB", &["吉字节"]),
+        ("Hz", &["赫兹"]),
+        ("GHz", &["吉赫兹"]),
+        ("W", &["瓦"]),
+        ("kW", &["千瓦"]),
+        ("V", &["伏"]),
+        ("°C", &["摄氏度"]),
+        ("℃", &["摄氏度"]),
+    ],
+    feminine: &[],
+    words: &[],
+    emoji: &[
+        ("😀", "嘿嘿"),
+        ("😂", "笑哭了"),
+        ("🙂", "呵呵"),
+        ("😊", "羞涩微笑"),
+        ("😉", "眨眼"),
+        ("😍", "花痴"),
+        ("🤔", "想一想"),
+        ("😅", "苦笑"),
+        ("😢", "哭"),
+        ("😭", "放声大哭"),
+        ("😡", "怒火中烧"),
+        ("😎", "墨镜笑脸"),
+        ("👍", "拇指向上"),
+        ("👎", "拇指向下"),
+        ("👏", "鼓掌"),
+        ("🙏", "双手合十"),
+        ("👋", "挥手"),
+        ("💪", "肌肉"),
+        ("👀", "双眼"),
+        ("❤", "红心"),
+        ("🔥", "火焰"),
+        ("✨", "闪亮"),
+        ("🎉", "拉炮彩带"),
+        ("🚀", "火箭"),
+        ("✅", "勾号"),
+        ("❌", "叉号"),
+        ("⚠", "警告"),
+        ("💡", "灯泡"),
+        ("🐛", "虫子"),
+        ("⭐", "星星"),
+    ],
+    symbols: &[
+        ("→", "到"),
+        ("->", "到"),
+        ("⇒", "到"),
+        ("=>", "到"),
+        ("←", "从"),
+        ("<-", "从"),
+        ("&&", "和"),
+        ("&", "和"),
+        ("||", "或"),
+        ("≠", "不等于"),
+        ("!=", "不等于"),
+        ("≤", "小于等于"),
+        ("<=", "小于等于"),
+        ("≥", "大于等于"),
+        (">=", "大于等于"),
+        ("±", "正负"),
+        ("×", "乘"),
+    ],
+};
+
+/// 1 to 9999 with 零 for inner zeros: 一千零五, 一百一十
+fn below_myriad(n: u64) -> String {
+    let mut words = String::new();
+    let mut zero = false;
+    for (value, name) in [(1000, "千"), (100, "百"), (10, "十"), (1, "")] {
+        let digit = n / value % 10;
+        if digit == 0 {
+            zero = !words.is_empty();
+            continue;
+        }
+        if zero {
+            words.push_str(DIGITS[0]);
+            zero = false;
+        }
+        words.push_str(DIGITS[digit as usize]);
+        words.push_str(name);
+    }
+    words
+}
+
+impl Speller for Chinese {
+    fn words(&self) -> &Words {
+        &WORDS
+    }
+
+    /// 一万零五, 十五, 三亿二千万
+    fn cardinal(&self, n: u64) -> String {
+        if n == 0 {
+            return DIGITS[0].to_string();
+        }
+        let mut words = String::new();
+        let mut rest = n;
+        for (scale, name) in [(100_000_000, "亿"), (10_000, "万"), (1, "")] {
+            let count = rest / scale;
+            rest %= scale;
+            if count == 0 {
+                continue;
+            }
+            // A group below a thousand after a higher group needs 零: 一万零五
+            if !words.is_empty() && count < 1000 {
+                words.push_str(DIGITS[0]);
+            }
+            let group = if count >= 10_000 {
+                self.cardinal(count)
+            } else {
+                below_myriad(count)
+            };
+            words.push_str(&group);
+            words.push_str(name);
+        }
+        // 十五, not 一十五
+        match words.strip_prefix("一十") {
+            Some(rest) => format!("十{}", rest),
+            None => words,
+        }
+    }
+
+    fn ordinal(&self, n: u64) -> String {
+        format!("第{}", self.cardinal(n))
+    }
+
+    /// Years digit by digit: "二零二五年三月十四日"
+    fn date(&self, year: u32, month: u32, day: u32) -> String {
+        let year: String = year
+            .to_string()
+            .chars()
+            .filter_map(|c| c.to_digit(10))
+            .map(|d| DIGITS[d as usize])
+            .collect();
+        format!(
+            "{}年{}月{}日",
+            year,
+            self.cardinal(month as u64),
+            self.cardinal(day as u64)
+        )
+    }
+
+    /// "3:45 pm" -> "下午三点四十五分"
+    fn time(&self, hour: u32, minute: u32, second: Option<u32>, period: Option<Period>) -> String {
+        let mut spoken = match period {
+            Some(Period::Am) => "上午".to_string(),
+            Some(Period::Pm) => "下午".to_string(),
+            None => String::new(),
+        };
+        let hour = match period {
+            Some(_) => match hour24(hour, period) % 12 {
+                0 => 12,
+                h => h,
+            },
+            None => hour,
+        };
+        spoken.push_str(&match hour {
+            2 => "两".to_string(),
+            h => self.cardinal(h as u64),
+        });
+        spoken.push('点');
+        if minute > 0 {
+            if minute < 10 {
+                spoken.push_str(DIGITS[0]);
+            }
+            spoken.push_str(&self.cardinal(minute as u64));
+            spoken.push('分');
+        }
+        if let Some(second) = second {
+            spoken.push_str(&self.cardinal(second as u64));
+            spoken.push('秒');
+        }
+        spoken
+    }
+
+    fn unit(&self, unit: Unit, _count: Option<u64>) -> &'static str {
+        match unit {
+            Unit::Dollar => "美元",
+            Unit::Euro => "欧元",
+            Unit::Pound => "英镑",
+            // ¥ is the yuan in Chinese text
+            Unit::Yen => "元",
+            Unit::Won => "韩元",
+            Unit::Dong => "越南盾",
+            Unit::Ruble => "卢布",
+            Unit::Cent => "美分",
+            Unit::EuroCent => "欧分",
+            Unit::Penny => "便士",
+            Unit::Kopek => "戈比",
+            Unit::Percent => "百分之",
+        }
+    }
+
+    /// Units follow the number without a space; percent comes first: 百分之十
+    fn with_unit(&self, number: String, unit: Unit, count: Option<u64>) -> String {
+        match unit {
+            Unit::Percent => format!("{}{}", self.unit(unit, count), number),
+            _ => format!("{}{}", number, self.unit(unit, count)),
+        }
+    }
+}
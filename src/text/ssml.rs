@@ -0,0 +1,570 @@
+//! SSML input - compiles markup into speech segments and silences
+//!
+//! Supported elements: `<speak>`, `<p>`, `<s>`, `<break>`, `<prosody rate>`,
+//! `<emphasis>`, `<say-as>`, `<sub>`, `<phoneme>`, and `<voice>`. Other
+//! elements are read as their text content. sherpa-onnx takes no phoneme
+//! input, so `<phoneme>` speaks its text.
+
+use super::{parse_duration, tag_end, Segment};
+use crate::error::{BiboError, Result};
+
+/// Pause after a `<p>` paragraph
+const PARAGRAPH_PAUSE: f32 = 0.6;
+
+/// Pause after an `<s>` sentence
+const SENTENCE_PAUSE: f32 = 0.3;
+
+/// Longest `<break>` in seconds
+const MAX_BREAK: f32 = 10.0;
+
+/// Parse an SSML document into segments for `TtsEngine::synthesize_segments`
+pub fn parse(input: &str) -> Result<Vec<Segment>> {
+    let mut builder = Builder::default();
+    let mut stack: Vec<Element> = vec![];
+
+    for token in tokenize(input)? {
+        match token {
+            Token::Text(text) => {
+                let text = decode_entities(text)?;
+                match stack.iter_mut().rev().find_map(|e| e.collect.as_mut()) {
+                    Some(inner) => inner.push_str(&text),
+                    None => builder.text(&text, &style(&stack)),
+                }
+            }
+            Token::Open {
+                name,
+                attrs,
+                self_closing,
+            } => {
+                let element = open(&name, &attrs, &mut builder)?;
+                if self_closing {
+                    close(element, &mut stack, &mut builder);
+                } else {
+                    stack.push(element);
+                }
+            }
+            Token::Close(name) => match stack.pop() {
+                Some(element) if element.name == name => close(element, &mut stack, &mut builder),
+                Some(element) => {
+                    return Err(BiboError::InvalidSsml(format!(
+                        "</{}> closes <{}>",
+                        name, element.name
+                    )))
+                }
+                None => return Err(BiboError::InvalidSsml(format!("Unexpected </{}>", name))),
+            },
+        }
+    }
+
+    if let Some(element) = stack.last() {
+        return Err(BiboError::InvalidSsml(format!(
+            "<{}> is never closed",
+            element.name
+        )));
+    }
+    Ok(builder.finish())
+}
+
+/// Voice and rate in effect for a run of text
+#[derive(Debug, Clone, PartialEq)]
+struct Style {
+    voice: Option<String>,
+    speaker: Option<u32>,
+    rate: f32,
+}
+
+/// An open element and what it changes
+#[derive(Debug)]
+struct Element {
+    name: String,
+    voice: Option<String>,
+    speaker: Option<u32>,
+    rate: f32,
+    /// Inner text gathered for `<say-as>` and `<sub>`
+    collect: Option<String>,
+    /// How the gathered text is spoken on close
+    replace: Replace,
+}
+
+#[derive(Debug)]
+enum Replace {
+    None,
+    SayAs {
+        interpret_as: String,
+        format: String,
+    },
+    Alias(String),
+}
+
+fn style(stack: &[Element]) -> Style {
+    let mut style = Style {
+        voice: None,
+        speaker: None,
+        rate: 1.0,
+    };
+    for element in stack {
+        if element.voice.is_some() {
+            style.voice = element.voice.clone();
+            style.speaker = element.speaker;
+        } else if element.speaker.is_some() {
+            // A bare variant switches speaker within the current voice
+            style.speaker = element.speaker;
+        }
+        style.rate *= element.rate;
+    }
+    style.rate = style.rate.clamp(0.25, 4.0);
+    style
+}
+
+fn attr<'a>(attrs: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    attrs
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.as_str())
+}
+
+fn open(name: &str, attrs: &[(String, String)], builder: &mut Builder) -> Result<Element> {
+    let mut element = Element {
+        name: name.to_string(),
+        voice: None,
+        speaker: None,
+        rate: 1.0,
+        collect: None,
+        replace: Replace::None,
+    };
+
+    match name {
+        "break" => builder.silence(break_secs(attrs)?),
+        "p" | "s" => builder.boundary(0.0),
+        "prosody" => {
+            if let Some(rate) = attr(attrs, "rate") {
+                element.rate = parse_rate(rate)?;
+            }
+        }
+        "emphasis" => {
+            element.rate = match attr(attrs, "level").unwrap_or("moderate") {
+                "strong" => 0.85,
+                "reduced" => 1.1,
+                "none" => 1.0,
+                _ => 0.92,
+            }
+        }
+        "voice" => {
+            element.voice = attr(attrs, "name").map(String::from);
+            element.speaker = attr(attrs, "variant").and_then(|v| v.parse().ok());
+        }
+        "say-as" => {
+            let interpret_as = attr(attrs, "interpret-as")
+                .ok_or_else(|| BiboError::InvalidSsml("<say-as> needs interpret-as".to_string()))?;
+            element.collect = Some(String::new());
+            element.replace = Replace::SayAs {
+                interpret_as: interpret_as.to_lowercase(),
+                format: attr(attrs, "format").unwrap_or("").to_lowercase(),
+            };
+        }
+        "sub" => {
+            let alias = attr(attrs, "alias")
+                .ok_or_else(|| BiboError::InvalidSsml("<sub> needs alias".to_string()))?;
+            element.collect = Some(String::new());
+            element.replace = Replace::Alias(decode_entities(alias)?);
+        }
+        _ => {}
+    }
+    Ok(element)
+}
+
+fn close(element: Element, stack: &mut [Element], builder: &mut Builder) {
+    let spoken = match element.replace {
+        Replace::None => None,
+        Replace::Alias(alias) => Some(alias),
+        Replace::SayAs {
+            interpret_as,
+            format,
+        } => {
+            let inner = element.collect.unwrap_or_default();
+            Some(say_as(inner.trim(), &interpret_as, &format))
+        }
+    };
+
+    if let Some(spoken) = spoken {
+        let spoken = format!(" {} ", spoken);
+        // Nested inside another say-as/sub: becomes part of its text
+        match stack.iter_mut().rev().find_map(|e| e.collect.as_mut()) {
+            Some(outer) => outer.push_str(&spoken),
+            None => builder.text(&spoken, &style(stack)),
+        }
+    }
+
+    match element.name.as_str() {
+        "p" => builder.boundary(PARAGRAPH_PAUSE),
+        "s" => builder.boundary(SENTENCE_PAUSE),
+        _ => {}
+    }
+}
+
+/// Pause length of a `<break>`, from `time` or `strength`
+fn break_secs(attrs: &[(String, String)]) -> Result<f32> {
+    if let Some(time) = attr(attrs, "time") {
+        // Bare numbers are milliseconds in SSML
+        let bare = time.trim().parse::<f32>().is_ok();
+        let secs = if bare {
+            parse_duration(&format!("{}ms", time.trim()))
+        } else {
+            parse_duration(time)
+        };
+        let secs =
+            secs.ok_or_else(|| BiboError::InvalidSsml(format!("Invalid break time: {}", time)))?;
+        // SSML caps breaks at 10s; longer ones would allocate huge silences
+        return Ok(secs.min(MAX_BREAK));
+    }
+
+    Ok(match attr(attrs, "strength").unwrap_or("medium") {
+        "none" => 0.0,
+        "x-weak" => 0.1,
+        "weak" => 0.25,
+        "strong" => 0.75,
+        "x-strong" => 1.2,
+        _ => 0.4,
+    })
+}
+
+/// Rate multiplier of a `<prosody rate>`: keyword, "150%", "+20%", or "1.2"
+fn parse_rate(rate: &str) -> Result<f32> {
+    let rate = rate.trim();
+    let keyword = match rate {
+        "x-slow" => Some(0.5),
+        "slow" => Some(0.75),
+        "medium" | "default" => Some(1.0),
+        "fast" => Some(1.25),
+        "x-fast" => Some(1.75),
+        _ => None,
+    };
+    if let Some(rate) = keyword {
+        return Ok(rate);
+    }
+
+    let parsed = match rate.strip_suffix('%') {
+        Some(percent) if percent.starts_with(['+', '-']) => percent
+            .parse::<f32>()
+            .ok()
+            .map(|change| 1.0 + change / 100.0),
+        Some(percent) => percent.parse::<f32>().ok().map(|p| p / 100.0),
+        None => rate.parse::<f32>().ok(),
+    };
+    parsed
+        .filter(|r| r.is_finite() && *r > 0.0)
+        .ok_or_else(|| BiboError::InvalidSsml(format!("Invalid prosody rate: {}", rate)))
+}
+
+/// Spoken form of `<say-as>` content
+fn say_as(text: &str, interpret_as: &str, format: &str) -> String {
+    match interpret_as {
+        "characters" | "spell-out" | "verbatim" => spell(text),
+        "digits" => spell(&text.replace(|c: char| !c.is_ascii_digit(), "")),
+        "cardinal" | "number" => {
+            let cleaned = text.replace([',', '_', ' '], "");
+            if cleaned.parse::<f64>().is_ok() {
+                cleaned
+            } else {
+                text.to_string()
+            }
+        }
+        "ordinal" => ordinal(text),
+        "date" => date(text, format).unwrap_or_else(|| text.to_string()),
+        "telephone" => telephone(text),
+        _ => text.to_string(),
+    }
+}
+
+/// "ABC" -> "A B C"
+fn spell(text: &str) -> String {
+    text.chars()
+        .filter(|c| !c.is_whitespace())
+        .map(String::from)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// "3" -> "3rd"
+fn ordinal(text: &str) -> String {
+    let Ok(n) = text.trim_end_matches('.').parse::<u64>() else {
+        return text.to_string();
+    };
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", n, suffix)
+}
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// Read a date in the given field order ("ymd", "mdy", "dmy", "md", ...)
+fn date(text: &str, format: &str) -> Option<String> {
+    let parts: Vec<u32> = text
+        .split(['-', '/', '.', ' '])
+        .filter(|p| !p.is_empty())
+        .map(|p| p.parse().ok())
+        .collect::<Option<_>>()?;
+
+    // ISO dates are the default; otherwise fields follow their count
+    let format = match format {
+        "" if parts.first().is_some_and(|y| *y > 31) => "ymd",
+        "" => "mdy",
+        format => format,
+    };
+    if format.len() != parts.len() {
+        return None;
+    }
+
+    let (mut year, mut month, mut day) = (None, None, None);
+    for (field, value) in format.chars().zip(parts) {
+        match field {
+            'y' => year = Some(value),
+            'm' if (1..=12).contains(&value) => month = Some(value),
+            'd' if (1..=31).contains(&value) => day = Some(value),
+            _ => return None,
+        }
+    }
+
+    let month = month.map(|m| MONTHS[m as usize - 1]);
+    Some(match (year, month, day) {
+        (Some(y), Some(m), Some(d)) => format!("{} {}, {}", m, ordinal(&d.to_string()), y),
+        (None, Some(m), Some(d)) => format!("{} {}", m, ordinal(&d.to_string())),
+        (Some(y), Some(m), None) => format!("{} {}", m, y),
+        (None, Some(m), None) => m.to_string(),
+        (None, None, Some(d)) => ordinal(&d.to_string()),
+        (Some(y), None, None) => y.to_string(),
+        _ => return None,
+    })
+}
+
+/// Digit by digit, pausing between groups: "555-1234" -> "5 5 5, 1 2 3 4"
+fn telephone(text: &str) -> String {
+    let mut groups = vec![];
+    if text.trim_start().starts_with('+') {
+        groups.push("plus".to_string());
+    }
+    for group in text.split(|c: char| !c.is_ascii_digit()) {
+        if !group.is_empty() {
+            groups.push(spell(group));
+        }
+    }
+    groups.join(", ")
+}
+
+/// Accumulates text runs and pauses into segments
+#[derive(Default)]
+struct Builder {
+    segments: Vec<Segment>,
+    text: String,
+    style: Option<Style>,
+    /// Structural pause owed before the next speech
+    pending: f32,
+}
+
+impl Builder {
+    fn text(&mut self, text: &str, style: &Style) {
+        if text.trim().is_empty() {
+            if !self.text.is_empty() {
+                self.text.push(' ');
+            }
+            return;
+        }
+        if self.pending > 0.0 {
+            let pause = std::mem::take(&mut self.pending);
+            self.silence(pause);
+        }
+        if self.style.as_ref() != Some(style) {
+            self.flush();
+            self.style = Some(style.clone());
+        }
+        self.text.push_str(text);
+    }
+
+    /// Pause spoken only if more speech follows
+    fn boundary(&mut self, secs: f32) {
+        self.flush();
+        if !self.segments.is_empty() {
+            self.pending = self.pending.max(secs);
+        }
+    }
+
+    fn silence(&mut self, secs: f32) {
+        self.flush();
+        let secs = secs.max(std::mem::take(&mut self.pending));
+        if secs <= 0.0 {
+            return;
+        }
+        match self.segments.last_mut() {
+            Some(Segment::Silence(last)) => *last = last.max(secs),
+            _ => self.segments.push(Segment::Silence(secs)),
+        }
+    }
+
+    fn flush(&mut self) {
+        let mut text = String::with_capacity(self.text.len());
+        for word in self.text.split_whitespace() {
+            // Tags leave gaps before punctuation: "<sub ...>WWW</sub>, and"
+            let attached = word.starts_with([',', '.', ';', ':', '!', '?']);
+            if !text.is_empty() && !attached {
+                text.push(' ');
+            }
+            text.push_str(word);
+        }
+        self.text.clear();
+        if text.is_empty() {
+            return;
+        }
+        let style = self.style.clone().unwrap_or(Style {
+            voice: None,
+            speaker: None,
+            rate: 1.0,
+        });
+        self.segments.push(Segment::Speech {
+            text,
+            voice: style.voice,
+            speaker: style.speaker,
+            rate: style.rate,
+        });
+    }
+
+    fn finish(mut self) -> Vec<Segment> {
+        self.flush();
+        self.segments
+    }
+}
+
+enum Token<'a> {
+    Text(&'a str),
+    Open {
+        name: String,
+        attrs: Vec<(String, String)>,
+        self_closing: bool,
+    },
+    Close(String),
+}
+
+/// Split markup into text and tags, skipping comments and declarations
+fn tokenize(input: &str) -> Result<Vec<Token<'_>>> {
+    let mut tokens = vec![];
+    let mut rest = input;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            tokens.push(Token::Text(rest));
+            break;
+        };
+        if start > 0 {
+            tokens.push(Token::Text(&rest[..start]));
+        }
+        rest = &rest[start..];
+
+        let skip_to = |end: &str| {
+            rest.find(end)
+                .map(|i| i + end.len())
+                .ok_or_else(|| BiboError::InvalidSsml(format!("Missing {}", end)))
+        };
+        if rest.starts_with("<!--") {
+            rest = &rest[skip_to("-->")?..];
+            continue;
+        }
+        if rest.starts_with("<?") || rest.starts_with("<!") {
+            rest = &rest[skip_to(">")?..];
+            continue;
+        }
+
+        let end = tag_end(rest).ok_or_else(|| BiboError::InvalidSsml("Missing >".to_string()))?;
+        let tag = rest[1..end].trim();
+        rest = &rest[end + 1..];
+
+        if let Some(name) = tag.strip_prefix('/') {
+            tokens.push(Token::Close(name.trim().to_lowercase()));
+            continue;
+        }
+        let (tag, self_closing) = match tag.strip_suffix('/') {
+            Some(tag) => (tag.trim_end(), true),
+            None => (tag, false),
+        };
+        let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+        let name = tag[..name_end].to_lowercase();
+        if name.is_empty() {
+            return Err(BiboError::InvalidSsml("Empty tag".to_string()));
+        }
+        tokens.push(Token::Open {
+            name,
+            attrs: parse_attrs(&tag[name_end..])?,
+            self_closing,
+        });
+    }
+
+    Ok(tokens)
+}
+
+/// `key="value" other='value'` pairs
+fn parse_attrs(mut rest: &str) -> Result<Vec<(String, String)>> {
+    let mut attrs = vec![];
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            return Ok(attrs);
+        }
+        let invalid = || BiboError::InvalidSsml(format!("Invalid attribute: {}", rest));
+        let eq = rest.find('=').ok_or_else(invalid)?;
+        let key = rest[..eq].trim().to_lowercase();
+        let value = rest[eq + 1..].trim_start();
+        let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'');
+        let quote = quote.ok_or_else(invalid)?;
+        let close = value[1..].find(quote).ok_or_else(invalid)?;
+        attrs.push((key, value[1..close + 1].to_string()));
+        rest = &value[close + 2..];
+    }
+}
+
+/// Decode XML entities (`&amp;`, `&#39;`, `&#x2014;`, ...)
+fn decode_entities(text: &str) -> Result<String> {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = rest
+            .find(';')
+            .ok_or_else(|| BiboError::InvalidSsml(format!("Unterminated entity: {}", rest)))?;
+        let entity = &rest[1..end];
+        let c = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| entity.strip_prefix('#').map(str::parse))
+                .and_then(|code| code.ok())
+                .and_then(char::from_u32),
+        };
+        let c = c.ok_or_else(|| BiboError::InvalidSsml(format!("Unknown entity: &{};", entity)))?;
+        decoded.push(c);
+        rest = &rest[end + 1..];
+    }
+    decoded.push_str(rest);
+    Ok(decoded)
+}
@@ -0,0 +1,204 @@
+//! Content-addressed synthesis cache
+//!
+//! Synthesized audio is stored as WAV under the bibo cache dir, keyed by a
+//! SHA-256 of the normalized text and every parameter that affects the
+//! output. Hits refresh the file mtime; the oldest files are evicted once
+//! the cache grows past its size limit.
+
+use crate::audio::AudioBuffer;
+use crate::error::{BiboError, Result};
+use crate::tts::sherpa::SHERPA_VERSION;
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tempfile::NamedTempFile;
+
+/// Bump when the key layout or stored format changes
+const CACHE_VERSION: u32 = 1;
+
+/// Default size limit in megabytes
+pub const DEFAULT_MAX_MB: u64 = 256;
+
+/// Everything that determines the synthesized audio
+pub struct CacheKey<'a> {
+    pub text: &'a str,
+    pub voice_id: &'a str,
+    /// Fingerprint of the model file (see `model_fingerprint`)
+    pub model: &'a str,
+    pub speaker: u32,
+    pub length_scale: f32,
+}
+
+impl CacheKey<'_> {
+    /// Hex digest used as the cache file name
+    pub fn digest(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(format!(
+            "v{}\0{}\0{}\0{}\0{}\0{:.2}\0",
+            CACHE_VERSION,
+            SHERPA_VERSION,
+            self.voice_id.to_lowercase(),
+            self.model,
+            self.speaker,
+            self.length_scale
+        ));
+        hasher.update(normalize_text(self.text));
+
+        hasher
+            .finalize()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+}
+
+/// Collapse whitespace so trivially different inputs share an entry
+pub fn normalize_text(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Cheap model fingerprint from file size and modification time.
+/// Hashing a multi-hundred-MB model on every call would cost more than synthesis.
+pub fn model_fingerprint(model_path: &Path) -> String {
+    let Ok(meta) = fs::metadata(model_path) else {
+        return String::new();
+    };
+    let mtime = meta
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    format!("{}:{}", meta.len(), mtime)
+}
+
+/// Cache usage summary
+pub struct CacheStats {
+    pub entries: usize,
+    pub total_bytes: u64,
+    pub max_bytes: u64,
+    pub dir: PathBuf,
+}
+
+/// On-disk audio cache with LRU eviction
+#[derive(Debug, Clone)]
+pub struct AudioCache {
+    dir: PathBuf,
+    max_bytes: u64,
+}
+
+impl AudioCache {
+    pub fn new(dir: PathBuf, max_bytes: u64) -> Self {
+        Self { dir, max_bytes }
+    }
+
+    /// Default cache location, size limit from BIBO_CACHE_MAX_MB
+    pub fn default_location() -> Self {
+        let max_mb = std::env::var("BIBO_CACHE_MAX_MB")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or(DEFAULT_MAX_MB);
+        Self::new(Self::default_dir(), max_mb.saturating_mul(1024 * 1024))
+    }
+
+    /// Get cache directory
+    pub fn default_dir() -> PathBuf {
+        dirs::cache_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("bibo")
+            .join("audio")
+    }
+
+    fn entry_path(&self, key: &CacheKey) -> PathBuf {
+        self.dir.join(format!("{}.wav", key.digest()))
+    }
+
+    /// Look up cached audio, refreshing its LRU timestamp on a hit
+    pub fn get(&self, key: &CacheKey) -> Option<AudioBuffer> {
+        let path = self.entry_path(key);
+        let audio = AudioBuffer::load(&path).ok()?;
+
+        if let Ok(file) = File::options().write(true).open(&path) {
+            let _ = file.set_modified(SystemTime::now());
+        }
+
+        Some(audio)
+    }
+
+    /// Store audio and evict old entries if over the size limit
+    pub fn put(&self, key: &CacheKey, audio: &AudioBuffer) -> Result<()> {
+        if self.max_bytes == 0 {
+            return Ok(());
+        }
+
+        fs::create_dir_all(&self.dir)
+            .map_err(|e| BiboError::Other(format!("Failed to create cache dir: {}", e)))?;
+
+        // Write to a unique temp file first so readers never see a partial
+        // file and concurrent writers of the same key never interleave
+        let temp = NamedTempFile::new_in(&self.dir)
+            .map_err(|e| BiboError::Other(format!("Failed to store cache entry: {}", e)))?;
+        audio.save(temp.path())?;
+        temp.persist(self.entry_path(key))
+            .map_err(|e| BiboError::Other(format!("Failed to store cache entry: {}", e)))?;
+
+        self.evict();
+        Ok(())
+    }
+
+    /// Cached files with size and last-used time
+    fn entries(&self) -> Vec<(PathBuf, u64, SystemTime)> {
+        let Ok(dir) = fs::read_dir(&self.dir) else {
+            return vec![];
+        };
+
+        dir.filter_map(|e| e.ok())
+            .filter(|e| e.path().extension().is_some_and(|ext| ext == "wav"))
+            .filter_map(|e| {
+                let meta = e.metadata().ok()?;
+                Some((e.path(), meta.len(), meta.modified().ok()?))
+            })
+            .collect()
+    }
+
+    /// Remove least recently used entries until under the size limit
+    pub fn evict(&self) {
+        let mut entries = self.entries();
+        let mut total: u64 = entries.iter().map(|(_, size, _)| size).sum();
+        if total <= self.max_bytes {
+            return;
+        }
+
+        entries.sort_by_key(|(_, _, used)| *used);
+        for (path, size, _) in entries {
+            if total <= self.max_bytes {
+                break;
+            }
+            if fs::remove_file(&path).is_ok() {
+                total -= size;
+            }
+        }
+    }
+
+    /// Usage summary
+    pub fn stats(&self) -> CacheStats {
+        let entries = self.entries();
+        CacheStats {
+            entries: entries.len(),
+            total_bytes: entries.iter().map(|(_, size, _)| size).sum(),
+            max_bytes: self.max_bytes,
+            dir: self.dir.clone(),
+        }
+    }
+
+    /// Remove all cached audio, returning the number of entries removed
+    pub fn clear(&self) -> Result<usize> {
+        let entries = self.entries();
+        for (path, _, _) in &entries {
+            fs::remove_file(path)
+                .map_err(|e| BiboError::Other(format!("Failed to remove cache entry: {}", e)))?;
+        }
+        Ok(entries.len())
+    }
+}
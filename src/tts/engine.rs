@@ -3,17 +3,41 @@
 //! Zero Python dependency - uses native sherpa-onnx binary
 //! Supports arm64 and x86_64 via universal binary
 
+use crate::audio::AudioBuffer;
 use crate::error::{BiboError, Result};
+use crate::text::normalize::{self, Normalization};
+use crate::text::Segment;
+use crate::tts::cache::{model_fingerprint, AudioCache, CacheKey};
+use crate::tts::pronunciation::{Pronunciations, Respelling};
 use crate::tts::sherpa::{find_sherpa_tts, sherpa_env};
 use crate::tts::voice::VoiceCatalog;
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+/// Rendered segment awaiting the output sample rate
+enum Part {
+    Audio(AudioBuffer),
+    Silence(f32),
+}
+
 /// TTS Engine wrapper (calls sherpa-onnx binary)
+#[derive(Debug, Clone)]
 pub struct TtsEngine {
     model_dir: PathBuf,
     onnx_file: String,
     voice_id: String,
+    /// Voice locale, used to spell out numbers
+    lang: &'static str,
+    /// Merged lexicon with the user's pronunciations, replacing lexicon.txt
+    lexicon: Option<PathBuf>,
+    /// User pronunciations the model's lexicon cannot take
+    respelling: Respelling,
+    speaker_id: u32,
+    model_fingerprint: String,
+    cache: Option<AudioCache>,
+    /// Dictionaries and symbol rules applied before synthesis
+    normalization: Normalization,
 }
 
 impl TtsEngine {
@@ -38,13 +62,83 @@ impl TtsEngine {
             )));
         }
 
+        // User pronunciations go into a lexicon copy when the model has one
+        let pronunciations = Pronunciations::load(voice.lang)?;
+        let mut fingerprint = model_fingerprint(&model_path);
+        let lexicon_path = model_dir.join("lexicon.txt");
+        let (lexicon, respelling) = if pronunciations.is_empty() || !lexicon_path.exists() {
+            (None, pronunciations.respelling())
+        } else {
+            let (lexicon, respelling) =
+                pronunciations.lexicon_overlay(&lexicon_path, &model_dir.join("tokens.txt"))?;
+            if let Some(name) = lexicon.file_name() {
+                fingerprint.push_str(&format!(":{}", name.to_string_lossy()));
+            }
+            (Some(lexicon), respelling)
+        };
+
         Ok(Self {
             model_dir,
             onnx_file: voice.onnx_file.to_string(),
             voice_id: voice_id.to_string(),
+            lang: voice.lang,
+            lexicon,
+            respelling,
+            speaker_id: 0,
+            model_fingerprint: fingerprint,
+            cache: Some(AudioCache::default_location()),
+            normalization: Normalization::default(),
         })
     }
 
+    /// Replace the synthesis cache (None disables caching)
+    pub fn set_cache(&mut self, cache: Option<AudioCache>) {
+        self.cache = cache;
+    }
+
+    /// Synthesis cache in use (None when caching is off)
+    pub fn cache(&self) -> Option<&AudioCache> {
+        self.cache.as_ref()
+    }
+
+    /// Engine for another voice with this engine's cache and normalization
+    pub fn for_voice(&self, voice_id: &str) -> Result<Self> {
+        let mut engine = TtsEngine::new(voice_id)?;
+        engine.set_cache(self.cache.clone());
+        engine.set_normalization(self.normalization.clone());
+        Ok(engine)
+    }
+
+    /// Replace the dictionaries and symbol rules (built-in by default)
+    pub fn set_normalization(&mut self, normalization: Normalization) {
+        self.normalization = normalization;
+    }
+
+    /// Dictionaries and symbol rules in use
+    pub fn normalization(&self) -> &Normalization {
+        &self.normalization
+    }
+
+    /// Select a speaker for multi-speaker models (e.g. aishell3)
+    pub fn set_speaker(&mut self, speaker_id: u32) {
+        self.speaker_id = speaker_id;
+    }
+
+    /// Currently selected speaker
+    pub fn speaker(&self) -> u32 {
+        self.speaker_id
+    }
+
+    /// Voice ID this engine was created for
+    pub fn voice_id(&self) -> &str {
+        &self.voice_id
+    }
+
+    /// Text as it is spoken: numbers, dates, times, and money in words
+    pub fn normalize(&self, text: &str) -> String {
+        normalize::normalize_with(text, self.lang, &self.normalization)
+    }
+
     /// Build sherpa-onnx command with model arguments
     fn build_command(&self, sherpa_path: &PathBuf) -> Result<Command> {
         let mut cmd = Command::new(sherpa_path);
@@ -67,8 +161,10 @@ impl TtsEngine {
             cmd.arg(format!("--vits-tokens={}", tokens_path.display()));
         }
 
-        // Optional: lexicon
-        if lexicon_path.exists() {
+        // Optional: lexicon, or its copy with user pronunciations
+        if let Some(lexicon) = &self.lexicon {
+            cmd.arg(format!("--vits-lexicon={}", lexicon.display()));
+        } else if lexicon_path.exists() {
             cmd.arg(format!("--vits-lexicon={}", lexicon_path.display()));
         }
 
@@ -99,6 +195,7 @@ impl TtsEngine {
         // Output file and speed (length_scale: larger = slower)
         cmd.arg(format!("--output-filename={}", output_path));
         cmd.arg(format!("--vits-length-scale={:.2}", length_scale));
+        cmd.arg(format!("--sid={}", self.speaker_id));
 
         // Text as positional argument
         cmd.arg(text);
@@ -118,29 +215,119 @@ impl TtsEngine {
         Ok(())
     }
 
-    /// Synthesize text and return audio samples (reads from temp file)
+    /// Synthesize text and return audio samples
     pub fn synthesize(&self, text: &str, length_scale: f32) -> Result<Vec<i16>> {
+        self.synthesize_audio(text, length_scale)
+            .map(|audio| audio.samples)
+    }
+
+    /// Synthesize text into a buffer carrying the model's real sample rate
+    ///
+    /// The text is normalized first (see [`TtsEngine::normalize`]), and user
+    /// pronunciations the model's lexicon lacks are respelled. Served from
+    /// the synthesis cache when possible, otherwise rendered by sherpa-onnx
+    /// into a temp file and stored in the cache.
+    pub fn synthesize_audio(&self, text: &str, length_scale: f32) -> Result<AudioBuffer> {
+        let text = &self.respelling.apply(&self.normalize(text));
+        let key = CacheKey {
+            text,
+            voice_id: &self.voice_id,
+            model: &self.model_fingerprint,
+            speaker: self.speaker_id,
+            length_scale,
+        };
+
+        if let Some(audio) = self.cache.as_ref().and_then(|c| c.get(&key)) {
+            return Ok(audio);
+        }
+
         let temp_file = tempfile::NamedTempFile::new()
             .map_err(|e| BiboError::Other(format!("Failed to create temp file: {}", e)))?;
 
-        let temp_path = temp_file.path().to_str().unwrap();
+        let temp_path = temp_file.path().to_str().ok_or_else(|| {
+            BiboError::Other(format!(
+                "Temp path is not valid UTF-8: {}",
+                temp_file.path().display()
+            ))
+        })?;
         let wav_path = format!("{}.wav", temp_path);
 
         self.synthesize_to_file(text, length_scale, &wav_path)?;
 
         // Read WAV samples
-        let reader = hound::WavReader::open(&wav_path)
-            .map_err(|e| BiboError::Other(format!("Failed to read WAV: {}", e)))?;
-
-        let samples: Vec<i16> = reader
-            .into_samples::<i16>()
-            .filter_map(|s| s.ok())
-            .collect();
+        let audio = AudioBuffer::load(Path::new(&wav_path));
 
         // Clean up
         let _ = std::fs::remove_file(&wav_path);
 
-        Ok(samples)
+        let audio = audio?;
+        if let Some(cache) = &self.cache {
+            // A full disk or read-only cache dir should not fail synthesis
+            let _ = cache.put(&key, &audio);
+        }
+
+        Ok(audio)
+    }
+
+    /// Synthesize a script of speech and silences into one buffer
+    ///
+    /// Segments naming another voice get their own engine, sharing this
+    /// engine's cache setting; their audio is resampled to the rate of the
+    /// first speech segment.
+    pub fn synthesize_segments(
+        &self,
+        segments: &[Segment],
+        length_scale: f32,
+    ) -> Result<AudioBuffer> {
+        let mut engines: HashMap<(String, u32), TtsEngine> = HashMap::new();
+        let mut parts = vec![];
+
+        for segment in segments {
+            let part = match segment {
+                Segment::Silence(secs) => Part::Silence(*secs),
+                Segment::Speech {
+                    text,
+                    voice,
+                    speaker,
+                    rate,
+                } => {
+                    let voice = voice.as_deref().unwrap_or(&self.voice_id);
+                    let speaker = speaker.unwrap_or(self.speaker_id);
+                    let scale = length_scale / rate;
+                    let audio = if voice.eq_ignore_ascii_case(&self.voice_id)
+                        && speaker == self.speaker_id
+                    {
+                        self.synthesize_audio(text, scale)?
+                    } else {
+                        let key = (voice.to_lowercase(), speaker);
+                        if !engines.contains_key(&key) {
+                            let mut engine = self.for_voice(voice)?;
+                            engine.set_speaker(speaker);
+                            engines.insert(key.clone(), engine);
+                        }
+                        engines[&key].synthesize_audio(text, scale)?
+                    };
+                    Part::Audio(audio)
+                }
+            };
+            parts.push(part);
+        }
+
+        let sample_rate = parts
+            .iter()
+            .find_map(|part| match part {
+                Part::Audio(audio) => Some(audio.sample_rate),
+                Part::Silence(_) => None,
+            })
+            .unwrap_or_else(|| self.sample_rate());
+        let mut output = AudioBuffer::new(vec![], sample_rate);
+        for part in parts {
+            match part {
+                Part::Audio(audio) => output.append(&audio.resample(sample_rate)),
+                Part::Silence(secs) => output.append(&AudioBuffer::silence(secs, sample_rate)),
+            }
+        }
+        Ok(output)
     }
 
     /// Get sample rate for audio playback
@@ -3,10 +3,15 @@
 //! Zero Python dependency - uses native sherpa-onnx binary
 //! Universal binary support for arm64 and x86_64
 
+pub mod cache;
 pub mod engine;
+pub mod pronunciation;
 pub mod sherpa;
+pub mod speed;
 pub mod voice;
 
+pub use cache::AudioCache;
 pub use engine::TtsEngine;
 pub use sherpa::{find_sherpa_tts, sherpa_available, sherpa_download_url, SHERPA_VERSION};
+pub use speed::{parse_length_scale, Speed};
 pub use voice::{Voice, VoiceCatalog, VOICE_CATALOG};
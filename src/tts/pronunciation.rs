@@ -0,0 +1,277 @@
+//! User pronunciations from `pronunciations.toml` next to `config.toml`
+//!
+//! One table per language maps words to a respelling, phonemes, or both:
+//!
+//! ```toml
+//! [en]
+//! bibo = "bee bo"
+//! sherpa = { respell = "sher pa", phonemes = "SH ER1 P AH0" }
+//!
+//! [zh]
+//! bibo = { phonemes = "b i1 b o1" }
+//! ```
+//!
+//! Models with a `lexicon.txt` get a merged copy with these words added, so
+//! the model files stay untouched. A respelling is turned into phonemes with
+//! the model's own lexicon. Other voices (espeak-based piper) read the
+//! respelling in place of the word.
+
+use crate::config::Config;
+use crate::error::{BiboError, Result};
+use regex_lite::Regex;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tempfile::NamedTempFile;
+
+/// How to say one word
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+enum Entry {
+    Respell(String),
+    Full {
+        respell: Option<String>,
+        phonemes: Option<String>,
+    },
+}
+
+impl Entry {
+    fn respell(&self) -> Option<&str> {
+        match self {
+            Entry::Respell(text) => Some(text),
+            Entry::Full { respell, .. } => respell.as_deref(),
+        }
+    }
+
+    fn phonemes(&self) -> Option<&str> {
+        match self {
+            Entry::Respell(_) => None,
+            Entry::Full { phonemes, .. } => phonemes.as_deref(),
+        }
+    }
+}
+
+/// Pronunciations that apply to one voice
+#[derive(Debug, Clone, Default)]
+pub struct Pronunciations {
+    entries: Vec<(String, Entry)>,
+}
+
+/// Words respelled in the text before synthesis
+#[derive(Debug, Clone, Default)]
+pub struct Respelling {
+    words: HashMap<String, String>,
+    pattern: Option<Regex>,
+}
+
+impl Pronunciations {
+    /// Entries for a voice locale ("en_US"; "zh_en" takes both tables)
+    pub fn load(locale: &str) -> Result<Self> {
+        let path = Config::pronunciation_path();
+        let Ok(content) = fs::read_to_string(&path) else {
+            return Ok(Self::default());
+        };
+        let tables: HashMap<String, HashMap<String, Entry>> = toml::from_str(&content)
+            .map_err(|e| BiboError::ConfigError(format!("{}: {}", path.display(), e.message())))?;
+        Ok(Self::for_locale(tables, locale))
+    }
+
+    fn for_locale(mut tables: HashMap<String, HashMap<String, Entry>>, locale: &str) -> Self {
+        let mut entries = vec![];
+        for lang in locale.split(['_', '-']).map(str::to_lowercase) {
+            if let Some(table) = tables.remove(&lang) {
+                entries.extend(table);
+            }
+        }
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        Self { entries }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Respell every word that has a respelling (voices without a lexicon)
+    pub fn respelling(&self) -> Respelling {
+        Respelling::new(
+            self.entries
+                .iter()
+                .filter_map(|(word, entry)| Some((word.clone(), entry.respell()?.to_string()))),
+        )
+    }
+
+    /// Merged copy of a model's lexicon with these words added
+    ///
+    /// Returns its path and the words that still need respelling because
+    /// neither phonemes were given nor could the respelling be looked up.
+    /// The copy lives in the bibo cache dir, named by a digest of its
+    /// contents, and is written only once.
+    pub fn lexicon_overlay(&self, lexicon: &Path, tokens: &Path) -> Result<(PathBuf, Respelling)> {
+        let base = fs::read_to_string(lexicon)
+            .map_err(|e| BiboError::ConfigError(format!("{}: {}", lexicon.display(), e)))?;
+        let known: HashMap<&str, &str> = base
+            .lines()
+            .filter_map(|line| line.trim().split_once(char::is_whitespace))
+            .fold(HashMap::new(), |mut map, (word, phones)| {
+                map.entry(word).or_insert(phones.trim());
+                map
+            });
+        let token_set: Option<HashSet<String>> = fs::read_to_string(tokens).ok().map(|text| {
+            text.lines()
+                .filter_map(|line| line.rsplit_once(' ').map(|(token, _)| token.to_string()))
+                .collect()
+        });
+
+        let mut added = vec![];
+        let mut respell = vec![];
+        for (word, entry) in &self.entries {
+            let key = word.to_lowercase();
+            let phones = match entry.phonemes() {
+                Some(phonemes) => {
+                    if let Some(set) = &token_set {
+                        if let Some(unknown) =
+                            phonemes.split_whitespace().find(|p| !set.contains(*p))
+                        {
+                            return Err(BiboError::ConfigError(format!(
+                                "Pronunciation of '{}': phoneme '{}' is not in {}",
+                                word,
+                                unknown,
+                                tokens.display()
+                            )));
+                        }
+                    }
+                    Some(phonemes.split_whitespace().collect::<Vec<_>>().join(" "))
+                }
+                None => entry.respell().and_then(|text| look_up(&known, text)),
+            };
+            match phones {
+                Some(phones) if !key.contains(char::is_whitespace) => added.push((key, phones)),
+                _ => {
+                    if let Some(text) = entry.respell() {
+                        respell.push((word.clone(), text.to_string()));
+                    }
+                }
+            }
+        }
+
+        let mut merged = String::new();
+        for (word, phones) in &added {
+            merged.push_str(&format!("{} {}\n", word, phones));
+        }
+        let replaced: HashSet<&str> = added.iter().map(|(word, _)| word.as_str()).collect();
+        for line in base.lines() {
+            let word = line.split_whitespace().next().unwrap_or("");
+            if !replaced.contains(word) {
+                merged.push_str(line);
+                merged.push('\n');
+            }
+        }
+
+        let digest: String = Sha256::digest(merged.as_bytes())
+            .iter()
+            .take(8)
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        let path = overlay_dir().join(format!("lexicon-{}.txt", digest));
+        if !path.exists() {
+            fs::create_dir_all(overlay_dir())
+                .map_err(|e| BiboError::Other(format!("Failed to create lexicon dir: {}", e)))?;
+            // Write to a unique temp file first so sherpa never reads a
+            // partial file and concurrent writers never interleave
+            NamedTempFile::new_in(overlay_dir())
+                .and_then(|mut temp| {
+                    temp.write_all(merged.as_bytes())?;
+                    temp.persist(&path)?;
+                    Ok(())
+                })
+                .map_err(|e| BiboError::Other(format!("Failed to write lexicon: {}", e)))?;
+        }
+        Ok((path, Respelling::new(respell)))
+    }
+}
+
+/// Merged lexicons, under the bibo cache dir
+fn overlay_dir() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("bibo")
+        .join("lexicons")
+}
+
+/// Phonemes of a respelling from the lexicon: word by word, or character
+/// by character for Chinese
+fn look_up(known: &HashMap<&str, &str>, text: &str) -> Option<String> {
+    let mut phones = vec![];
+    for word in text.split_whitespace() {
+        let word = word.to_lowercase();
+        match known.get(word.as_str()) {
+            Some(found) => phones.push(found.to_string()),
+            None => {
+                for c in word.chars() {
+                    phones.push(known.get(c.to_string().as_str())?.to_string());
+                }
+            }
+        }
+    }
+    (!phones.is_empty()).then(|| phones.join(" "))
+}
+
+impl Respelling {
+    fn new(words: impl IntoIterator<Item = (String, String)>) -> Self {
+        let words: HashMap<String, String> = words
+            .into_iter()
+            .map(|(word, text)| (word.to_lowercase(), text))
+            .collect();
+        if words.is_empty() {
+            return Self::default();
+        }
+        let mut keys: Vec<&String> = words.keys().collect();
+        keys.sort_by(|a, b| b.len().cmp(&a.len()).then(a.cmp(b)));
+        let alternatives: Vec<String> = keys.iter().map(|k| regex_lite::escape(k)).collect();
+        let pattern = Regex::new(&format!("(?i){}", alternatives.join("|"))).ok();
+        Self { words, pattern }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Replace whole words with their respelling, ignoring case
+    pub fn apply(&self, text: &str) -> String {
+        let Some(pattern) = &self.pattern else {
+            return text.to_string();
+        };
+        let mut out = String::with_capacity(text.len());
+        let mut pos = 0;
+        while let Some(found) = pattern.find_at(text, pos) {
+            let word = found.as_str();
+            let before = text[..found.start()].chars().next_back();
+            let after = text[found.end()..].chars().next();
+            let first = word.chars().next().unwrap_or(' ');
+            let last = word.chars().next_back().unwrap_or(' ');
+            out.push_str(&text[pos..found.start()]);
+            if glued(before, first) || glued(after, last) {
+                out.push(first);
+                pos = found.start() + first.len_utf8();
+                continue;
+            }
+            match self.words.get(&word.to_lowercase()) {
+                Some(respelled) => out.push_str(respelled),
+                None => out.push_str(word),
+            }
+            pos = found.end();
+        }
+        out.push_str(&text[pos..]);
+        out
+    }
+}
+
+/// Whether a neighbour joins the word: letters run together in spaced
+/// scripts, but Chinese and Japanese have no spaces between words
+fn glued(neighbour: Option<char>, edge: char) -> bool {
+    let spaced = |c: char| !('\u{2e80}'..='\u{9fff}').contains(&c);
+    neighbour.is_some_and(|c| c.is_alphanumeric() && spaced(c) && spaced(edge))
+}
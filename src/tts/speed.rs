@@ -0,0 +1,51 @@
+//! Speaking rate presets and rate parsing
+
+use crate::error::BiboError;
+use std::str::FromStr;
+
+/// Speaking rate preset
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum Speed {
+    Slow,
+    #[default]
+    Normal,
+    Fast,
+}
+
+impl Speed {
+    /// Convert to length scale for Piper
+    /// Lower = faster speech
+    pub fn to_length_scale(&self) -> f32 {
+        match self {
+            Speed::Slow => 1.2,
+            Speed::Normal => 1.0,
+            Speed::Fast => 0.8,
+        }
+    }
+}
+
+impl FromStr for Speed {
+    type Err = BiboError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "slow" => Ok(Speed::Slow),
+            "normal" => Ok(Speed::Normal),
+            "fast" => Ok(Speed::Fast),
+            _ => Err(BiboError::InvalidSpeed(s.to_string())),
+        }
+    }
+}
+
+/// Parse a speed preset ("fast") or rate multiplier ("1.2") into a length scale
+pub fn parse_length_scale(spec: &str) -> Result<f32, BiboError> {
+    if let Ok(preset) = spec.parse::<Speed>() {
+        return Ok(preset.to_length_scale());
+    }
+
+    match spec.trim_end_matches('x').parse::<f32>() {
+        Ok(rate) if (0.25..=4.0).contains(&rate) => Ok(1.0 / rate),
+        _ => Err(BiboError::InvalidSpeed(spec.to_string())),
+    }
+}
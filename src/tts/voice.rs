@@ -3,7 +3,7 @@
 //! Curated selection of high-quality sherpa-onnx voices
 
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Voice metadata for sherpa-onnx models
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,34 +20,44 @@ pub struct Voice {
     pub onnx_file: &'static str,
     /// Download URL for the model
     pub download_url: &'static str,
+    /// Number of speakers (select with --speaker / --sid)
+    pub num_speakers: u32,
 }
 
 impl Voice {
     /// Get the model directory path
-    pub fn model_dir_path(&self, base: &PathBuf) -> PathBuf {
+    pub fn model_dir_path(&self, base: &Path) -> PathBuf {
         base.join(self.model_dir)
     }
 
     /// Get the model onnx path
-    pub fn model_path(&self, base: &PathBuf) -> PathBuf {
+    pub fn model_path(&self, base: &Path) -> PathBuf {
         self.model_dir_path(base).join(self.onnx_file)
     }
 
     /// Get the tokens.txt path
-    pub fn tokens_path(&self, base: &PathBuf) -> PathBuf {
+    pub fn tokens_path(&self, base: &Path) -> PathBuf {
         self.model_dir_path(base).join("tokens.txt")
     }
 
     /// Get the lexicon.txt path (optional, for some models)
-    pub fn lexicon_path(&self, base: &PathBuf) -> PathBuf {
+    pub fn lexicon_path(&self, base: &Path) -> PathBuf {
         self.model_dir_path(base).join("lexicon.txt")
     }
 
     /// Get the dict_dir path (for Chinese models)
-    pub fn dict_dir(&self, base: &PathBuf) -> PathBuf {
+    pub fn dict_dir(&self, base: &Path) -> PathBuf {
         self.model_dir_path(base).join("dict")
     }
 
+    /// Locale codes spoken by this voice ("zh_en" expands to both)
+    pub fn languages(&self) -> Vec<&'static str> {
+        match self.lang {
+            "zh_en" => vec!["zh_CN", "en_US"],
+            lang => vec![lang],
+        }
+    }
+
     /// Check if this is a MeloTTS model (Chinese+English)
     pub fn is_melo(&self) -> bool {
         self.model_dir.contains("melo")
@@ -69,6 +79,7 @@ pub static VOICE_CATALOG: &[Voice] = &[
         model_dir: "vits-melo-tts-zh_en",
         onnx_file: "model.onnx",
         download_url: "https://github.com/k2-fsa/sherpa-onnx/releases/download/tts-models/vits-melo-tts-zh_en.tar.bz2",
+        num_speakers: 1,
     },
     // Chinese only
     Voice {
@@ -81,6 +92,7 @@ pub static VOICE_CATALOG: &[Voice] = &[
         model_dir: "vits-piper-zh_CN-huayan-medium",
         onnx_file: "zh_CN-huayan-medium.onnx",
         download_url: "https://github.com/k2-fsa/sherpa-onnx/releases/download/tts-models/vits-piper-zh_CN-huayan-medium.tar.bz2",
+        num_speakers: 1,
     },
     Voice {
         id: "aishell3",
@@ -92,6 +104,7 @@ pub static VOICE_CATALOG: &[Voice] = &[
         model_dir: "vits-zh-aishell3",
         onnx_file: "vits-aishell3.onnx",
         download_url: "https://github.com/k2-fsa/sherpa-onnx/releases/download/tts-models/vits-zh-aishell3.tar.bz2",
+        num_speakers: 174,
     },
     // Korean
     Voice {
@@ -104,6 +117,7 @@ pub static VOICE_CATALOG: &[Voice] = &[
         model_dir: "vits-mimic3-ko_KO-kss_low",
         onnx_file: "ko_KO-kss_low.onnx",
         download_url: "https://github.com/k2-fsa/sherpa-onnx/releases/download/tts-models/vits-mimic3-ko_KO-kss_low.tar.bz2",
+        num_speakers: 1,
     },
     // English - US
     Voice {
@@ -116,6 +130,7 @@ pub static VOICE_CATALOG: &[Voice] = &[
         model_dir: "vits-piper-en_US-amy-low",
         onnx_file: "en_US-amy-low.onnx",
         download_url: "https://github.com/k2-fsa/sherpa-onnx/releases/download/tts-models/vits-piper-en_US-amy-low.tar.bz2",
+        num_speakers: 1,
     },
     Voice {
         id: "lessac",
@@ -127,6 +142,7 @@ pub static VOICE_CATALOG: &[Voice] = &[
         model_dir: "vits-piper-en_US-lessac-high",
         onnx_file: "en_US-lessac-high.onnx",
         download_url: "https://github.com/k2-fsa/sherpa-onnx/releases/download/tts-models/vits-piper-en_US-lessac-high.tar.bz2",
+        num_speakers: 1,
     },
     Voice {
         id: "ryan",
@@ -138,6 +154,7 @@ pub static VOICE_CATALOG: &[Voice] = &[
         model_dir: "vits-piper-en_US-ryan-high",
         onnx_file: "en_US-ryan-high.onnx",
         download_url: "https://github.com/k2-fsa/sherpa-onnx/releases/download/tts-models/vits-piper-en_US-ryan-high.tar.bz2",
+        num_speakers: 1,
     },
     Voice {
         id: "joe",
@@ -149,6 +166,7 @@ pub static VOICE_CATALOG: &[Voice] = &[
         model_dir: "vits-piper-en_US-joe-medium",
         onnx_file: "en_US-joe-medium.onnx",
         download_url: "https://github.com/k2-fsa/sherpa-onnx/releases/download/tts-models/vits-piper-en_US-joe-medium.tar.bz2",
+        num_speakers: 1,
     },
     Voice {
         id: "ljspeech",
@@ -160,6 +178,7 @@ pub static VOICE_CATALOG: &[Voice] = &[
         model_dir: "vits-ljs",
         onnx_file: "vits-ljs.onnx",
         download_url: "https://github.com/k2-fsa/sherpa-onnx/releases/download/tts-models/vits-ljs.tar.bz2",
+        num_speakers: 1,
     },
     // English - GB
     Voice {
@@ -172,6 +191,7 @@ pub static VOICE_CATALOG: &[Voice] = &[
         model_dir: "vits-piper-en_GB-alan-medium",
         onnx_file: "en_GB-alan-medium.onnx",
         download_url: "https://github.com/k2-fsa/sherpa-onnx/releases/download/tts-models/vits-piper-en_GB-alan-medium.tar.bz2",
+        num_speakers: 1,
     },
     Voice {
         id: "alba",
@@ -183,6 +203,7 @@ pub static VOICE_CATALOG: &[Voice] = &[
         model_dir: "vits-piper-en_GB-alba-medium",
         onnx_file: "en_GB-alba-medium.onnx",
         download_url: "https://github.com/k2-fsa/sherpa-onnx/releases/download/tts-models/vits-piper-en_GB-alba-medium.tar.bz2",
+        num_speakers: 1,
     },
     // German
     Voice {
@@ -195,6 +216,7 @@ pub static VOICE_CATALOG: &[Voice] = &[
         model_dir: "vits-piper-de_DE-thorsten-high",
         onnx_file: "de_DE-thorsten-high.onnx",
         download_url: "https://github.com/k2-fsa/sherpa-onnx/releases/download/tts-models/vits-piper-de_DE-thorsten-high.tar.bz2",
+        num_speakers: 1,
     },
     // French
     Voice {
@@ -207,6 +229,7 @@ pub static VOICE_CATALOG: &[Voice] = &[
         model_dir: "vits-piper-fr_FR-siwis-medium",
         onnx_file: "fr_FR-siwis-medium.onnx",
         download_url: "https://github.com/k2-fsa/sherpa-onnx/releases/download/tts-models/vits-piper-fr_FR-siwis-medium.tar.bz2",
+        num_speakers: 1,
     },
     // Spanish
     Voice {
@@ -219,6 +242,7 @@ pub static VOICE_CATALOG: &[Voice] = &[
         model_dir: "vits-piper-es_ES-davefx-medium",
         onnx_file: "es_ES-davefx-medium.onnx",
         download_url: "https://github.com/k2-fsa/sherpa-onnx/releases/download/tts-models/vits-piper-es_ES-davefx-medium.tar.bz2",
+        num_speakers: 1,
     },
     // Russian
     Voice {
@@ -231,6 +255,7 @@ pub static VOICE_CATALOG: &[Voice] = &[
         model_dir: "vits-piper-ru_RU-irina-medium",
         onnx_file: "ru_RU-irina-medium.onnx",
         download_url: "https://github.com/k2-fsa/sherpa-onnx/releases/download/tts-models/vits-piper-ru_RU-irina-medium.tar.bz2",
+        num_speakers: 1,
     },
     Voice {
         id: "ruslan",
@@ -242,6 +267,7 @@ pub static VOICE_CATALOG: &[Voice] = &[
         model_dir: "vits-piper-ru_RU-ruslan-medium",
         onnx_file: "ru_RU-ruslan-medium.onnx",
         download_url: "https://github.com/k2-fsa/sherpa-onnx/releases/download/tts-models/vits-piper-ru_RU-ruslan-medium.tar.bz2",
+        num_speakers: 1,
     },
     // Vietnamese
     Voice {
@@ -254,6 +280,7 @@ pub static VOICE_CATALOG: &[Voice] = &[
         model_dir: "vits-mimic3-vi_VN-vais1000_low",
         onnx_file: "vi_VN-vais1000_low.onnx",
         download_url: "https://github.com/k2-fsa/sherpa-onnx/releases/download/tts-models/vits-mimic3-vi_VN-vais1000_low.tar.bz2",
+        num_speakers: 1,
     },
 ];
 
@@ -0,0 +1,212 @@
+//! Terminal presentation - errors, download progress, and the voice catalog
+
+use bibo::download::{Progress, VoiceDownloader};
+use bibo::error::{BiboError, Result};
+use bibo::tts::{VoiceCatalog, VOICE_CATALOG};
+use colored::Colorize;
+use indicatif::{ProgressBar, ProgressStyle};
+
+/// Display errors with actionable tips
+pub trait ShowError {
+    fn show(&self);
+}
+
+impl ShowError for BiboError {
+    /// Display formatted error with tips
+    fn show(&self) {
+        eprintln!("\n{} {}", "❌".red(), self.to_string().red().bold());
+
+        let tips = self.tips();
+        if !tips.is_empty() {
+            eprintln!("\n{}", "💡 How to fix:".yellow());
+            for tip in tips {
+                eprintln!("   {} {}", "→".cyan(), tip);
+            }
+        }
+        eprintln!();
+    }
+}
+
+/// Progress bar driven by `Progress::Downloading` events
+#[derive(Default)]
+struct DownloadBar(Option<ProgressBar>);
+
+impl DownloadBar {
+    fn update(&mut self, downloaded: u64, total: Option<u64>) {
+        let Some(total) = total.filter(|t| *t > 0) else {
+            return;
+        };
+        let pb = self.0.get_or_insert_with(|| {
+            let pb = ProgressBar::new(total);
+            pb.set_style(
+                ProgressStyle::default_bar()
+                    .template("   [{bar:30.cyan/blue}] {bytes}/{total_bytes} ({percent}%)")
+                    .unwrap()
+                    .progress_chars("█░"),
+            );
+            pb
+        });
+        pb.set_position(downloaded);
+    }
+
+    fn finish(&mut self) {
+        if let Some(pb) = self.0.take() {
+            pb.finish_and_clear();
+        }
+    }
+}
+
+/// Progress reporter for voice downloads
+pub fn voice_progress(quiet: bool) -> impl FnMut(Progress) {
+    let mut bar = DownloadBar::default();
+    move |progress| {
+        if quiet {
+            return;
+        }
+        match progress {
+            Progress::AlreadyInstalled { name } => {
+                if let Some(voice) = VoiceCatalog::find(&name) {
+                    println!(
+                        "{} {} ({}) already installed",
+                        "✅".green(),
+                        voice.name,
+                        voice.lang
+                    );
+                }
+            }
+            Progress::Started { name, .. } => {
+                if let Some(voice) = VoiceCatalog::find(&name) {
+                    println!(
+                        "\n{} Downloading: {} ({}, {}, {}, ~{}MB)",
+                        "📥".cyan(),
+                        voice.name,
+                        voice.lang,
+                        voice.gender,
+                        voice.quality,
+                        voice.size_mb
+                    );
+                }
+                println!("   Source: sherpa-onnx");
+            }
+            Progress::Downloading { downloaded, total } => bar.update(downloaded, total),
+            Progress::Extracting => {
+                bar.finish();
+                println!("   {} Extracting...", "📂".cyan());
+            }
+            Progress::Finished { name } => {
+                let name = VoiceCatalog::find(&name).map_or(name.as_str(), |v| v.name);
+                println!("{} {} installed successfully!", "✅".green(), name);
+            }
+        }
+    }
+}
+
+/// Progress reporter for the sherpa-onnx engine download
+pub fn sherpa_progress(quiet: bool) -> impl FnMut(Progress) {
+    let mut bar = DownloadBar::default();
+    move |progress| {
+        if quiet {
+            return;
+        }
+        match progress {
+            Progress::AlreadyInstalled { .. } => {
+                println!("{} Sherpa-onnx already installed", "✅".green())
+            }
+            Progress::Started { url, .. } => {
+                println!("{} Downloading sherpa-onnx TTS engine...", "📦".cyan());
+                println!("   From: {}", url);
+            }
+            Progress::Downloading { downloaded, total } => bar.update(downloaded, total),
+            Progress::Extracting => {
+                bar.finish();
+                println!("   {} Extracting...", "📂".cyan());
+            }
+            Progress::Finished { .. } => {
+                println!("{} Sherpa-onnx installed successfully!", "✅".green())
+            }
+        }
+    }
+}
+
+/// Show available voices for download
+pub fn show_catalog() {
+    let installed = VoiceCatalog::installed();
+
+    println!("\n{}", "📦 Available voices for download:".cyan().bold());
+    println!();
+    println!(
+        "{:<3} {:<12} {:<12} {:<8} {:<3} {:<7} {:<6} Status",
+        "#", "ID", "Name", "Lang", "G", "Quality", "Size"
+    );
+    println!("{}", "─".repeat(75));
+
+    for (idx, voice) in VOICE_CATALOG.iter().enumerate() {
+        let is_installed = installed
+            .iter()
+            .any(|v| v.to_lowercase().contains(&voice.model_dir.to_lowercase()));
+        let status = if is_installed {
+            "✅ installed".green().to_string()
+        } else {
+            String::new()
+        };
+
+        println!(
+            "{:<3} {:<12} {:<12} {:<8} {:<3} {:<7} {}MB  {}",
+            idx + 1,
+            voice.id,
+            voice.name,
+            voice.lang,
+            voice.gender,
+            voice.quality,
+            voice.size_mb,
+            status
+        );
+    }
+
+    println!();
+    println!("{}", "💡 Usage:".yellow());
+    println!("   bibo -d <id>        Download single voice");
+    println!("   bibo -d all         Download all voices");
+    println!("   bibo -d 1,3,5       Download by numbers");
+    println!();
+    println!("{}", "🌍 Languages:".yellow());
+    println!("   melo    - Chinese + English bilingual (recommended)");
+    println!("   kss     - Korean");
+    println!("   amy     - English (US)");
+    println!("   huayan  - Chinese");
+}
+
+/// Handle `bibo -d <spec>`: "list", a voice ID, "all", or "1,3,5"
+pub async fn download_voices(spec: &str, quiet: bool) -> Result<usize> {
+    if spec.eq_ignore_ascii_case("list") {
+        show_catalog();
+        return Ok(0);
+    }
+
+    let voices = VoiceDownloader::resolve_spec(spec)?;
+    if voices.len() == 1 {
+        VoiceDownloader::download_voice(voices[0].id, voice_progress(quiet)).await?;
+        return Ok(1);
+    }
+
+    if !quiet && voices.len() == VOICE_CATALOG.len() {
+        println!("{}", "📦 Downloading all voices...".cyan());
+    }
+    let mut success = 0;
+    for voice in &voices {
+        match VoiceDownloader::download_voice(voice.id, voice_progress(quiet)).await {
+            Ok(_) => success += 1,
+            Err(e) if !quiet => e.show(),
+            Err(_) => {}
+        }
+    }
+    if !quiet {
+        println!(
+            "\n{} Downloaded {}/{} voices",
+            "✅".green(),
+            success,
+            voices.len()
+        );
+    }
+    Ok(success)
+}
@@ -0,0 +1,224 @@
+//! Watch mode - re-render audio when the input file changes
+//!
+//! Audio is synthesized per paragraph and kept in memory, so an edit only
+//! re-synthesizes the paragraphs whose text actually changed.
+
+use crate::cli::InputFormat;
+use crate::ui::ShowError;
+use bibo::audio::AudioBuffer;
+use bibo::config::Config;
+use bibo::error::{BiboError, Result};
+use bibo::input::{read_file_content, to_segments};
+use bibo::text::Segment;
+use bibo::tts::TtsEngine;
+use colored::Colorize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+/// How often the input file is checked for changes
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Silence inserted between paragraphs
+const PARAGRAPH_GAP_SECS: f32 = 0.5;
+
+/// Split text into paragraphs on blank lines
+pub fn split_paragraphs(text: &str) -> Vec<String> {
+    let mut paragraphs = vec![];
+    let mut current = String::new();
+
+    for line in text.lines() {
+        if line.trim().is_empty() {
+            if !current.is_empty() {
+                paragraphs.push(std::mem::take(&mut current));
+            }
+        } else {
+            if !current.is_empty() {
+                current.push('\n');
+            }
+            current.push_str(line.trim_end());
+        }
+    }
+    if !current.is_empty() {
+        paragraphs.push(current);
+    }
+
+    paragraphs
+}
+
+/// Speech segments of a document: paragraphs for plain text, rendered
+/// structure for Markdown and scripts
+pub fn document_segments(text: &str, format: InputFormat, config: &Config) -> Result<Vec<Segment>> {
+    match format {
+        InputFormat::Txt | InputFormat::Epub | InputFormat::Auto => {
+            let mut segments = vec![];
+            for paragraph in split_paragraphs(text) {
+                if !segments.is_empty() {
+                    segments.push(Segment::Silence(PARAGRAPH_GAP_SECS));
+                }
+                segments.push(Segment::speech(paragraph));
+            }
+            Ok(segments)
+        }
+        format => to_segments(text, format, config),
+    }
+}
+
+/// Audio cache key: text, voice, speaker, and rate of a speech segment
+type CacheKey = (String, Option<String>, Option<u32>, u32);
+
+/// Segment-level renderer with an in-memory audio cache
+pub struct Renderer<'a> {
+    engine: &'a TtsEngine,
+    length_scale: f32,
+    cache: HashMap<CacheKey, AudioBuffer>,
+}
+
+/// Result of one render pass
+pub struct RenderStats {
+    pub paragraphs: usize,
+    pub synthesized: usize,
+    pub duration_secs: f32,
+}
+
+impl<'a> Renderer<'a> {
+    pub fn new(engine: &'a TtsEngine, length_scale: f32) -> Self {
+        Self {
+            engine,
+            length_scale,
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Render segments to a single buffer, reusing audio for unchanged speech
+    pub fn render(&mut self, segments: &[Segment]) -> Result<(AudioBuffer, RenderStats)> {
+        let mut cache = HashMap::with_capacity(segments.len());
+        let mut rendered = Vec::with_capacity(segments.len());
+        let mut synthesized = 0;
+
+        for segment in segments {
+            let Segment::Speech {
+                text,
+                voice,
+                speaker,
+                rate,
+            } = segment
+            else {
+                rendered.push(None);
+                continue;
+            };
+            let key = (text.clone(), voice.clone(), *speaker, rate.to_bits());
+            // Borrowed, not taken: a failed synthesis below keeps the cache intact
+            let audio = match self.cache.get(&key).or_else(|| cache.get(&key)) {
+                Some(audio) => audio.clone(),
+                None => {
+                    synthesized += 1;
+                    self.engine
+                        .synthesize_segments(std::slice::from_ref(segment), self.length_scale)?
+                }
+            };
+            rendered.push(Some(audio.clone()));
+            cache.insert(key, audio);
+        }
+
+        // Voices may differ in rate; the first one sets the output rate
+        let sample_rate = rendered
+            .iter()
+            .flatten()
+            .map(|audio| audio.sample_rate)
+            .next()
+            .unwrap_or_else(|| self.engine.sample_rate());
+        let mut output = AudioBuffer::new(vec![], sample_rate);
+        for (segment, audio) in segments.iter().zip(&rendered) {
+            match (segment, audio) {
+                (_, Some(audio)) => output.append(&audio.resample(sample_rate)),
+                (Segment::Silence(secs), None) => {
+                    output.append(&AudioBuffer::silence(*secs, sample_rate))
+                }
+                (Segment::Speech { .. }, None) => {}
+            }
+        }
+
+        // Only keep audio for segments still in the document
+        self.cache = cache;
+
+        let stats = RenderStats {
+            paragraphs: rendered.iter().flatten().count(),
+            synthesized,
+            duration_secs: output.duration_secs(),
+        };
+        Ok((output, stats))
+    }
+}
+
+/// File modification stamp used for change detection
+fn file_stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let meta = fs::metadata(path).ok()?;
+    Some((meta.modified().ok()?, meta.len()))
+}
+
+/// Watch the input file and re-render the output on every change
+pub fn run(
+    engine: &TtsEngine,
+    input: &str,
+    output: &str,
+    format: InputFormat,
+    config: &Config,
+    length_scale: f32,
+    quiet: bool,
+) -> Result<()> {
+    let input_path = Path::new(input);
+    // Stdin can't change and a missing file would be polled silently forever
+    if input == "-" {
+        return Err(BiboError::Other(
+            "--watch needs an input file, not stdin".to_string(),
+        ));
+    }
+    if !input_path.is_file() {
+        return Err(BiboError::FileNotFound(input.to_string()));
+    }
+    let mut renderer = Renderer::new(engine, length_scale);
+    let mut last_stamp = None;
+
+    if !quiet {
+        println!(
+            "{} Watching {} (Ctrl+C to stop)",
+            "👀".cyan(),
+            input_path.display()
+        );
+    }
+
+    loop {
+        let stamp = file_stamp(input_path);
+        if stamp.is_some() && stamp != last_stamp {
+            // Let editors finish writing before reading
+            thread::sleep(POLL_INTERVAL / 2);
+            last_stamp = file_stamp(input_path);
+
+            let started = Instant::now();
+            let rendered = read_file_content(input, format)
+                .and_then(|text| document_segments(&text, format.resolve(input)?, config))
+                .and_then(|segments| renderer.render(&segments))
+                .and_then(|(audio, stats)| audio.save(Path::new(output)).map(|_| stats));
+
+            // Render errors (e.g. an emptied file) wait for the next edit
+            match rendered {
+                Ok(stats) if !quiet => println!(
+                    "{} Rendered {} ({}/{} paragraphs re-synthesized, {:.1}s audio, took {:.1}s)",
+                    "🔁".cyan(),
+                    output,
+                    stats.synthesized,
+                    stats.paragraphs,
+                    stats.duration_secs,
+                    started.elapsed().as_secs_f32()
+                ),
+                Ok(_) => {}
+                Err(e) => e.show(),
+            }
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
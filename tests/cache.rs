@@ -0,0 +1,118 @@
+//! Synthesis cache: key stability, LRU eviction, and --no-cache
+
+mod common;
+
+use bibo::tts::cache::CacheKey;
+use bibo::{AudioBuffer, AudioCache};
+use common::MockHome;
+use std::fs::File;
+use std::time::{Duration, SystemTime};
+
+const RYAN: (&str, &str) = ("vits-piper-en_US-ryan-high", "en_US-ryan-high.onnx");
+
+fn key(text: &str) -> CacheKey<'_> {
+    CacheKey {
+        text,
+        voice_id: "ryan",
+        model: "1234:5678",
+        speaker: 0,
+        length_scale: 1.0,
+    }
+}
+
+#[test]
+fn keys_are_stable_and_cover_every_parameter() {
+    let digest = key("Hello world").digest();
+    assert_eq!(digest.len(), 64);
+    assert_eq!(digest, key("Hello world").digest());
+    // Whitespace and voice ID case don't matter
+    assert_eq!(digest, key("  Hello\n world ").digest());
+    assert_eq!(
+        digest,
+        CacheKey {
+            voice_id: "Ryan",
+            ..key("Hello world")
+        }
+        .digest()
+    );
+
+    assert_ne!(digest, key("Hello World").digest());
+    assert_ne!(
+        digest,
+        CacheKey {
+            speaker: 1,
+            ..key("Hello world")
+        }
+        .digest()
+    );
+    assert_ne!(
+        digest,
+        CacheKey {
+            model: "1234:9999",
+            ..key("Hello world")
+        }
+        .digest()
+    );
+    assert_ne!(
+        digest,
+        CacheKey {
+            length_scale: 0.8,
+            ..key("Hello world")
+        }
+        .digest()
+    );
+}
+
+#[test]
+fn least_recently_used_entries_are_evicted() {
+    let dir = tempfile::tempdir().unwrap();
+    // 1000 samples: a 2044-byte WAV, so two entries fit
+    let cache = AudioCache::new(dir.path().to_path_buf(), 5000);
+    let audio = AudioBuffer::new(vec![0; 1000], 22050);
+    let entry = |text: &str| dir.path().join(format!("{}.wav", key(text).digest()));
+    let age = |text: &str, secs: u64| {
+        let file = File::options().write(true).open(entry(text)).unwrap();
+        file.set_modified(SystemTime::now() - Duration::from_secs(secs))
+            .unwrap();
+    };
+
+    cache.put(&key("first"), &audio).unwrap();
+    cache.put(&key("second"), &audio).unwrap();
+    age("first", 7200);
+    age("second", 3600);
+    // A hit makes "first" the most recently used
+    assert!(cache.get(&key("first")).is_some());
+
+    cache.put(&key("third"), &audio).unwrap();
+    assert!(entry("first").exists());
+    assert!(!entry("second").exists());
+    assert!(entry("third").exists());
+    assert_eq!(cache.stats().entries, 2);
+    // No temp files are left behind
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 2);
+}
+
+#[test]
+fn no_cache_always_synthesizes() {
+    let home = MockHome::new(&[RYAN]);
+    let out = home.path().join("out.wav");
+    let run = |extra: &[&str]| {
+        let status = home
+            .command()
+            .args(["-v", "ryan", "-q", "-o"])
+            .arg(&out)
+            .args(extra)
+            .arg("Cache me")
+            .status()
+            .unwrap();
+        assert!(status.success());
+    };
+
+    run(&[]);
+    run(&[]);
+    assert_eq!(home.calls().len(), 1, "second run should be a cache hit");
+
+    run(&["--no-cache"]);
+    run(&["--no-cache"]);
+    assert_eq!(home.calls().len(), 3);
+}
@@ -0,0 +1,190 @@
+//! Shared fixtures: a mock sherpa-onnx backend, an isolated bibo home, and
+//! Markdown block builders
+
+#![allow(dead_code)]
+
+use bibo::text::markdown::{Block, Hint};
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdout, Command, Stdio};
+use tempfile::TempDir;
+
+/// Sample rate of the mock backend's output
+pub const MOCK_SAMPLE_RATE: u32 = 22050;
+
+/// Isolated HOME with a fake installed voice and a mock sherpa binary
+pub struct MockHome {
+    pub dir: TempDir,
+}
+
+impl MockHome {
+    /// Create a home with the given catalog voices "installed"
+    pub fn new(voices: &[(&str, &str)]) -> Self {
+        let dir = tempfile::tempdir().unwrap();
+        let home = Self { dir };
+
+        // (model_dir, onnx_file) pairs from the voice catalog
+        for (model_dir, onnx_file) in voices {
+            let model_dir = home.data_dir().join("models").join(model_dir);
+            fs::create_dir_all(&model_dir).unwrap();
+            fs::write(model_dir.join(onnx_file), b"mock").unwrap();
+        }
+
+        home.write_fixture_wav();
+        home.write_mock_sherpa();
+        home
+    }
+
+    pub fn path(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// bibo data dir as resolved by `dirs::data_dir()` under this HOME
+    pub fn data_dir(&self) -> PathBuf {
+        #[cfg(target_os = "macos")]
+        let base = self.path().join("Library/Application Support");
+        #[cfg(not(target_os = "macos"))]
+        let base = self.path().join(".local/share");
+        base.join("bibo")
+    }
+
+    /// Arguments of every mock sherpa invocation, one line per call
+    pub fn calls(&self) -> Vec<String> {
+        fs::read_to_string(self.path().join("calls.log"))
+            .unwrap_or_default()
+            .lines()
+            .map(String::from)
+            .collect()
+    }
+
+    /// 0.1s clips: fixture.wav at MOCK_SAMPLE_RATE, fixture-melo.wav at
+    /// MeloTTS's 44.1 kHz
+    fn write_fixture_wav(&self) {
+        for (name, sample_rate) in [
+            ("fixture.wav", MOCK_SAMPLE_RATE),
+            ("fixture-melo.wav", 44100),
+        ] {
+            let spec = hound::WavSpec {
+                channels: 1,
+                sample_rate,
+                bits_per_sample: 16,
+                sample_format: hound::SampleFormat::Int,
+            };
+            let mut writer = hound::WavWriter::create(self.path().join(name), spec).unwrap();
+            for i in 0..sample_rate / 10 {
+                writer
+                    .write_sample(((i % 100) * 100) as i16 - 5000)
+                    .unwrap();
+            }
+            writer.finalize().unwrap();
+        }
+    }
+
+    /// Shell script standing in for sherpa-onnx-offline-tts: logs its
+    /// arguments and copies the fixture WAV for the model to --output-filename.
+    /// MOCK_SHERPA_DELAY (seconds) simulates slow synthesis.
+    fn write_mock_sherpa(&self) {
+        let script = format!(
+            r#"#!/bin/sh
+echo "$*" >> "{dir}/calls.log"
+sleep "${{MOCK_SHERPA_DELAY:-0}}"
+fixture=fixture.wav
+case "$*" in
+  *vits-melo-tts*) fixture=fixture-melo.wav ;;
+esac
+for arg in "$@"; do
+  case "$arg" in
+    --output-filename=*) cp "{dir}/$fixture" "${{arg#--output-filename=}}" ;;
+  esac
+done
+"#,
+            dir = self.path().display()
+        );
+        let path = self.sherpa_path();
+        fs::write(&path, script).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    pub fn sherpa_path(&self) -> PathBuf {
+        self.path().join("mock-sherpa")
+    }
+
+    /// `bibo` command running against this home
+    pub fn command(&self) -> Command {
+        self.command_for(env!("CARGO_BIN_EXE_bibo"))
+    }
+
+    /// Any of the crate's binaries running against this home
+    pub fn command_for(&self, program: &str) -> Command {
+        let mut cmd = Command::new(program);
+        cmd.env("HOME", self.path())
+            .env("BIBO_SHERPA_PATH", self.sherpa_path())
+            .env_remove("XDG_DATA_HOME")
+            .env_remove("XDG_CACHE_HOME")
+            .env_remove("XDG_CONFIG_HOME")
+            .env_remove("XDG_RUNTIME_DIR")
+            .env_remove("BIBO_SOCKET")
+            .env_remove("BIBO_NO_DAEMON")
+            .env_remove("BIBO_CONFIG")
+            .env_remove("BIBO_VOICE");
+        cmd
+    }
+}
+
+/// Markdown/HTML block without table cells
+pub fn block(hint: Hint, quote: usize, text: &str) -> Block {
+    Block {
+        hint,
+        quote,
+        text: text.to_string(),
+        cells: vec![],
+    }
+}
+
+/// Child process killed on drop
+pub struct Server {
+    pub child: Child,
+    pub addr: String,
+    // Kept open so later writes to stdout don't hit a closed pipe
+    _stdout: BufReader<ChildStdout>,
+}
+
+impl Server {
+    /// Spawn a server and wait for its "Listening on <addr>" line
+    #[allow(clippy::zombie_processes)] // reaped in Drop
+    pub fn spawn(mut cmd: Command) -> Self {
+        let mut child = cmd
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .unwrap();
+
+        let stdout = child.stdout.take().unwrap();
+        let mut reader = BufReader::new(stdout);
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if reader.read_line(&mut line).unwrap() == 0 {
+                panic!("server exited before listening");
+            }
+            if let Some(idx) = line.find("Listening on ") {
+                let addr = line[idx + "Listening on ".len()..].trim();
+                let addr = addr.split("://").last().unwrap().to_string();
+                return Self {
+                    child,
+                    addr,
+                    _stdout: reader,
+                };
+            }
+        }
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
@@ -0,0 +1,247 @@
+//! Daemon tests: CLI forwarding and control commands over the Unix socket
+
+#![cfg(unix)]
+
+mod common;
+
+use common::{MockHome, Server};
+use std::process::Output;
+use std::time::{Duration, Instant};
+
+const RYAN: (&str, &str) = ("vits-piper-en_US-ryan-high", "en_US-ryan-high.onnx");
+
+fn start_daemon(home: &MockHome) -> Server {
+    let mut cmd = home.command();
+    cmd.args(["daemon", "-q"]);
+    Server::spawn(cmd)
+}
+
+fn bibo(home: &MockHome, args: &[&str]) -> Output {
+    home.command().args(args).output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+/// Wait until the mock backend has been called `n` times
+fn wait_for_calls(home: &MockHome, n: usize) -> Vec<String> {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    loop {
+        let calls = home.calls();
+        if calls.len() >= n || Instant::now() > deadline {
+            return calls;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+}
+
+#[test]
+fn control_commands_report_missing_daemon() {
+    let home = MockHome::new(&[RYAN]);
+
+    let output = bibo(&home, &["daemon", "status"]);
+    assert!(!output.status.success());
+    assert!(stdout(&output).contains("Daemon not running"));
+}
+
+#[test]
+fn cli_forwards_speech_to_running_daemon() {
+    let home = MockHome::new(&[RYAN]);
+    let daemon = start_daemon(&home);
+    assert!(daemon.addr.ends_with("daemon.sock"));
+
+    let output = bibo(&home, &["-v", "ryan", "--speaker", "0", "Hello. World."]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("Queued in daemon"));
+
+    // The daemon synthesizes sentence by sentence
+    let calls = wait_for_calls(&home, 1);
+    assert!(calls[0].contains("Hello."));
+}
+
+#[test]
+fn unknown_voice_is_rejected_by_daemon() {
+    let home = MockHome::new(&[RYAN]);
+    let _daemon = start_daemon(&home);
+
+    // Not in the catalog: daemon refuses, local fallback reports the error
+    let output = bibo(&home, &["-v", "nobody", "Hi"]);
+    assert!(!output.status.success());
+    assert!(!stdout(&output).contains("Queued in daemon"));
+}
+
+#[test]
+fn control_commands_talk_to_daemon() {
+    let home = MockHome::new(&[RYAN]);
+    let mut daemon = start_daemon(&home);
+
+    let status = bibo(&home, &["daemon", "status"]);
+    assert!(status.status.success());
+    assert!(stdout(&status).contains("Daemon running"));
+
+    for action in ["queue", "skip", "stop"] {
+        let output = bibo(&home, &["daemon", action]);
+        assert!(output.status.success(), "daemon {} failed", action);
+    }
+
+    let output = bibo(&home, &["daemon", "shutdown"]);
+    assert!(output.status.success());
+
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while daemon.child.try_wait().unwrap().is_none() {
+        assert!(Instant::now() < deadline, "daemon did not exit");
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    assert!(!std::path::Path::new(&daemon.addr).exists());
+}
+
+#[test]
+fn idle_connection_does_not_block_others() {
+    let home = MockHome::new(&[RYAN]);
+    let daemon = start_daemon(&home);
+
+    // Connected but never sends a request line
+    let _idle = std::os::unix::net::UnixStream::connect(&daemon.addr).unwrap();
+
+    let started = Instant::now();
+    let status = bibo(&home, &["daemon", "status"]);
+    assert!(status.status.success());
+    assert!(started.elapsed() < Duration::from_secs(3));
+}
+
+#[test]
+fn second_daemon_refuses_to_start() {
+    let home = MockHome::new(&[RYAN]);
+    let _daemon = start_daemon(&home);
+
+    let output = bibo(&home, &["daemon"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn queue_plays_higher_priority_first() {
+    let home = MockHome::new(&[RYAN]);
+    let mut cmd = home.command();
+    cmd.args(["daemon", "-q"]).env("MOCK_SHERPA_DELAY", "1");
+    let _daemon = Server::spawn(cmd);
+
+    // First job occupies the player; the rest wait and get reordered
+    for (text, priority) in [
+        ("Busy", "normal"),
+        ("Later", "low"),
+        ("Normal", "normal"),
+        ("Soon", "high"),
+    ] {
+        let output = bibo(&home, &["-v", "ryan", "--priority", priority, text]);
+        assert!(stdout(&output).contains("Queued in daemon"));
+    }
+
+    let queue = stdout(&bibo(&home, &["daemon", "queue"]));
+    let soon = queue.find("Soon").unwrap();
+    let normal = queue.find("Normal").unwrap();
+    let later = queue.find("Later").unwrap();
+    assert!(soon < normal && normal < later, "{}", queue);
+
+    let calls = wait_for_calls(&home, 4);
+    let position = |text: &str| calls.iter().position(|call| call.contains(text)).unwrap();
+    assert!(position("Busy") < position("Soon"));
+    assert!(position("Soon") < position("Normal"));
+    assert!(position("Normal") < position("Later"));
+}
+
+#[test]
+fn markdown_pauses_play_in_process() {
+    let home = MockHome::new(&[RYAN]);
+    let _daemon = start_daemon(&home);
+
+    // Heading and paragraph pauses would be lost as daemon plain text
+    let notes = home.path().join("notes.md");
+    std::fs::write(&notes, "# Title\n\nFirst paragraph.\n").unwrap();
+    let output = bibo(&home, &["-v", "ryan", "-i", notes.to_str().unwrap()]);
+    assert!(!stdout(&output).contains("Queued in daemon"));
+
+    // Synthesized locally, block by block
+    let calls = home.calls();
+    assert!(calls.iter().any(|call| call.contains("Title")));
+    assert!(calls.iter().any(|call| call.contains("First paragraph.")));
+}
+
+#[test]
+fn daemon_and_in_process_playback_take_turns() {
+    let home = MockHome::new(&[RYAN]);
+    let _daemon = start_daemon(&home);
+
+    // Hold the playback lock as another bibo process would
+    let lock_dir = home.path().join(".cache").join("bibo");
+    std::fs::create_dir_all(&lock_dir).unwrap();
+    let lock = std::fs::File::create(lock_dir.join("playback.lock")).unwrap();
+    lock.lock().unwrap();
+
+    // The daemon renders the first sentence, then waits for its turn
+    let output = bibo(&home, &["-v", "ryan", "One. Two."]);
+    assert!(stdout(&output).contains("Queued in daemon"));
+    wait_for_calls(&home, 1);
+
+    // So does an in-process run
+    let notes = home.path().join("notes.md");
+    std::fs::write(&notes, "# Title\n\nBody.\n").unwrap();
+    let mut local = home
+        .command()
+        .args(["-v", "ryan", "-q", "-i"])
+        .arg(&notes)
+        .spawn()
+        .unwrap();
+    wait_for_calls(&home, 3);
+    std::thread::sleep(Duration::from_millis(500));
+    assert!(local.try_wait().unwrap().is_none());
+    assert!(!home.calls().iter().any(|call| call.contains("Two.")));
+    assert!(stdout(&bibo(&home, &["daemon", "queue"])).contains("One. Two."));
+
+    // Once released, both get to play
+    drop(lock);
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while local.try_wait().unwrap().is_none() {
+        assert!(Instant::now() < deadline, "in-process run never played");
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    while stdout(&bibo(&home, &["daemon", "queue"])).contains("One.") {
+        assert!(Instant::now() < deadline, "daemon never played");
+        std::thread::sleep(Duration::from_millis(50));
+    }
+}
+
+#[test]
+fn no_cache_is_forwarded_to_the_daemon() {
+    let home = MockHome::new(&[RYAN]);
+    let _daemon = start_daemon(&home);
+
+    for _ in 0..2 {
+        let output = bibo(&home, &["-v", "ryan", "--no-cache", "Hello."]);
+        assert!(stdout(&output).contains("Queued in daemon"));
+    }
+    // Both requests were synthesized rather than one served from the cache
+    assert_eq!(wait_for_calls(&home, 2).len(), 2);
+}
+
+#[test]
+fn stalled_daemon_falls_back_to_in_process_playback() {
+    let home = MockHome::new(&[RYAN]);
+
+    // Accepts connections but never answers
+    let socket = home.path().join(".cache").join("bibo").join("daemon.sock");
+    std::fs::create_dir_all(socket.parent().unwrap()).unwrap();
+    let listener = std::os::unix::net::UnixListener::bind(&socket).unwrap();
+    let _stalled = std::thread::spawn(move || {
+        let mut held = vec![];
+        for stream in listener.incoming() {
+            held.push(stream);
+        }
+    });
+
+    let started = Instant::now();
+    let output = bibo(&home, &["-v", "ryan", "Hello."]);
+    assert!(started.elapsed() < Duration::from_secs(15));
+    assert!(!stdout(&output).contains("Queued in daemon"));
+    assert!(home.calls().iter().any(|call| call.contains("Hello.")));
+}
@@ -0,0 +1,113 @@
+//! Dialogue scripts: cast parsing and multi-voice rendering
+
+mod common;
+
+use bibo::text::{dialogue, Segment};
+use bibo::{AudioBuffer, BiboError};
+use common::{MockHome, MOCK_SAMPLE_RATE};
+use std::fs;
+
+const RYAN: (&str, &str) = ("vits-piper-en_US-ryan-high", "en_US-ryan-high.onnx");
+const MELO: (&str, &str) = ("vits-melo-tts-zh_en", "model.onnx");
+
+const SCRIPT: &str = "\
+# Onboarding, scene 1
+---
+ALICE: melo speed=fast
+Bob: voice=ryan
+gap: 300ms
+---
+ALICE: Welcome aboard.
+BOB: Thanks!
+  Glad to be here.
+[pause 1s]
+alice: Let's start.
+";
+
+fn line(text: &str, voice: &str, rate: f32) -> Segment {
+    Segment::Speech {
+        text: text.to_string(),
+        voice: Some(voice.to_string()),
+        speaker: Some(0),
+        rate,
+    }
+}
+
+#[test]
+fn script_compiles_to_lines_and_gaps() {
+    assert_eq!(
+        dialogue::parse(SCRIPT).unwrap(),
+        vec![
+            line("Welcome aboard.", "melo", 1.25),
+            Segment::Silence(0.3),
+            line("Thanks! Glad to be here.", "ryan", 1.0),
+            Segment::Silence(1.0),
+            line("Let's start.", "melo", 1.25),
+        ]
+    );
+}
+
+#[test]
+fn invalid_scripts_name_the_line() {
+    let cases = [
+        ("ALICE: Hi", "line 1"),
+        ("---\nALICE: amy\n", "never closed"),
+        ("---\nALICE: nobody\n---\nALICE: Hi", "Unknown voice nobody"),
+        ("---\nALICE: amy speaker=3\n---\nALICE: Hi", "1 speaker"),
+        (
+            "---\nALICE: amy\n---\nALICE: Hi\nCAROL: Hey",
+            "line 5: CAROL",
+        ),
+        ("---\nALICE: amy\n---\nHello", "line 4"),
+    ];
+    for (script, message) in cases {
+        match dialogue::parse(script) {
+            Err(BiboError::InvalidDialogue(e)) => assert!(e.contains(message), "{}", e),
+            other => panic!("{:?} for {:?}", other, script),
+        }
+    }
+}
+
+#[test]
+fn voices_are_resampled_to_the_first_line() {
+    let home = MockHome::new(&[RYAN, MELO]);
+    let input = home.path().join("scene.dialogue");
+    let output = home.path().join("scene.wav");
+    fs::write(&input, SCRIPT).unwrap();
+
+    let status = home
+        .command()
+        .args(["-q", "-i"])
+        .arg(&input)
+        .arg("-o")
+        .arg(&output)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let calls = home.calls();
+    assert_eq!(calls.len(), 3);
+    assert!(calls[0].contains("model.onnx") && calls[0].contains("--vits-length-scale=0.80"));
+    assert!(calls[1].contains("en_US-ryan-high.onnx"));
+
+    // Three 0.1s lines, a 0.3s gap, and a 1s pause at melo's 44.1 kHz
+    let audio = AudioBuffer::load(&output).unwrap();
+    assert_eq!(audio.sample_rate, 44100);
+    assert_eq!(audio.samples.len(), 3 * 4410 + 13230 + 44100);
+
+    // Piper-only casts keep the piper rate
+    fs::write(&input, "---\nBOB: ryan\n---\nBOB: Hi\nBOB: Bye").unwrap();
+    let status = home
+        .command()
+        .args(["-q", "--format", "dialogue", "-i"])
+        .arg(&input)
+        .arg("-o")
+        .arg(&output)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    assert_eq!(
+        AudioBuffer::load(&output).unwrap().sample_rate,
+        MOCK_SAMPLE_RATE
+    );
+}
@@ -0,0 +1,265 @@
+//! EPUB input: chapters from the spine and TOC, written as an audiobook
+
+mod common;
+
+use bibo::text::epub::Book;
+use bibo::AudioBuffer;
+use common::{MockHome, MOCK_SAMPLE_RATE};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+const RYAN: (&str, &str) = ("vits-piper-en_US-ryan-high", "en_US-ryan-high.onnx");
+
+const CONTAINER: &str = r#"<?xml version="1.0"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+  <rootfiles><rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/></rootfiles>
+</container>"#;
+
+const PACKAGE: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0">
+  <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+    <dc:title>The Little Book</dc:title>
+  </metadata>
+  <manifest>
+    <item id="nav" href="nav.xhtml" media-type="application/xhtml+xml" properties="nav"/>
+    <item id="cover" href="text/cover.xhtml" media-type="application/xhtml+xml"/>
+    <item id="one" href="text/one.xhtml" media-type="application/xhtml+xml"/>
+    <item id="one-b" href="text/one-b.xhtml" media-type="application/xhtml+xml"/>
+    <item id="two" href="text/chapter%202.xhtml" media-type="application/xhtml+xml"/>
+    <item id="img" href="images/cover.png" media-type="image/png"/>
+  </manifest>
+  <spine>
+    <itemref idref="cover"/>
+    <itemref idref="nav"/>
+    <itemref idref="one"/>
+    <itemref idref="one-b"/>
+    <itemref idref="two"/>
+  </spine>
+</package>"#;
+
+const NAV: &str = r#"<html xmlns:epub="http://www.idpf.org/2007/ops"><body>
+  <nav epub:type="landmarks"><ol><li><a href="text/cover.xhtml">Cover</a></li></ol></nav>
+  <nav epub:type="toc"><ol>
+    <li><a href="text/one.xhtml">One: The Start</a></li>
+    <li><a href="text/one.xhtml#part">Part of one</a></li>
+    <li><a href="text/chapter%202.xhtml">Two &amp; Last</a></li>
+  </ol></nav>
+</body></html>"#;
+
+fn chapter(body: &str) -> String {
+    format!(
+        "<?xml version=\"1.0\"?><html><head><title>Book</title></head><body>{}</body></html>",
+        body
+    )
+}
+
+/// Write an EPUB with the given files after the mimetype
+fn write_epub(path: &Path, files: &[(&str, String)]) {
+    let mut zip = ZipWriter::new(File::create(path).unwrap());
+    let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+    zip.start_file("mimetype", stored).unwrap();
+    zip.write_all(b"application/epub+zip").unwrap();
+    for (name, content) in files {
+        zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+        zip.write_all(content.as_bytes()).unwrap();
+    }
+    zip.finish().unwrap();
+}
+
+fn little_book(path: &Path) {
+    write_epub(
+        path,
+        &[
+            ("META-INF/container.xml", CONTAINER.to_string()),
+            ("OEBPS/content.opf", PACKAGE.to_string()),
+            ("OEBPS/nav.xhtml", NAV.to_string()),
+            (
+                "OEBPS/text/cover.xhtml",
+                chapter("<img src=\"../images/cover.png\" alt=\"\"/>"),
+            ),
+            (
+                "OEBPS/text/one.xhtml",
+                chapter("<section><header><h1>One</h1></header><p>It began.</p></section>"),
+            ),
+            ("OEBPS/text/one-b.xhtml", chapter("<p>It went on.</p>")),
+            (
+                "OEBPS/text/chapter 2.xhtml",
+                chapter("<h1>Two</h1><p>It ended.</p>"),
+            ),
+        ],
+    );
+}
+
+#[test]
+fn chapters_follow_the_spine_and_toc() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("little.epub");
+    little_book(&path);
+
+    let book = Book::open(&path).unwrap();
+    assert_eq!(book.title, "The Little Book");
+    let titles: Vec<&str> = book.chapters.iter().map(|c| c.title.as_str()).collect();
+    assert_eq!(titles, ["One: The Start", "Two & Last"]);
+    // A spine document without a TOC entry continues the chapter
+    assert_eq!(book.chapters[0].documents.len(), 2);
+    assert_eq!(book.chapters[0].text(), "One\n\nIt began.\n\nIt went on.");
+    assert_eq!(book.chapters[1].text(), "Two\n\nIt ended.");
+}
+
+#[test]
+fn epub2_books_take_titles_from_the_ncx() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("old.epub");
+    let package = r#"<package version="2.0"><metadata><dc:title>Old</dc:title></metadata>
+      <manifest>
+        <item id="ncx" href="toc.ncx" media-type="application/x-dtbncx+xml"/>
+        <item id="a" href="a.html" media-type="application/xhtml+xml"/>
+        <item id="b" href="b.html" media-type="application/xhtml+xml"/>
+      </manifest>
+      <spine toc="ncx"><itemref idref="a"/><itemref idref="b" linear="no"/></spine></package>"#;
+    let ncx = r#"<ncx><navMap>
+      <navPoint id="p1"><navLabel><text>Opening</text></navLabel><content src="a.html"/></navPoint>
+    </navMap></ncx>"#;
+    write_epub(
+        &path,
+        &[
+            (
+                "META-INF/container.xml",
+                CONTAINER.replace("OEBPS/content.opf", "book.opf"),
+            ),
+            ("book.opf", package.to_string()),
+            ("toc.ncx", ncx.to_string()),
+            ("a.html", chapter("<p>First words.</p>")),
+            ("b.html", chapter("<p>Footnotes.</p>")),
+        ],
+    );
+
+    let book = Book::open(&path).unwrap();
+    assert_eq!(book.title, "Old");
+    assert_eq!(book.chapters.len(), 1);
+    assert_eq!(book.chapters[0].title, "Opening");
+    assert_eq!(book.text(), "First words.");
+
+    fs::write(&path, "not a zip").unwrap();
+    assert!(Book::open(&path).is_err());
+}
+
+#[test]
+fn chapters_are_written_as_files_and_resume() {
+    let home = MockHome::new(&[RYAN]);
+    let input = home.path().join("little.epub");
+    // New directories need the trailing slash
+    let output = home.path().join("audiobook/");
+    little_book(&input);
+
+    let render = || {
+        let status = home
+            .command()
+            .args(["-v", "ryan", "-q", "--no-cache", "-i"])
+            .arg(&input)
+            .arg("-o")
+            .arg(&output)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    };
+    render();
+    let first = output.join("01 One The Start.wav");
+    let second = output.join("02 Two & Last.wav");
+    assert!(first.exists() && second.exists());
+    let calls = home.calls().len();
+
+    // Finished chapters are not rendered again
+    render();
+    assert_eq!(home.calls().len(), calls);
+    fs::remove_file(&second).unwrap();
+    render();
+    let redone = &home.calls()[calls..];
+    assert_eq!(redone.len(), 2);
+    assert!(redone[0].contains("Two") && redone[1].contains("It ended."));
+
+    // A different voice setting renders everything again
+    let status = home
+        .command()
+        .args(["-v", "ryan", "-q", "--no-cache", "-s", "fast", "-i"])
+        .arg(&input)
+        .arg("-o")
+        .arg(&output)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    assert_eq!(home.calls().len(), 2 * calls + 2);
+}
+
+#[test]
+fn other_outputs_are_not_turned_into_directories() {
+    let home = MockHome::new(&[RYAN]);
+    let input = home.path().join("little.epub");
+    little_book(&input);
+
+    for name in ["book.mp3", "book"] {
+        let output = home.path().join(name);
+        let result = home
+            .command()
+            .args(["-v", "ryan", "-q", "-i"])
+            .arg(&input)
+            .arg("-o")
+            .arg(&output)
+            .output()
+            .unwrap();
+        assert!(!result.status.success(), "-o {} was accepted", name);
+        assert!(String::from_utf8_lossy(&result.stderr).contains(".wav file or a directory"));
+        assert!(!output.exists());
+    }
+    assert!(home.calls().is_empty());
+}
+
+#[test]
+fn a_single_file_has_chapter_markers() {
+    let home = MockHome::new(&[RYAN]);
+    let input = home.path().join("little.epub");
+    let output = home.path().join("little.wav");
+    little_book(&input);
+
+    let status = home
+        .command()
+        .args(["-v", "ryan", "-q", "-i"])
+        .arg(&input)
+        .arg("-o")
+        .arg(&output)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    assert!(!home.path().join(".little.wav.parts").exists());
+
+    let audio = AudioBuffer::load(&output).unwrap();
+    assert_eq!(audio.sample_rate, MOCK_SAMPLE_RATE);
+    let bytes = fs::read(&output).unwrap();
+    let find = |needle: &[u8]| bytes.windows(needle.len()).position(|w| w == needle);
+    assert!(find(b"cue ").is_some());
+    assert!(find(b"One: The Start\0").is_some());
+    assert!(find(b"Two & Last\0").is_some());
+
+    // The second marker sits after the first chapter and the gap
+    let cue = find(b"cue ").unwrap() + 8;
+    let offset = |i: usize| {
+        let at = cue + 4 + i * 24 + 20;
+        u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap()) as usize
+    };
+    assert_eq!(offset(0), 0);
+    assert!(offset(1) > MOCK_SAMPLE_RATE as usize && offset(1) < audio.samples.len());
+
+    // --print-normalized shows each chapter
+    let printed = home
+        .command()
+        .args(["-v", "ryan", "--print-normalized", "-i"])
+        .arg(&input)
+        .output()
+        .unwrap();
+    let printed = String::from_utf8_lossy(&printed.stdout);
+    assert!(printed.starts_with("# One: The Start\n\nOne\n\nIt began."));
+    assert!(printed.contains("# Two & Last"));
+}
@@ -0,0 +1,54 @@
+//! C ABI smoke tests, calling the exported functions directly
+
+use bibo::ffi::*;
+use std::ffi::{CStr, CString};
+
+fn take(ptr: *mut std::ffi::c_char) -> String {
+    assert!(!ptr.is_null());
+    let s = unsafe { CStr::from_ptr(ptr) }.to_str().unwrap().to_string();
+    unsafe { bibo_string_free(ptr) };
+    s
+}
+
+fn c(s: &str) -> CString {
+    CString::new(s).unwrap()
+}
+
+#[test]
+fn version_matches_crate() {
+    let version = unsafe { CStr::from_ptr(bibo_version()) };
+    assert_eq!(version.to_str().unwrap(), env!("CARGO_PKG_VERSION"));
+}
+
+#[test]
+fn voices_json_lists_catalog() {
+    let voices: serde_json::Value = serde_json::from_str(&take(bibo_voices_json())).unwrap();
+    let ids: Vec<_> = voices
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v["id"].as_str().unwrap())
+        .collect();
+    assert!(ids.contains(&"melo") && ids.contains(&"ryan"));
+}
+
+#[test]
+fn clean_text_and_speed() {
+    let cleaned = unsafe { bibo_clean_text(c("## Hi **you**").as_ptr(), c("md").as_ptr()) };
+    assert_eq!(take(cleaned), "Hi you");
+
+    assert_eq!(unsafe { bibo_length_scale(c("2").as_ptr()) }, 0.5);
+    assert_eq!(unsafe { bibo_length_scale(c("warp").as_ptr()) }, -1.0);
+}
+
+#[test]
+fn failures_set_last_error() {
+    let engine = unsafe { bibo_engine_new(c("nobody").as_ptr()) };
+    assert!(engine.is_null());
+
+    let error = unsafe { CStr::from_ptr(bibo_last_error()) };
+    assert_eq!(error.to_str().unwrap(), "Voice 'nobody' not found");
+
+    let cleaned = unsafe { bibo_clean_text(c("x").as_ptr(), std::ptr::null()) };
+    assert!(cleaned.is_null());
+}
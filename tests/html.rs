@@ -0,0 +1,124 @@
+//! HTML input: main content read with the structure of Markdown
+
+mod common;
+
+use bibo::text::html;
+use bibo::text::markdown::{Block, Hint, Rules};
+use bibo::text::Segment;
+use common::{block, MockHome};
+use std::fs;
+
+const RYAN: (&str, &str) = ("vits-piper-en_US-ryan-high", "en_US-ryan-high.onnx");
+
+const PAGE: &str = r#"<!DOCTYPE html>
+<html>
+<head><title>Blog</title><style>p { color: red }</style></head>
+<body>
+  <nav><a href="/">Home</a> <a href="/about">About</a></nav>
+  <header><h1>My Blog</h1></header>
+  <main>
+    <article>
+      <header><h1>Release &ndash; v0.4</h1></header>
+      <p>Fish &amp; chips,
+         now&nbsp;with <b>bold</b> text.</p>
+      <p>No end tag
+      <p>See <a href="https://www.docs.example.com/guide">the docs</a>.
+      <ol start="2"><li>Build<li>Ship</ol>
+      <table>
+        <tr><th>Name<th>Role
+        <tr><td>Ann<td>Lead
+      </table>
+      <blockquote><p>Move fast.</p></blockquote>
+      <script>document.write("<p>not read</p>")</script>
+      <div hidden>Hidden</div>
+      <img src="cat.png" alt="A cat"><br>Caption
+    </article>
+  </main>
+  <footer>Copyright</footer>
+</body>
+</html>
+"#;
+
+#[test]
+fn main_content_is_read_with_its_structure() {
+    let item = |number| Hint::ListItem {
+        depth: 1,
+        number: Some(number),
+    };
+    let row = |hint, cells: &[&str]| Block {
+        cells: cells.iter().map(|c| c.to_string()).collect(),
+        ..block(hint, 0, &cells.join(", "))
+    };
+    assert_eq!(
+        html::parse(PAGE),
+        vec![
+            block(Hint::Heading(1), 0, "Release – v0.4"),
+            block(Hint::Paragraph, 0, "Fish & chips, now with bold text."),
+            block(Hint::Paragraph, 0, "No end tag"),
+            block(Hint::Paragraph, 0, "See the docs."),
+            block(item(2), 0, "Build"),
+            block(item(3), 0, "Ship"),
+            row(Hint::TableHeader, &["Name", "Role"]),
+            row(Hint::TableRow, &["Ann", "Lead"]),
+            block(Hint::Paragraph, 1, "Move fast."),
+            block(Hint::Paragraph, 0, "A cat\nCaption"),
+        ]
+    );
+}
+
+#[test]
+fn pages_without_main_skip_navigation_and_code() {
+    let page = "<body><nav>Menu</nav><h2>Title</h2><div>Text<pre><code class=\"language-sh\">\
+                ls -la</code></pre></div><aside>Ads</aside></body>";
+    assert_eq!(
+        html::render(page, &Rules::default()),
+        vec![
+            Segment::speech("Title"),
+            Segment::Silence(0.8),
+            Segment::speech("Text"),
+        ]
+    );
+    assert_eq!(
+        html::to_text("<p>1 &lt; 2 &#38; 3 &#x2014; R&D &bogus;</p>"),
+        "1 < 2 & 3 — R&D &bogus;"
+    );
+    // NUL, controls, surrogates, and out-of-range codes are dropped
+    assert_eq!(
+        html::decode_entities("a&#0;b&#x1b;c&#xD800;d&#1114112;e&#9;f"),
+        "abcde\tf"
+    );
+}
+
+#[test]
+fn html_files_are_spoken() {
+    let home = MockHome::new(&[RYAN]);
+    let input = home.path().join("page.htm");
+    fs::write(&input, PAGE).unwrap();
+
+    let status = home
+        .command()
+        .args(["-v", "ryan", "-q", "-o"])
+        .arg(home.path().join("page.wav"))
+        .arg("-i")
+        .arg(&input)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let calls = home.calls().join("\n");
+    assert!(calls.contains("Fish and chips"));
+    assert!(!calls.contains("Home") && !calls.contains("Copyright"));
+
+    // Pages with nothing to read are empty files
+    fs::write(
+        &input,
+        "<html><head><title>Empty</title></head><body></body></html>",
+    )
+    .unwrap();
+    let output = home
+        .command()
+        .args(["-v", "ryan", "-q", "-i"])
+        .arg(&input)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+}
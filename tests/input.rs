@@ -0,0 +1,99 @@
+//! Text input: stdin, `-i -`, and --format overriding the file extension
+
+mod common;
+
+use common::MockHome;
+use std::fs;
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+const RYAN: (&str, &str) = ("vits-piper-en_US-ryan-high", "en_US-ryan-high.onnx");
+
+const MARKDOWN: &str = "# Title\n\nSome **bold** text.\n";
+
+/// Run `cmd` with `input` piped to stdin
+fn piped(mut cmd: Command, input: &str) -> Output {
+    let mut child = cmd
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn piped_stdin_is_spoken_when_no_text_is_given() {
+    let home = MockHome::new(&[RYAN]);
+    let output = home.path().join("out.wav");
+
+    let mut cmd = home.command();
+    cmd.args(["-v", "ryan", "-q", "-o"]).arg(&output);
+    let result = piped(cmd, "Hello from a pipe.\n");
+    assert!(result.status.success());
+
+    assert!(home
+        .calls()
+        .iter()
+        .any(|call| call.contains("Hello from a pipe.")));
+    assert!(output.exists());
+}
+
+#[test]
+fn dash_input_reads_stdin_as_plain_text() {
+    let home = MockHome::new(&[RYAN]);
+
+    let mut cmd = home.command();
+    cmd.args(["-v", "ryan", "--print-normalized", "-i", "-"]);
+    let result = piped(cmd, MARKDOWN);
+    assert!(result.status.success());
+    assert!(stdout(&result).contains("Some **bold** text."));
+}
+
+#[test]
+fn format_overrides_the_extension() {
+    let home = MockHome::new(&[RYAN]);
+    let notes = home.path().join("notes.txt");
+    fs::write(&notes, MARKDOWN).unwrap();
+
+    // Markdown in a .txt file
+    let result = home
+        .command()
+        .args(["-v", "ryan", "--print-normalized", "--format", "md", "-i"])
+        .arg(&notes)
+        .output()
+        .unwrap();
+    assert!(result.status.success());
+    let spoken = stdout(&result);
+    assert!(spoken.contains("Some bold text."), "{}", spoken);
+    assert!(!spoken.contains('#'));
+
+    // A .md file read as-is
+    let readme = home.path().join("README.md");
+    fs::write(&readme, MARKDOWN).unwrap();
+    let result = home
+        .command()
+        .args(["-v", "ryan", "--print-normalized", "--format", "txt", "-i"])
+        .arg(&readme)
+        .output()
+        .unwrap();
+    assert!(result.status.success());
+    assert!(stdout(&result).contains("Some **bold** text."));
+
+    // Markdown from stdin
+    let mut cmd = home.command();
+    cmd.args(["-v", "ryan", "--print-normalized", "--format", "md"]);
+    let result = piped(cmd, MARKDOWN);
+    assert!(result.status.success());
+    assert!(stdout(&result).contains("Some bold text."));
+}
@@ -0,0 +1,97 @@
+//! Language detection and per-language voice routing
+
+mod common;
+
+use bibo::text::language::{detect, Languages};
+use bibo::text::normalize::Lang;
+use bibo::text::Segment;
+use common::{MockHome, MOCK_SAMPLE_RATE};
+use std::fs;
+
+const RYAN: (&str, &str) = ("vits-piper-en_US-ryan-high", "en_US-ryan-high.onnx");
+const MELO: (&str, &str) = ("vits-melo-tts-zh_en", "model.onnx");
+
+#[test]
+fn text_splits_into_language_runs() {
+    let runs = detect(
+        "你好, 我们用 GPU 训练. 안녕하세요! Привет, мир. Das ist nicht gut. It is 3.5 now.",
+        Lang::En,
+    );
+    assert_eq!(
+        runs,
+        vec![
+            (Lang::Zh, "你好, 我们用 GPU 训练. ".to_string()),
+            (Lang::Ko, "안녕하세요! ".to_string()),
+            (Lang::Ru, "Привет, мир. ".to_string()),
+            (Lang::De, "Das ist nicht gut. ".to_string()),
+            (Lang::En, "It is 3.5 now.".to_string()),
+        ]
+    );
+    // Nothing tells these apart, so the voice's own language is assumed
+    assert_eq!(
+        detect("Guten Tag", Lang::De),
+        vec![(Lang::De, "Guten Tag".to_string())]
+    );
+}
+
+#[test]
+fn runs_are_routed_to_voices() {
+    let languages = Languages {
+        detect: true,
+        voices: [("ko".to_string(), "kss".to_string())].into(),
+    };
+    let speech = |text: &str, voice: Option<&str>, speaker| Segment::Speech {
+        text: text.to_string(),
+        voice: voice.map(String::from),
+        speaker,
+        rate: 1.0,
+    };
+    let routed = languages.route(
+        vec![
+            Segment::speech("Hello there. 안녕하세요. Привет!"),
+            Segment::Silence(0.5),
+            speech("안녕", Some("melo"), None),
+        ],
+        "ryan",
+    );
+    assert_eq!(
+        routed,
+        vec![
+            speech("Hello there.", None, None),
+            speech("안녕하세요.", Some("kss"), Some(0)),
+            speech("Привет!", Some("irina"), Some(0)),
+            Segment::Silence(0.5),
+            speech("안녕", Some("melo"), None),
+        ]
+    );
+    // A bilingual voice keeps both of its languages
+    assert_eq!(languages.voice_for(Lang::En, "melo"), "melo");
+    assert_eq!(languages.voice_for(Lang::Zh, "ryan"), "melo");
+}
+
+#[test]
+fn detect_lang_speaks_each_run_in_its_voice() {
+    let home = MockHome::new(&[RYAN, MELO]);
+    let config = home.path().join("config.toml");
+    fs::write(&config, "[languages.voices]\nzh = \"melo\"\n").unwrap();
+
+    let wav = home.path().join("out.wav");
+    let status = home
+        .command()
+        .env("BIBO_CONFIG", &config)
+        .args(["-v", "ryan", "-q", "--detect-lang", "-o"])
+        .arg(&wav)
+        .arg("Good morning. 早上好")
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let calls = home.calls();
+    assert_eq!(calls.len(), 2);
+    assert!(calls[0].contains("en_US-ryan") && calls[0].ends_with("Good morning."));
+    assert!(calls[1].contains("vits-melo-tts") && calls[1].ends_with("早上好"));
+    // Melo's 44.1 kHz audio is resampled to the first voice's rate
+    let audio = bibo::AudioBuffer::load(&wav).unwrap();
+    assert_eq!(audio.sample_rate, MOCK_SAMPLE_RATE);
+    assert_eq!(audio.samples.len(), MOCK_SAMPLE_RATE as usize / 10 * 2);
+}
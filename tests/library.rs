@@ -0,0 +1,113 @@
+//! Public library API, used without the CLI
+
+use bibo::audio::WavFileWriter;
+use bibo::tts::parse_length_scale;
+use bibo::{clean_markdown, AudioBuffer, BiboError, InputFormat, Speed, VoiceDownloader};
+
+#[test]
+fn speed_presets_and_rates() {
+    assert_eq!("FAST".parse::<Speed>().unwrap(), Speed::Fast);
+    assert_eq!(parse_length_scale("slow").unwrap(), 1.2);
+    assert_eq!(parse_length_scale("2x").unwrap(), 0.5);
+    assert!(matches!(
+        parse_length_scale("9"),
+        Err(BiboError::InvalidSpeed(_))
+    ));
+}
+
+#[test]
+fn markdown_preprocessing() {
+    assert_eq!(InputFormat::from_extension("MD"), Some(InputFormat::Md));
+    assert_eq!(InputFormat::from_extension("pdf"), None);
+    assert_eq!(
+        clean_markdown("# Title\n\nSome **bold** [link](https://x.dev)."),
+        "Title\n\nSome bold link."
+    );
+}
+
+#[test]
+fn download_specs_resolve_against_catalog() {
+    let voices = VoiceDownloader::resolve_spec("1,3").unwrap();
+    assert_eq!(voices.len(), 2);
+    assert_eq!(VoiceDownloader::resolve_spec("Ryan").unwrap()[0].id, "ryan");
+    assert!(VoiceDownloader::resolve_spec("0").is_err());
+    assert!(VoiceDownloader::resolve_spec("nobody").is_err());
+}
+
+#[test]
+fn audio_buffer_round_trips_through_wav() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("clip.wav");
+
+    let mut audio = AudioBuffer::new(vec![100, -100, 200], 22050);
+    audio.append(&AudioBuffer::silence(0.01, 22050));
+    audio.save(&path).unwrap();
+
+    let loaded = AudioBuffer::load(&path).unwrap();
+    assert_eq!(loaded.sample_rate, 22050);
+    assert_eq!(loaded.samples, audio.samples);
+}
+
+#[test]
+fn resample_changes_rate_and_length() {
+    let audio = AudioBuffer::new(vec![0, 100, 200, 300], 22050);
+    let up = audio.resample(44100);
+    assert_eq!(up.sample_rate, 44100);
+    assert_eq!(up.samples.len(), 8);
+    assert_eq!(up.samples[..3], [0, 50, 100]);
+    assert_eq!(audio.resample(22050).samples, audio.samples);
+}
+
+#[test]
+fn downsampling_filters_what_the_new_rate_cannot_hold() {
+    let tone = |freq: f64| {
+        let samples = (0..4410)
+            .map(|i| {
+                let t = i as f64 / 44100.0;
+                (10000.0 * (2.0 * std::f64::consts::PI * freq * t).sin()) as i16
+            })
+            .collect();
+        AudioBuffer::new(samples, 44100).resample(22050)
+    };
+    let peak = |audio: &AudioBuffer| {
+        audio.samples[100..audio.samples.len() - 100]
+            .iter()
+            .map(|s| s.unsigned_abs())
+            .max()
+            .unwrap()
+    };
+
+    // 15 kHz is above the new 11 kHz Nyquist and would alias to 7 kHz
+    let high = tone(15000.0);
+    assert_eq!(high.samples.len(), 2205);
+    assert!(peak(&high) < 500, "peak {}", peak(&high));
+
+    // 1 kHz passes through
+    assert!(peak(&tone(1000.0)) > 9000);
+
+    // A constant stays constant up to the edges
+    let flat = AudioBuffer::new(vec![1234; 100], 48000).resample(16000);
+    assert!(flat.samples.iter().all(|&s| s == 1234));
+}
+
+#[test]
+fn wav_file_writer_streams_clips_with_markers() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("book.wav");
+
+    let mut writer = WavFileWriter::create(&path, 22050).unwrap();
+    writer
+        .append(&AudioBuffer::new(vec![1, 2, 3], 22050))
+        .unwrap();
+    // Other rates are resampled to the file's
+    writer.append(&AudioBuffer::new(vec![5; 8], 44100)).unwrap();
+    assert_eq!(writer.samples_written(), 7);
+    writer.finish(&[(0, "One"), (3, "Two")]).unwrap();
+
+    let loaded = AudioBuffer::load(&path).unwrap();
+    assert_eq!(loaded.samples, vec![1, 2, 3, 5, 5, 5, 5]);
+    let bytes = std::fs::read(&path).unwrap();
+    let riff_size = u32::from_le_bytes(bytes[4..8].try_into().unwrap());
+    assert_eq!(riff_size as usize, bytes.len() - 8);
+    assert!(bytes.windows(4).any(|w| w == b"Two\0"));
+}
@@ -0,0 +1,75 @@
+//! Streamed lines: include/exclude filters and queue overflow policies
+
+use bibo::input::lines::{LineFilter, LineQueue, Overflow};
+use bibo::BiboError;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+fn patterns(list: &[&str]) -> Vec<String> {
+    list.iter().map(|p| p.to_string()).collect()
+}
+
+/// Everything left in a closed queue
+fn drain(queue: &LineQueue) -> Vec<String> {
+    queue.close();
+    std::iter::from_fn(|| queue.pop()).collect()
+}
+
+#[test]
+fn filter_keeps_matches_and_drops_exclusions() {
+    let filter = LineFilter::new(&patterns(&["ERROR", "WARN"]), &patterns(&["ignored"])).unwrap();
+    assert!(filter.accepts("ERROR: disk full"));
+    assert!(filter.accepts("WARN low memory"));
+    assert!(!filter.accepts("INFO started"));
+    assert!(!filter.accepts("ERROR ignored on purpose"));
+
+    // No includes: everything but exclusions and blank lines
+    let filter = LineFilter::new(&[], &patterns(&["^DEBUG"])).unwrap();
+    assert!(filter.accepts("anything"));
+    assert!(!filter.accepts("DEBUG noise"));
+    assert!(!filter.accepts("   "));
+}
+
+#[test]
+fn invalid_pattern_is_a_config_error() {
+    assert!(matches!(
+        LineFilter::new(&patterns(&["("]), &[]),
+        Err(BiboError::ConfigError(e)) if e.contains("(")
+    ));
+}
+
+#[test]
+fn drop_oldest_keeps_the_newest_lines() {
+    let queue = LineQueue::new(2, Overflow::DropOldest);
+    assert_eq!(queue.push("a".into()), 0);
+    assert_eq!(queue.push("b".into()), 0);
+    assert_eq!(queue.push("c".into()), 1);
+    assert_eq!(drain(&queue), ["b", "c"]);
+}
+
+#[test]
+fn latest_discards_the_backlog() {
+    let queue = LineQueue::new(2, Overflow::Latest);
+    queue.push("a".into());
+    queue.push("b".into());
+    assert_eq!(queue.push("c".into()), 2);
+    assert_eq!(drain(&queue), ["c"]);
+}
+
+#[test]
+fn block_waits_for_room() {
+    let queue = Arc::new(LineQueue::new(1, Overflow::Block));
+    queue.push("a".into());
+
+    let writer = {
+        let queue = Arc::clone(&queue);
+        thread::spawn(move || queue.push("b".into()))
+    };
+    thread::sleep(Duration::from_millis(100));
+    assert!(!writer.is_finished());
+
+    assert_eq!(queue.pop().as_deref(), Some("a"));
+    assert_eq!(writer.join().unwrap(), 0);
+    assert_eq!(drain(&queue), ["b"]);
+}
@@ -0,0 +1,263 @@
+//! Markdown to speech: what is spoken and the structure it is tagged with
+
+mod common;
+
+use bibo::text::code::{self, CodeMode};
+use bibo::text::markdown::{self, Block, Hint, LinkMode, Rules};
+use bibo::text::Segment;
+use bibo::{clean_markdown, AudioBuffer, Config};
+use common::{block, MockHome, MOCK_SAMPLE_RATE};
+use std::fs;
+
+const RYAN: (&str, &str) = ("vits-piper-en_US-ryan-high", "en_US-ryan-high.onnx");
+
+fn row(hint: Hint, cells: &[&str]) -> Block {
+    Block {
+        cells: cells.iter().map(|c| c.to_string()).collect(),
+        ..block(hint, 0, &cells.join(", "))
+    }
+}
+
+fn speech(text: &str) -> Segment {
+    Segment::speech(text)
+}
+
+#[test]
+fn every_heading_and_inline_markup_is_stripped() {
+    let md = "\
+---
+title: Notes
+---
+
+# Title
+
+Some ***nested _emphasis_*** and `code`, ~~struck~~.
+
+## Second [ref link][docs]
+
+Line one
+line two<br>![diagram](d.png) here.[^1]
+
+[docs]: https://docs.example.com
+[^1]: A footnote that is not read.
+";
+    assert_eq!(
+        clean_markdown(md),
+        "Title\n\nSome nested emphasis and code, struck.\n\nSecond ref link\n\n\
+         Line one line two diagram here."
+    );
+}
+
+#[test]
+fn code_and_html_blocks_are_not_spoken() {
+    let md = "Before\n\n```rust\nfn main() {}\n```\n\n<div>\n<b>raw</b>\n</div>\n\nAfter";
+    assert_eq!(clean_markdown(md), "Before\n\nAfter");
+}
+
+#[test]
+fn code_blocks_are_read_per_mode() {
+    let md = "Run:\n\n```rust,ignore\nlet maxLen = parse_http(req)?;\nok()\n```\n\n    indented\n";
+    let render = |code| {
+        let rules = Rules {
+            code,
+            paragraph_pause: 0.0,
+            ..Rules::default()
+        };
+        markdown::render(md, &rules)
+    };
+    assert_eq!(render(CodeMode::Skip), vec![speech("Run:")]);
+    assert_eq!(
+        render(CodeMode::Announce),
+        vec![speech(
+            "Run:\n\nCode block in Rust, 2 lines, skipped.\n\nCode block, 1 line, skipped."
+        )]
+    );
+    assert_eq!(
+        render(CodeMode::Verbatim),
+        vec![speech(
+            "Run:\n\nlet maxLen = parse_http(req)?;\nok()\n\nindented"
+        )]
+    );
+    assert_eq!(
+        render(CodeMode::Identifiers),
+        vec![speech(
+            "Run:\n\nlet max len equals parse http open paren req close paren \
+             question mark semicolon\nok open paren close paren\n\nindented"
+        )]
+    );
+}
+
+#[test]
+fn identifiers_split_into_words() {
+    let cases = [
+        ("parseHTTPRequest", "parse http request"),
+        ("MAX_RETRY_COUNT", "max retry count"),
+        ("__init__", "init"),
+        ("utf8Decoder", "utf8 decoder"),
+        ("v2", "v2"),
+    ];
+    for (identifier, words) in cases {
+        assert_eq!(code::split_identifier(identifier).join(" "), words);
+    }
+    assert_eq!(
+        code::speak_line("fn run(&self) -> Result<()> {"),
+        "fn run open paren ampersand self close paren arrow result less than \
+         open paren close paren greater than open brace"
+    );
+}
+
+#[test]
+fn blocks_carry_structure() {
+    let md = "\
+# Setup
+
+1. Install
+2. Configure
+   - nested *bullet*
+
+   Second paragraph of item two.
+
+> Quoted text
+>
+> > Nested quote
+
+| Name | Role |
+|------|------|
+| Ann  | Lead |
+
+- [x] Done task
+";
+    let item = |depth, number| Hint::ListItem { depth, number };
+    assert_eq!(
+        markdown::parse(md),
+        vec![
+            block(Hint::Heading(1), 0, "Setup"),
+            block(item(1, Some(1)), 0, "Install"),
+            block(item(1, Some(2)), 0, "Configure"),
+            block(item(2, None), 0, "nested bullet"),
+            block(Hint::Paragraph, 0, "Second paragraph of item two."),
+            block(Hint::Paragraph, 1, "Quoted text"),
+            block(Hint::Paragraph, 2, "Nested quote"),
+            row(Hint::TableHeader, &["Name", "Role"]),
+            row(Hint::TableRow, &["Ann", "Lead"]),
+            block(item(1, None), 0, "Done task"),
+        ]
+    );
+}
+
+const DOC: &str = "\
+# Release
+
+Steps:
+
+1. Build
+2. Ship
+
+> Move fast.
+
+| Name | Role |
+|------|------|
+| Ann  | Lead |
+| Bo   |      |
+
+See [the docs](https://www.docs.example.com/guide) or <https://example.org/x>.
+";
+
+#[test]
+fn structure_is_read_with_pauses_and_announcements() {
+    assert_eq!(
+        markdown::render(DOC, &Rules::default()),
+        vec![
+            speech("Release"),
+            Segment::Silence(0.8),
+            speech("Steps:"),
+            Segment::Silence(0.5),
+            speech("First, Build"),
+            Segment::Silence(0.3),
+            speech("Second, Ship"),
+            Segment::Silence(0.3),
+            speech("Quote. Move fast. End quote."),
+            Segment::Silence(0.5),
+            speech("Name: Ann, Role: Lead"),
+            Segment::Silence(0.5),
+            speech("Name: Bo"),
+            Segment::Silence(0.5),
+            speech("See the docs or https://example.org/x."),
+        ]
+    );
+}
+
+#[test]
+fn rules_turn_features_off_and_links_into_domains() {
+    let rules = Rules {
+        heading_pause: 0.0,
+        paragraph_pause: 0.0,
+        item_pause: 0.0,
+        ordinals: false,
+        table_headers: false,
+        quote_start: String::new(),
+        quote_end: String::new(),
+        links: LinkMode::Domain,
+        code: CodeMode::Skip,
+    };
+    assert_eq!(
+        markdown::render(DOC, &rules),
+        vec![speech(
+            "Release\n\nSteps:\n\nBuild\n\nShip\n\nMove fast.\n\n\
+             Name, Role\n\nAnn, Lead\n\nBo\n\n\
+             See the docs (docs.example.com) or example.org."
+        )]
+    );
+}
+
+#[test]
+fn config_file_sets_rules() {
+    let home = MockHome::new(&[RYAN]);
+    let config = home.path().join("config.toml");
+    fs::write(
+        &config,
+        "[markdown]\nheading_pause = 2\nlinks = \"domain\"\n",
+    )
+    .unwrap();
+    std::env::set_var("BIBO_CONFIG", &config);
+    let loaded = Config::load();
+    std::env::remove_var("BIBO_CONFIG");
+
+    let rules = loaded.unwrap().markdown;
+    assert_eq!(rules.heading_pause, 2.0);
+    assert_eq!(rules.links, LinkMode::Domain);
+    assert!(rules.ordinals);
+
+    // Markdown input is synthesized block by block with pauses between
+    let input = home.path().join("doc.md");
+    let output = home.path().join("doc.wav");
+    fs::write(&input, "# Title\n\nBody text.\n\n```\ncode\n```\n").unwrap();
+    let status = home
+        .command()
+        .env("BIBO_CONFIG", &config)
+        .args(["-v", "ryan", "-q", "-i"])
+        .arg(&input)
+        .arg("-o")
+        .arg(&output)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    assert_eq!(home.calls().len(), 2);
+    let audio = AudioBuffer::load(&output).unwrap();
+    assert_eq!(
+        audio.samples.len(),
+        2 * 2205 + 2 * MOCK_SAMPLE_RATE as usize
+    );
+
+    // Unknown settings are reported
+    fs::write(&config, "[markdown]\nheading_pauses = 2\n").unwrap();
+    let output = home
+        .command()
+        .env("BIBO_CONFIG", &config)
+        .args(["-v", "ryan", "-q", "-i"])
+        .arg(&input)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("heading_pauses"));
+}
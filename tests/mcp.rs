@@ -0,0 +1,166 @@
+//! MCP stdio server tests against the mock backend
+
+#![cfg(unix)]
+
+mod common;
+
+use common::MockHome;
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Stdio};
+use std::time::{Duration, Instant};
+
+const RYAN: (&str, &str) = ("vits-piper-en_US-ryan-high", "en_US-ryan-high.onnx");
+
+struct McpClient {
+    child: Child,
+    stdin: Option<ChildStdin>,
+    stdout: BufReader<ChildStdout>,
+    next_id: u64,
+}
+
+impl McpClient {
+    #[allow(clippy::zombie_processes)] // reaped in Drop
+    fn start(home: &MockHome) -> Self {
+        let mut child = home
+            .command()
+            .args(["mcp", "-v", "ryan"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        let stdin = child.stdin.take();
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+        Self {
+            child,
+            stdin,
+            stdout,
+            next_id: 1,
+        }
+    }
+
+    fn send(&mut self, message: Value) {
+        let stdin = self.stdin.as_mut().unwrap();
+        writeln!(stdin, "{}", message).unwrap();
+        stdin.flush().unwrap();
+    }
+
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.send(json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }));
+
+        let mut line = String::new();
+        self.stdout.read_line(&mut line).unwrap();
+        let response: Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(response["id"], id);
+        response
+    }
+
+    fn call_tool(&mut self, name: &str, arguments: Value) -> Value {
+        self.request(
+            "tools/call",
+            json!({ "name": name, "arguments": arguments }),
+        )["result"]
+            .clone()
+    }
+}
+
+impl Drop for McpClient {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn initialized(home: &MockHome) -> McpClient {
+    let mut client = McpClient::start(home);
+    let init = client.request(
+        "initialize",
+        json!({ "protocolVersion": "2025-06-18", "capabilities": {}, "clientInfo": { "name": "test", "version": "0" } }),
+    );
+    assert_eq!(init["result"]["protocolVersion"], "2025-06-18");
+    assert_eq!(init["result"]["serverInfo"]["name"], "bibo");
+    client.send(json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }));
+    client
+}
+
+#[test]
+fn lists_tools() {
+    let home = MockHome::new(&[RYAN]);
+    let mut client = initialized(&home);
+
+    let tools = client.request("tools/list", json!({}));
+    let names: Vec<_> = tools["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["name"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(names, ["speak", "list_voices", "synthesize_to_file"]);
+}
+
+#[test]
+fn list_voices_reports_installed() {
+    let home = MockHome::new(&[RYAN]);
+    let mut client = initialized(&home);
+
+    let result = client.call_tool("list_voices", json!({}));
+    let voices: Value =
+        serde_json::from_str(result["content"][0]["text"].as_str().unwrap()).unwrap();
+    let ryan = voices
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|v| v["id"] == "ryan")
+        .unwrap();
+    assert_eq!(ryan["installed"], true);
+    assert_eq!(ryan["default"], true);
+}
+
+#[test]
+fn synthesize_to_file_writes_wav() {
+    let home = MockHome::new(&[RYAN]);
+    let mut client = initialized(&home);
+    let path = home.path().join("out.wav");
+
+    let result = client.call_tool(
+        "synthesize_to_file",
+        json!({ "text": "Tests passed", "path": path, "speed": 1.25 }),
+    );
+    assert_eq!(result["isError"], false);
+    assert!(path.exists());
+    assert!(home.calls()[0].contains("--vits-length-scale=0.80"));
+}
+
+#[test]
+fn speak_returns_without_waiting_for_playback() {
+    let home = MockHome::new(&[RYAN]);
+    let mut client = initialized(&home);
+
+    let started = Instant::now();
+    let result = client.call_tool("speak", json!({ "text": "Build finished" }));
+    assert_eq!(result["isError"], false);
+    assert!(result["content"][0]["text"]
+        .as_str()
+        .unwrap()
+        .contains("Queued"));
+    assert!(started.elapsed() < Duration::from_secs(2));
+}
+
+#[test]
+fn tool_errors_are_reported_in_result() {
+    let home = MockHome::new(&[RYAN]);
+    let mut client = initialized(&home);
+
+    let result = client.call_tool("speak", json!({ "text": "Hi", "voice": "amy" }));
+    assert_eq!(result["isError"], true);
+    assert!(result["content"][0]["text"]
+        .as_str()
+        .unwrap()
+        .contains("not installed"));
+
+    let response = client.request("resources/list", json!({}));
+    assert_eq!(response["error"]["code"], -32601);
+}
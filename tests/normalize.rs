@@ -0,0 +1,357 @@
+//! Text normalization: numbers, dates, times, and money read as words
+
+mod common;
+
+use bibo::text::normalize::symbols::{Rules, SymbolMode, UrlMode};
+use bibo::text::normalize::{normalize, normalize_with, Dictionaries, Normalization};
+use bibo::BiboError;
+use common::MockHome;
+use std::fs;
+
+const RYAN: (&str, &str) = ("vits-piper-en_US-ryan-high", "en_US-ryan-high.onnx");
+
+#[test]
+fn english_formats_are_spelled_out() {
+    let cases = [
+        ("2025-03-14", "March fourteenth, twenty twenty-five"),
+        (
+            "$1,299.99",
+            "one thousand two hundred ninety-nine dollars and ninety-nine cents",
+        ),
+        ("3:45 pm", "three forty-five p m"),
+        ("9:05", "nine oh five"),
+        ("1st and 22nd", "first and twenty-second"),
+        ("10%", "ten percent"),
+        ("v0.3.0", "version zero point three point zero"),
+        ("-5 and 3-5", "minus five and three-five"),
+        ("£1 or 50 €", "one pound or fifty euros"),
+        ("agent 007", "agent zero zero seven"),
+        ("mp3 and x86", "mp3 and x86"),
+        ("1e10 and 3D", "1e10 and 3D"),
+        ("1,2,3", "one,two,three"),
+        ("Page 3. Next", "Page three. Next"),
+        (
+            "1999 and 2005",
+            "one thousand nine hundred ninety-nine and two thousand five",
+        ),
+    ];
+    for (text, spoken) in cases {
+        assert_eq!(normalize(text, "en_US"), spoken, "{}", text);
+    }
+}
+
+#[test]
+fn each_catalog_language_has_its_own_words() {
+    let cases = [
+        (
+            "de_DE",
+            "2025-03-14",
+            "vierzehnter März zweitausendfünfundzwanzig",
+        ),
+        (
+            "de_DE",
+            "1.234,5 €",
+            "eintausendzweihundertvierunddreißig Komma fünf Euro",
+        ),
+        ("de_DE", "15:30", "fünfzehn Uhr dreißig"),
+        (
+            "de_DE",
+            "am 3. Mai, bis 21. Juni",
+            "am dritten Mai, bis einundzwanzigsten Juni",
+        ),
+        ("de_DE", "Es sind 3. oder 4.", "Es sind drei. oder vier."),
+        (
+            "fr_FR",
+            "1er mai, 71 %",
+            "premier mai, soixante et onze pour cent",
+        ),
+        (
+            "fr_FR",
+            "2025-03-14",
+            "le quatorze mars deux mille vingt-cinq",
+        ),
+        (
+            "es_ES",
+            "$1,299.99",
+            "mil doscientos noventa y nueve dólares con noventa y nueve centavos",
+        ),
+        (
+            "es_ES",
+            "3:45 pm",
+            "las tres y cuarenta y cinco de la tarde",
+        ),
+        (
+            "ru_RU",
+            "2025-03-14",
+            "четырнадцатое марта две тысячи двадцать пятого года",
+        ),
+        ("ru_RU", "21 ₽", "двадцать один рубль"),
+        ("ru_RU", "3-го", "третьего"),
+        ("ko_KR", "3:45 pm", "오후 세 시 사십오 분"),
+        ("ko_KR", "₩15000", "만 오천 원"),
+        ("zh_CN", "10005", "一万零五"),
+        (
+            "zh_CN",
+            "2025-03-14 3:05 pm",
+            "二零二五年三月十四日 下午三点零五分",
+        ),
+        ("vi_VN", "1005", "một nghìn không trăm lẻ năm"),
+        ("vi_VN", "25%", "hai mươi lăm phần trăm"),
+    ];
+    for (locale, text, spoken) in cases {
+        assert_eq!(normalize(text, locale), spoken, "{} {}", locale, text);
+    }
+}
+
+#[test]
+fn bilingual_voices_follow_the_text() {
+    assert_eq!(normalize("价格是¥5, 10%", "zh_en"), "价格是五元, 百分之十");
+    assert_eq!(normalize("It costs $5", "zh_en"), "It costs five dollars");
+}
+
+#[test]
+fn engine_speaks_normalized_text() {
+    let home = MockHome::new(&[RYAN]);
+
+    let output = home
+        .command()
+        .args([
+            "-v",
+            "ryan",
+            "--print-normalized",
+            "Due 2025-03-14, 10% off",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Due March fourteenth, twenty twenty-five, ten percent off\n"
+    );
+    assert!(home.calls().is_empty());
+
+    let wav = home.path().join("out.wav");
+    let status = home
+        .command()
+        .args(["-v", "ryan", "-q", "-o"])
+        .arg(&wav)
+        .arg("Version v1.2.0")
+        .status()
+        .unwrap();
+    assert!(status.success());
+    assert!(home.calls()[0].contains("Version version one point two point zero"));
+}
+
+#[test]
+fn abbreviations_units_and_acronyms_are_expanded() {
+    let cases = [
+        (
+            "en_US",
+            "Use e.g. the GPU API, etc.",
+            "Use for example the G P U A P I, et cetera.",
+        ),
+        (
+            "en_US",
+            "1 MB at 120 km/h, 2.5 GB",
+            "one megabyte at one hundred twenty kilometers per hour, two point five gigabytes",
+        ),
+        ("en_US", "1 ft or 3 ft", "one foot or three feet"),
+        (
+            "en_US",
+            "NASA, JSON, HTTPS, and APIs",
+            "NASA, JSON, H T T P S, and A P I's",
+        ),
+        ("en_US", "THE END of HTTPServer", "THE END of HTTPServer"),
+        (
+            "de_DE",
+            "z.B. 1 h, 1 km",
+            "zum Beispiel eine Stunde, ein Kilometer",
+        ),
+        (
+            "fr_FR",
+            "21 min, 1 km, 0 km",
+            "vingt et une minutes, un kilomètre, zéro kilomètre",
+        ),
+        ("es_ES", "1 h, 21 km", "una hora, veintiún kilómetros"),
+        (
+            "ru_RU",
+            "1 мин, 2 мин, 5 км/ч",
+            "одна минута, две минуты, пять километров в час",
+        ),
+        ("zh_CN", "5 km", "五公里"),
+    ];
+    for (locale, text, spoken) in cases {
+        assert_eq!(normalize(text, locale), spoken, "{} {}", locale, text);
+    }
+}
+
+#[test]
+fn user_dictionaries_extend_the_builtins() {
+    let home = MockHome::new(&[RYAN]);
+    let config = home.path().join("config.toml");
+    let dictionaries = home.path().join("dictionaries");
+    fs::create_dir(&dictionaries).unwrap();
+    fs::write(
+        dictionaries.join("en.toml"),
+        r#"
+[abbreviations]
+"k8s" = "kubernetes"
+
+[units]
+"rpm" = ["revolution per minute", "revolutions per minute"]
+
+[acronyms]
+spell = ["ONNX"]
+words = ["GPU"]
+"#,
+    )
+    .unwrap();
+
+    let output = home
+        .command()
+        .env("BIBO_CONFIG", &config)
+        .args(["-v", "ryan", "--print-normalized", "k8s ONNX GPU at 1 rpm"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "kubernetes O N N X GPU at one revolution per minute\n"
+    );
+
+    fs::write(dictionaries.join("en.toml"), "[units]\nrpm = 5\n").unwrap();
+    let output = home
+        .command()
+        .env("BIBO_CONFIG", &config)
+        .args(["-v", "ryan", "--print-normalized", "hello"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("en.toml"));
+}
+
+/// Built-in dictionaries with the given symbol rules
+fn with_symbols(symbols: Rules) -> Normalization {
+    Normalization {
+        symbols,
+        ..Normalization::default()
+    }
+}
+
+#[test]
+fn library_callers_pass_their_own_dictionaries() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("en.toml"),
+        "[abbreviations]\n\"k8s\" = \"kubernetes\"\n",
+    )
+    .unwrap();
+    let normalization = Normalization {
+        dictionaries: Dictionaries::load(dir.path()).unwrap(),
+        ..Normalization::default()
+    };
+    assert_eq!(
+        normalize_with("k8s on 2 GPUs", "en_US", &normalization),
+        "kubernetes on two G P U's"
+    );
+    // Without them only the built-ins apply
+    assert_eq!(normalize("k8s", "en_US"), "k8s");
+
+    fs::write(dir.path().join("de.toml"), "[units]\nrpm = 5\n").unwrap();
+    assert!(matches!(
+        Dictionaries::load(dir.path()),
+        Err(BiboError::ConfigError(e)) if e.contains("de.toml")
+    ));
+}
+
+#[test]
+fn emoji_symbols_and_urls_are_read_as_words() {
+    let described = with_symbols(Rules::default());
+    let cases = [
+        ("Ship it 🚀🚀🚀 👍🏽!", "Ship it rocket thumbs up!"),
+        ("a->b && c != d", "a to b and c not equal to d"),
+        ("2×3 ≤ 6", "two times three less than or equal to six"),
+        (
+            "See https://github.com/larrykoo711/bibo?tab=readme.",
+            "See github dot com.",
+        ),
+        ("Or www.docs.rs, maybe", "Or docs dot rs, maybe"),
+        (
+            "Mail ann.lee@example.com",
+            "Mail ann dot lee at example dot com",
+        ),
+    ];
+    for (text, spoken) in cases {
+        assert_eq!(
+            normalize_with(text, "en_US", &described),
+            spoken,
+            "{}",
+            text
+        );
+    }
+    assert_eq!(normalize_with("好的👍", "zh", &described), "好的拇指向上");
+    assert_eq!(
+        normalize_with("Super 👍 -> www.heise.de", "de_DE", &described),
+        "Super Daumen hoch zu heise Punkt de"
+    );
+
+    let full = with_symbols(Rules {
+        urls: UrlMode::Describe,
+        ..Rules::default()
+    });
+    assert_eq!(
+        normalize_with(
+            "https://github.com/larrykoo711/bibo/blob/main/README.md",
+            "en_US",
+            &full
+        ),
+        "github dot com slash larrykoo711 slash bibo slash blob slash main slash README dot md"
+    );
+
+    let dropped = with_symbols(Rules {
+        emoji: SymbolMode::Drop,
+        symbols: SymbolMode::Drop,
+        urls: UrlMode::Drop,
+    });
+    assert_eq!(
+        normalize_with("Done ✅ a->b, see https://docs.rs", "en_US", &dropped),
+        "Done a b, see"
+    );
+}
+
+#[test]
+fn symbol_rules_come_from_the_config_file() {
+    let home = MockHome::new(&[RYAN]);
+    let config = home.path().join("config.toml");
+    fs::write(
+        &config,
+        "[symbols]\nemoji = \"drop\"\nurls = \"describe\"\n",
+    )
+    .unwrap();
+
+    let output = home
+        .command()
+        .env("BIBO_CONFIG", &config)
+        .args([
+            "-v",
+            "ryan",
+            "--print-normalized",
+            "Nice 🎉 http://example.com/docs",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Nice example dot com slash docs\n"
+    );
+
+    fs::write(&config, "[symbols]\nemoji = \"shout\"\n").unwrap();
+    let output = home
+        .command()
+        .env("BIBO_CONFIG", &config)
+        .args(["-v", "ryan", "--print-normalized", "hello"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+}
@@ -0,0 +1,85 @@
+//! User pronunciations: lexicon overlays and respelling
+
+mod common;
+
+use common::MockHome;
+use std::fs;
+
+const RYAN: (&str, &str) = ("vits-piper-en_US-ryan-high", "en_US-ryan-high.onnx");
+const MELO: (&str, &str) = ("vits-melo-tts-zh_en", "model.onnx");
+
+const PRONUNCIATIONS: &str = r#"
+[en]
+bibo = "bee bo"
+sherpa = { respell = "sher pa", phonemes = "sh er p a" }
+Jan = "yahn"
+"#;
+
+/// Speak `text` with `voice` and return the mock sherpa call
+fn speak(home: &MockHome, voice: &str, text: &str) -> std::process::Output {
+    home.command()
+        .env("BIBO_CONFIG", home.path().join("config.toml"))
+        .args(["-v", voice, "-q", "-o"])
+        .arg(home.path().join("out.wav"))
+        .arg(text)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn espeak_voices_read_respellings() {
+    let home = MockHome::new(&[RYAN]);
+    fs::write(home.path().join("pronunciations.toml"), PRONUNCIATIONS).unwrap();
+
+    assert!(speak(&home, "ryan", "Ask Bibo, Jan, not bibos")
+        .status
+        .success());
+    let call = &home.calls()[0];
+    assert!(call.contains("Ask bee bo, yahn, not bibos"), "{}", call);
+    assert!(!call.contains("--vits-lexicon"));
+}
+
+#[test]
+fn lexicon_models_get_a_merged_lexicon() {
+    let home = MockHome::new(&[MELO]);
+    let model_dir = home.data_dir().join("models").join(MELO.0);
+    let lexicon = "bee b i\nbo b o\nhello h e l o\nsherpa x x\n";
+    fs::write(model_dir.join("lexicon.txt"), lexicon).unwrap();
+    fs::write(
+        model_dir.join("tokens.txt"),
+        ["b", "i", "o", "h", "e", "l", "sh", "er", "p", "a", "x"]
+            .iter()
+            .enumerate()
+            .map(|(id, token)| format!("{} {}\n", token, id))
+            .collect::<String>(),
+    )
+    .unwrap();
+    fs::write(home.path().join("pronunciations.toml"), PRONUNCIATIONS).unwrap();
+
+    assert!(speak(&home, "melo", "hello bibo and Jan").status.success());
+    let call = &home.calls()[0];
+    // Respellings found in the lexicon become entries; the rest are respelled
+    assert!(call.contains("hello bibo and yahn"), "{}", call);
+    let overlay = call
+        .split_whitespace()
+        .find_map(|arg| arg.strip_prefix("--vits-lexicon="))
+        .unwrap();
+    assert!(overlay.contains("/bibo/lexicons/"));
+    assert_eq!(
+        fs::read_to_string(overlay).unwrap(),
+        "bibo b i b o\nsherpa sh er p a\nbee b i\nbo b o\nhello h e l o\n"
+    );
+    assert_eq!(
+        fs::read_to_string(model_dir.join("lexicon.txt")).unwrap(),
+        lexicon
+    );
+
+    fs::write(
+        home.path().join("pronunciations.toml"),
+        "[en]\nbibo = { phonemes = \"b zz\" }\n",
+    )
+    .unwrap();
+    let output = speak(&home, "melo", "hello bibo");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("'zz'"));
+}
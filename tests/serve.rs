@@ -0,0 +1,174 @@
+//! Integration tests for `bibo serve` against a mock sherpa-onnx backend
+
+#![cfg(unix)]
+
+mod common;
+
+use common::{MockHome, Server, MOCK_SAMPLE_RATE};
+use serde_json::{json, Value};
+
+const RYAN: (&str, &str) = ("vits-piper-en_US-ryan-high", "en_US-ryan-high.onnx");
+
+fn start(home: &MockHome) -> Server {
+    let mut cmd = home.command();
+    cmd.args(["serve", "--port", "0", "-v", "ryan", "-q"]);
+    Server::spawn(cmd)
+}
+
+async fn post_speech(server: &Server, body: Value) -> reqwest::Response {
+    reqwest::Client::new()
+        .post(format!("http://{}/v1/audio/speech", server.addr))
+        .header("Content-Type", "application/json")
+        .body(body.to_string())
+        .send()
+        .await
+        .unwrap()
+}
+
+async fn get_json(server: &Server, path: &str) -> Value {
+    let body = reqwest::get(format!("http://{}{}", server.addr, path))
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    serde_json::from_str(&body).unwrap()
+}
+
+#[tokio::test]
+async fn health_reports_ok() {
+    let home = MockHome::new(&[RYAN]);
+    let server = start(&home);
+
+    let health = get_json(&server, "/health").await;
+    assert_eq!(health["status"], "ok");
+}
+
+#[tokio::test]
+async fn speech_returns_wav() {
+    let home = MockHome::new(&[RYAN]);
+    let server = start(&home);
+
+    let resp = post_speech(
+        &server,
+        json!({ "model": "tts-1", "input": "Build finished", "voice": "ryan" }),
+    )
+    .await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers()["content-type"], "audio/wav");
+    assert_eq!(
+        resp.headers()["x-sample-rate"],
+        MOCK_SAMPLE_RATE.to_string().as_str()
+    );
+
+    let bytes = resp.bytes().await.unwrap();
+    assert_eq!(&bytes[..4], b"RIFF");
+    assert_eq!(&bytes[8..12], b"WAVE");
+}
+
+#[tokio::test]
+async fn openai_voice_names_map_to_catalog() {
+    let home = MockHome::new(&[RYAN]);
+    let server = start(&home);
+
+    // "echo" maps to ryan
+    let resp = post_speech(&server, json!({ "input": "Hello", "voice": "echo" })).await;
+    assert_eq!(resp.status(), 200);
+    assert!(home.calls()[0].contains("en_US-ryan-high.onnx"));
+}
+
+#[tokio::test]
+async fn speed_maps_to_length_scale() {
+    let home = MockHome::new(&[RYAN]);
+    let server = start(&home);
+
+    let resp = post_speech(&server, json!({ "input": "Faster", "speed": 2.0 })).await;
+    assert_eq!(resp.status(), 200);
+    assert!(home.calls()[0].contains("--vits-length-scale=0.50"));
+}
+
+#[tokio::test]
+async fn pcm_format_returns_raw_samples() {
+    let home = MockHome::new(&[RYAN]);
+    let server = start(&home);
+
+    let resp = post_speech(
+        &server,
+        json!({ "input": "Raw please", "response_format": "pcm" }),
+    )
+    .await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers()["content-type"], "audio/pcm");
+    // Mock fixture is 2205 mono 16-bit samples
+    assert_eq!(resp.bytes().await.unwrap().len(), 2205 * 2);
+}
+
+#[tokio::test]
+async fn rejects_invalid_requests() {
+    let home = MockHome::new(&[RYAN]);
+    let server = start(&home);
+
+    let cases = [
+        (
+            json!({ "input": "x", "response_format": "mp3" }),
+            "response_format",
+        ),
+        (json!({ "input": "x", "voice": "nobody" }), "voice"),
+        (json!({ "input": "x", "speed": 9.0 }), "speed"),
+        (json!({ "input": "  " }), "input"),
+    ];
+
+    for (body, param) in cases {
+        let resp = post_speech(&server, body).await;
+        assert_eq!(resp.status(), 400);
+        let error: Value = serde_json::from_str(&resp.text().await.unwrap()).unwrap();
+        assert_eq!(error["error"]["param"], param);
+        assert_eq!(error["error"]["type"], "invalid_request_error");
+    }
+
+    // Known voice that isn't installed
+    let resp = post_speech(&server, json!({ "input": "x", "voice": "amy" })).await;
+    assert_eq!(resp.status(), 400);
+    assert!(home.calls().is_empty());
+}
+
+#[tokio::test]
+async fn lists_models_and_voices() {
+    let home = MockHome::new(&[RYAN]);
+    let server = start(&home);
+
+    let models = get_json(&server, "/v1/models").await;
+    let ids: Vec<_> = models["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["id"].as_str().unwrap().to_string())
+        .collect();
+    assert!(ids.contains(&"tts-1".to_string()));
+
+    let voices = get_json(&server, "/v1/voices").await;
+    let ryan = voices["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|v| v["id"] == "ryan")
+        .unwrap();
+    assert_eq!(ryan["installed"], true);
+    assert!(ryan["aliases"].as_array().unwrap().contains(&json!("echo")));
+}
+
+#[tokio::test]
+async fn no_cache_applies_to_pooled_engines() {
+    let home = MockHome::new(&[RYAN]);
+    let mut cmd = home.command();
+    cmd.args(["serve", "--port", "0", "-v", "ryan", "-q", "--no-cache"]);
+    let server = Server::spawn(cmd);
+
+    for _ in 0..2 {
+        let body = json!({ "model": "tts-1", "input": "Same words", "voice": "ryan" });
+        assert_eq!(post_speech(&server, body).await.status(), 200);
+    }
+    // Both requests synthesized; nothing was cached
+    assert_eq!(home.calls().len(), 2);
+    assert!(!home.path().join(".cache/bibo").exists());
+}
@@ -0,0 +1,165 @@
+//! speech-dispatcher module protocol tests, driving the sd_bibo binary
+
+#![cfg(unix)]
+
+mod common;
+
+use common::MockHome;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Stdio};
+
+const RYAN: (&str, &str) = ("vits-piper-en_US-ryan-high", "en_US-ryan-high.onnx");
+const AMY: (&str, &str) = ("vits-piper-en_US-amy-low", "en_US-amy-low.onnx");
+
+struct Module {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl Module {
+    #[allow(clippy::zombie_processes)] // reaped in Drop
+    fn start(home: &MockHome) -> Self {
+        let mut child = home
+            .command_for(env!("CARGO_BIN_EXE_sd_bibo"))
+            .arg("/etc/speech-dispatcher/modules/bibo.conf")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        let stdin = child.stdin.take().unwrap();
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+        Self {
+            child,
+            stdin,
+            stdout,
+        }
+    }
+
+    fn send(&mut self, lines: &[&str]) {
+        for line in lines {
+            writeln!(self.stdin, "{}", line).unwrap();
+        }
+        self.stdin.flush().unwrap();
+    }
+
+    fn line(&mut self) -> String {
+        let mut line = String::new();
+        self.stdout.read_line(&mut line).unwrap();
+        line.trim_end().to_string()
+    }
+
+    /// Read lines up to and including one that starts with `end`
+    fn until(&mut self, end: &str) -> Vec<String> {
+        let mut lines = vec![];
+        loop {
+            let line = self.line();
+            assert!(!line.is_empty(), "module closed stdout; got {:?}", lines);
+            let done = line.starts_with(end);
+            lines.push(line);
+            if done {
+                return lines;
+            }
+        }
+    }
+}
+
+impl Drop for Module {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[test]
+fn init_and_quit() {
+    let home = MockHome::new(&[RYAN]);
+    let mut module = Module::start(&home);
+
+    module.send(&["INIT"]);
+    assert_eq!(
+        module.until("299 "),
+        [
+            "299-bibo: Initialized successfully.",
+            "299 OK LOADED SUCCESSFULLY"
+        ]
+    );
+
+    module.send(&["AUDIO", "audio_output_method=libao", "."]);
+    assert_eq!(module.line(), "207 OK RECEIVING AUDIO SETTINGS");
+    assert_eq!(module.line(), "203 OK AUDIO INITIALIZED");
+
+    module.send(&["QUIT"]);
+    assert_eq!(module.line(), "210 OK QUIT");
+    assert!(module.child.wait().unwrap().success());
+}
+
+#[test]
+fn lists_installed_voices() {
+    let home = MockHome::new(&[RYAN, AMY]);
+    let mut module = Module::start(&home);
+
+    module.send(&["LIST VOICES"]);
+    let lines = module.until("200 OK");
+    assert!(lines.contains(&"200-ryan\ten-US\tnone".to_string()));
+    assert!(lines.contains(&"200-amy\ten-US\tnone".to_string()));
+    assert_eq!(lines.last().unwrap(), "200 OK VOICE LIST SENT");
+}
+
+#[test]
+fn speak_reports_marks_and_maps_rate() {
+    let home = MockHome::new(&[RYAN]);
+    let mut module = Module::start(&home);
+
+    module.send(&["SET", "rate=100", "synthesis_voice=ryan", "pitch=0", "."]);
+    assert_eq!(module.line(), "203 OK RECEIVING SETTINGS");
+    assert_eq!(module.line(), "203 OK SETTINGS RECEIVED");
+
+    module.send(&[
+        "SPEAK",
+        "<speak><mark name=\"m1\"/>Fish &amp; chips</speak>",
+        ".",
+    ]);
+    assert_eq!(module.line(), "202 OK RECEIVING MESSAGE");
+    assert_eq!(module.line(), "200 OK SPEAKING");
+    assert_eq!(module.line(), "701 BEGIN");
+    assert_eq!(module.line(), "700-m1");
+    assert_eq!(module.line(), "700 INDEX MARK");
+
+    // Without an audio device playback ends in STOP; with one, END
+    let end = module.line();
+    assert!(end == "702 END" || end == "703 STOP", "{}", end);
+
+    let calls = home.calls();
+    assert!(calls[0].contains("Fish and chips"));
+    // rate=100 maps to 4x speed
+    assert!(calls[0].contains("--vits-length-scale=0.25"));
+}
+
+#[test]
+fn picks_voice_by_language_and_gender() {
+    let home = MockHome::new(&[RYAN, AMY]);
+    let mut module = Module::start(&home);
+
+    module.send(&["SET", "voice=FEMALE1", "language=en-US", "."]);
+    module.until("203 OK SETTINGS");
+
+    module.send(&["SPEAK", "Hello", "."]);
+    let lines = module.until("70");
+    assert_eq!(lines.last().unwrap(), "701 BEGIN");
+    let end = module.line();
+    assert!(end.starts_with("70"), "{}", end);
+
+    let calls = home.calls();
+    assert!(calls[0].contains("amy"), "{}", calls[0]);
+}
+
+#[test]
+fn unknown_command_is_rejected() {
+    let home = MockHome::new(&[RYAN]);
+    let mut module = Module::start(&home);
+
+    module.send(&["FROBNICATE"]);
+    assert_eq!(module.line(), "300 ERR UNKNOWN COMMAND");
+}
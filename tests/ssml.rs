@@ -0,0 +1,185 @@
+//! SSML parsing and synthesis of SSML input
+
+mod common;
+
+use bibo::text::{ssml, Segment};
+use bibo::{AudioBuffer, BiboError};
+use common::{MockHome, MOCK_SAMPLE_RATE};
+use std::fs;
+
+const RYAN: (&str, &str) = ("vits-piper-en_US-ryan-high", "en_US-ryan-high.onnx");
+const AMY: (&str, &str) = ("vits-piper-en_US-amy-low", "en_US-amy-low.onnx");
+
+fn speech(text: &str) -> Segment {
+    Segment::speech(text)
+}
+
+#[test]
+fn breaks_and_structure_become_silences() {
+    let segments = ssml::parse(
+        r#"<?xml version="1.0"?>
+        <speak>
+          <p><s>Hello there.</s><s>Second &amp; last.</s></p>
+          <!-- comment -->
+          <p>Wait<break time="1.5s"/>for it<break strength="weak"/></p>
+        </speak>"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        segments,
+        vec![
+            speech("Hello there."),
+            Segment::Silence(0.3),
+            speech("Second & last."),
+            Segment::Silence(0.6),
+            speech("Wait"),
+            Segment::Silence(1.5),
+            speech("for it"),
+            Segment::Silence(0.25),
+        ]
+    );
+}
+
+#[test]
+fn long_breaks_are_capped() {
+    let segments = ssml::parse("<speak>A<break time='99999999s'/>B</speak>").unwrap();
+    assert_eq!(
+        segments,
+        vec![speech("A"), Segment::Silence(10.0), speech("B")]
+    );
+}
+
+#[test]
+fn quoted_attributes_may_contain_angle_brackets() {
+    let segments = ssml::parse(r#"<speak><sub alias="x>y">xy</sub></speak>"#).unwrap();
+    assert_eq!(segments, vec![speech("x>y")]);
+}
+
+#[test]
+fn prosody_and_voice_change_style() {
+    let segments = ssml::parse(
+        r#"<speak>Normal <prosody rate="slow">slower <prosody rate="200%">nested</prosody></prosody>
+        <voice name="amy" variant="2">Hi</voice></speak>"#,
+    )
+    .unwrap();
+
+    let style = |segment: &Segment| match segment {
+        Segment::Speech {
+            text,
+            voice,
+            speaker,
+            rate,
+        } => (text.clone(), voice.clone(), *speaker, *rate),
+        Segment::Silence(_) => panic!("unexpected silence"),
+    };
+    assert_eq!(style(&segments[0]), ("Normal".into(), None, None, 1.0));
+    assert_eq!(style(&segments[1]), ("slower".into(), None, None, 0.75));
+    assert_eq!(style(&segments[2]), ("nested".into(), None, None, 1.5));
+    assert_eq!(
+        style(&segments[3]),
+        ("Hi".into(), Some("amy".into()), Some(2), 1.0)
+    );
+}
+
+#[test]
+fn say_as_sub_and_phoneme_rewrite_text() {
+    let segments = ssml::parse(
+        r#"<speak>
+          <say-as interpret-as="characters">API</say-as>,
+          <say-as interpret-as="cardinal">12,345</say-as>,
+          <say-as interpret-as="date" format="mdy">3/5/2024</say-as>,
+          <say-as interpret-as="date">2024-12-22</say-as>,
+          <say-as interpret-as="telephone">+1 555-0123</say-as>,
+          <sub alias="World Wide Web">WWW</sub>,
+          <phoneme alphabet="ipa" ph="təˈmeɪtoʊ">tomato</phoneme>
+        </speak>"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        segments,
+        vec![speech(
+            "A P I, 12345, March 5th, 2024, December 22nd, 2024, \
+             plus, 1, 5 5 5, 0 1 2 3, World Wide Web, tomato"
+        )]
+    );
+}
+
+#[test]
+fn malformed_markup_is_rejected() {
+    for markup in [
+        "<speak>unclosed",
+        "<speak><p>crossed</speak></p>",
+        "<speak>stray</p>",
+        r#"<speak><break time="soon"/></speak>"#,
+        r#"<speak><prosody rate="fastest">x</prosody></speak>"#,
+        "<speak>&bogus;</speak>",
+        "<speak><say-as>x</say-as></speak>",
+    ] {
+        assert!(
+            matches!(ssml::parse(markup), Err(BiboError::InvalidSsml(_))),
+            "{}",
+            markup
+        );
+    }
+}
+
+#[test]
+fn ssml_file_renders_speech_and_pauses() {
+    let home = MockHome::new(&[RYAN, AMY]);
+    let input = home.path().join("script.ssml");
+    let output = home.path().join("out.wav");
+    fs::write(
+        &input,
+        r#"<speak>One<break time="1s"/><prosody rate="2">Two</prosody>
+        <voice name="amy">Three</voice></speak>"#,
+    )
+    .unwrap();
+
+    let status = home
+        .command()
+        .args(["-v", "ryan", "-q", "-i"])
+        .arg(&input)
+        .arg("-o")
+        .arg(&output)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let calls = home.calls();
+    assert_eq!(calls.len(), 3);
+    assert!(calls[1].contains("--vits-length-scale=0.50"));
+    assert!(calls[2].contains("en_US-amy-low.onnx"));
+
+    // Three 0.1s clips around one second of silence
+    let audio = AudioBuffer::load(&output).unwrap();
+    assert_eq!(audio.sample_rate, MOCK_SAMPLE_RATE);
+    assert_eq!(audio.samples.len(), 3 * 2205 + MOCK_SAMPLE_RATE as usize);
+}
+
+#[test]
+fn ssml_flag_parses_direct_text() {
+    let home = MockHome::new(&[RYAN]);
+    let output = home.path().join("out.wav");
+
+    let status = home
+        .command()
+        .args(["-v", "ryan", "-q", "--ssml", "-o"])
+        .arg(&output)
+        .arg(r#"<speak>Hi <sub alias="there">t</sub></speak>"#)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    assert!(home.calls()[0].contains("Hi there"));
+
+    let invalid = home
+        .command()
+        .args(["-v", "ryan", "-q", "--ssml", "-o"])
+        .arg(&output)
+        .arg("<speak>oops")
+        .output()
+        .unwrap();
+    assert!(!invalid.status.success());
+    assert!(String::from_utf8_lossy(&invalid.stderr).contains("Invalid SSML"));
+}
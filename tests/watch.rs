@@ -0,0 +1,101 @@
+//! Watch mode: edits re-render only what changed; inputs that can never
+//! change are refused up front
+
+mod common;
+
+use common::MockHome;
+use std::fs;
+use std::path::Path;
+use std::process::Child;
+use std::time::{Duration, Instant, SystemTime};
+
+const RYAN: (&str, &str) = ("vits-piper-en_US-ryan-high", "en_US-ryan-high.onnx");
+
+#[test]
+fn watch_rejects_stdin_and_missing_files() {
+    let home = MockHome::new(&[RYAN]);
+    let out = home.path().join("out.wav");
+    let missing = home.path().join("missing.md");
+
+    for input in ["-", missing.to_str().unwrap()] {
+        let output = home
+            .command()
+            .args(["-v", "ryan", "--watch", "-i", input, "-o"])
+            .arg(&out)
+            .output()
+            .unwrap();
+        assert!(
+            !output.status.success(),
+            "--watch -i {} kept polling",
+            input
+        );
+    }
+    assert!(home.calls().is_empty());
+}
+
+/// Poll until `done` holds, failing after a few seconds
+fn wait_until(what: &str, mut done: impl FnMut() -> bool) {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while !done() {
+        assert!(Instant::now() < deadline, "timed out waiting for {}", what);
+        std::thread::sleep(Duration::from_millis(50));
+    }
+}
+
+/// Watch process, killed when the test ends or fails
+struct Watcher(Child);
+
+impl Drop for Watcher {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[test]
+fn edits_resynthesize_only_changed_paragraphs() {
+    let home = MockHome::new(&[RYAN]);
+    let doc = home.path().join("doc.md");
+    let out = home.path().join("out.wav");
+    fs::write(
+        &doc,
+        "First paragraph.\n\nSecond paragraph.\n\nThird paragraph.\n",
+    )
+    .unwrap();
+
+    // No disk cache, so every render that is not reused calls the backend
+    let watcher = Watcher(
+        home.command()
+            .args(["-v", "ryan", "-q", "--no-cache", "--watch", "-i"])
+            .arg(&doc)
+            .arg("-o")
+            .arg(&out)
+            .spawn()
+            .unwrap(),
+    );
+
+    wait_until("the first render", || {
+        home.calls().len() == 3 && modified(&out).is_some()
+    });
+    let first = modified(&out).unwrap();
+
+    // Modification times need to move past the first write
+    std::thread::sleep(Duration::from_millis(1100));
+    fs::write(
+        &doc,
+        "First paragraph.\n\nSecond paragraph, edited.\n\nThird paragraph.\n",
+    )
+    .unwrap();
+
+    wait_until("the re-render", || modified(&out) > Some(first));
+    std::thread::sleep(Duration::from_millis(600));
+    let calls = home.calls();
+    drop(watcher);
+
+    assert_eq!(calls.len(), 4, "{:?}", calls);
+    assert!(calls[3].contains("Second paragraph, edited."));
+}
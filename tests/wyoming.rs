@@ -0,0 +1,212 @@
+//! Wyoming protocol tests using a minimal client stub
+
+#![cfg(unix)]
+
+mod common;
+
+use common::{MockHome, Server, MOCK_SAMPLE_RATE};
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+
+const RYAN: (&str, &str) = ("vits-piper-en_US-ryan-high", "en_US-ryan-high.onnx");
+const AISHELL3: (&str, &str) = ("vits-zh-aishell3", "vits-aishell3.onnx");
+
+/// Minimal Wyoming client: JSON header line + data + payload
+struct Client {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Client {
+    fn connect(server: &Server) -> Self {
+        let stream = TcpStream::connect(&server.addr).unwrap();
+        Self {
+            reader: BufReader::new(stream.try_clone().unwrap()),
+            writer: stream,
+        }
+    }
+
+    /// Send with data in a separate block, as the Python library does
+    fn send(&mut self, kind: &str, data: Value) {
+        let data = data.to_string();
+        let header = json!({ "type": kind, "data_length": data.len() });
+        writeln!(self.writer, "{}", header).unwrap();
+        self.writer.write_all(data.as_bytes()).unwrap();
+    }
+
+    fn recv(&mut self) -> (String, Value, Vec<u8>) {
+        let mut line = String::new();
+        self.reader.read_line(&mut line).unwrap();
+        let header: Value = serde_json::from_str(&line).unwrap();
+
+        let mut data = header.get("data").cloned().unwrap_or(json!({}));
+        if let Some(len) = header["data_length"].as_u64() {
+            let mut buf = vec![0; len as usize];
+            self.reader.read_exact(&mut buf).unwrap();
+            data = serde_json::from_slice(&buf).unwrap();
+        }
+
+        let mut payload = vec![0; header["payload_length"].as_u64().unwrap_or(0) as usize];
+        self.reader.read_exact(&mut payload).unwrap();
+
+        (header["type"].as_str().unwrap().to_string(), data, payload)
+    }
+
+    /// Collect events until audio-stop or error
+    fn recv_audio(&mut self) -> Vec<(String, Value, Vec<u8>)> {
+        let mut events = vec![];
+        loop {
+            let event = self.recv();
+            let done = event.0 == "audio-stop" || event.0 == "error";
+            events.push(event);
+            if done {
+                return events;
+            }
+        }
+    }
+}
+
+fn start(home: &MockHome) -> Server {
+    start_with(home, &[])
+}
+
+fn start_with(home: &MockHome, args: &[&str]) -> Server {
+    let mut cmd = home.command();
+    cmd.args([
+        "wyoming",
+        "--host",
+        "127.0.0.1",
+        "--port",
+        "0",
+        "-v",
+        "ryan",
+        "-q",
+    ])
+    .args(args);
+    Server::spawn(cmd)
+}
+
+#[test]
+fn describe_lists_installed_voices() {
+    let home = MockHome::new(&[RYAN, AISHELL3]);
+    let server = start(&home);
+    let mut client = Client::connect(&server);
+
+    client.send("describe", json!({}));
+    let (kind, data, _) = client.recv();
+    assert_eq!(kind, "info");
+
+    let voices = data["tts"][0]["voices"].as_array().unwrap();
+    assert_eq!(voices.len(), 2);
+
+    let ryan = voices.iter().find(|v| v["name"] == "ryan").unwrap();
+    assert_eq!(ryan["languages"], json!(["en_US"]));
+    assert!(ryan["speakers"].is_null());
+
+    let aishell3 = voices.iter().find(|v| v["name"] == "aishell3").unwrap();
+    assert_eq!(aishell3["speakers"].as_array().unwrap().len(), 174);
+}
+
+#[test]
+fn synthesize_streams_audio_per_sentence() {
+    let home = MockHome::new(&[RYAN]);
+    let server = start(&home);
+    let mut client = Client::connect(&server);
+
+    client.send(
+        "synthesize",
+        json!({ "text": "Front door opened. Garage is closed.", "voice": { "name": "ryan" } }),
+    );
+    let events = client.recv_audio();
+
+    assert_eq!(events[0].0, "audio-start");
+    assert_eq!(events[0].1["rate"], MOCK_SAMPLE_RATE);
+    assert_eq!(events[0].1["width"], 2);
+    assert_eq!(events.last().unwrap().0, "audio-stop");
+
+    // Two sentences, 2205 samples each from the mock backend
+    let audio_bytes: usize = events
+        .iter()
+        .filter(|(kind, _, _)| kind == "audio-chunk")
+        .map(|(_, _, payload)| payload.len())
+        .sum();
+    assert_eq!(audio_bytes, 2 * 2205 * 2);
+    assert_eq!(home.calls().len(), 2);
+}
+
+#[test]
+fn synthesize_uses_the_server_speed() {
+    let home = MockHome::new(&[RYAN]);
+    let server = start_with(&home, &["-s", "fast"]);
+    let mut client = Client::connect(&server);
+
+    client.send("synthesize", json!({ "text": "Hello." }));
+    client.recv_audio();
+
+    let calls = home.calls();
+    assert_eq!(calls.len(), 1);
+    assert!(
+        calls[0].contains("--vits-length-scale=0.80"),
+        "{}",
+        calls[0]
+    );
+}
+
+#[test]
+fn synthesize_selects_voice_by_language_and_speaker() {
+    let home = MockHome::new(&[RYAN, AISHELL3]);
+    let server = start(&home);
+    let mut client = Client::connect(&server);
+
+    client.send(
+        "synthesize",
+        json!({ "text": "你好", "voice": { "language": "zh_CN", "speaker": "12" } }),
+    );
+    let events = client.recv_audio();
+    assert_eq!(events.last().unwrap().0, "audio-stop");
+
+    let call = &home.calls()[0];
+    assert!(call.contains("vits-aishell3.onnx"));
+    assert!(call.contains("--sid=12"));
+}
+
+#[test]
+fn unknown_voice_returns_error_event() {
+    let home = MockHome::new(&[RYAN]);
+    let server = start(&home);
+    let mut client = Client::connect(&server);
+
+    client.send(
+        "synthesize",
+        json!({ "text": "Hi", "voice": { "name": "nobody" } }),
+    );
+    let (kind, data, _) = client.recv();
+    assert_eq!(kind, "error");
+    assert!(data["text"].as_str().unwrap().contains("nobody"));
+
+    // Connection stays usable after an error
+    client.send("describe", json!({}));
+    assert_eq!(client.recv().0, "info");
+}
+
+#[test]
+fn oversized_lengths_are_refused() {
+    let home = MockHome::new(&[RYAN]);
+    let server = start(&home);
+
+    for field in ["data_length", "payload_length"] {
+        let mut client = Client::connect(&server);
+        let header = json!({ "type": "describe", field: u64::MAX });
+        writeln!(client.writer, "{}", header).unwrap();
+
+        // The connection is dropped without reading a body
+        let mut line = String::new();
+        assert_eq!(client.reader.read_line(&mut line).unwrap_or(0), 0);
+    }
+
+    // The server keeps answering
+    let mut client = Client::connect(&server);
+    client.send("describe", json!({}));
+    assert_eq!(client.recv().0, "info");
+}